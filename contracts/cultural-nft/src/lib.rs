@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec, Symbol};
+use soroban_sdk::{contract, contractclient, contractimpl, contracttype, symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, String, Vec, Symbol, Val};
 
 /// Contrato para tokens de patrimonio cultural
 /// 
@@ -21,6 +21,118 @@ const OWNER_KEY: Symbol = symbol_short!("OWNER");
 const METADATA_KEY: Symbol = symbol_short!("METADATA");
 const PROVENANCE_KEY: Symbol = symbol_short!("PROV");
 const MUSEUM_REGISTRY_KEY: Symbol = symbol_short!("MUS_REG");
+const CULTURE_INDEX_KEY: Symbol = symbol_short!("CULT_IDX");
+const PERIOD_INDEX_KEY: Symbol = symbol_short!("PER_IDX");
+const GEO_CELL_KEY: Symbol = symbol_short!("GEO_CELL");
+const APPROVAL_KEY: Symbol = symbol_short!("APPROVAL");
+const OPERATOR_KEY: Symbol = symbol_short!("OPERATOR");
+const MULTI_ROYALTY_KEY: Symbol = symbol_short!("MROYALTY");
+const ROYALTY_SHARES_KEY: Symbol = symbol_short!("ROY_SHARE");
+const RESEARCHER_KEY: Symbol = symbol_short!("RESEARCH");
+const ATTESTATION_KEY: Symbol = symbol_short!("ATTEST");
+const DISPUTE_KEY: Symbol = symbol_short!("DISPUTE");
+const MINT_REQUEST_KEY: Symbol = symbol_short!("MINT_REQ");
+const MINT_REQUEST_COUNT_KEY: Symbol = symbol_short!("MINT_RCNT");
+const PENDING_MINTS_KEY: Symbol = symbol_short!("MINT_PEND");
+const MINT_REQUEST_TTL_KEY: Symbol = symbol_short!("MINT_TTL");
+const CONDITION_REPORT_KEY: Symbol = symbol_short!("COND_RPT");
+const CONTRIBUTORS_KEY: Symbol = symbol_short!("CONTRIB");
+const METADATA_VERSION_KEY: Symbol = symbol_short!("META_VER");
+const METADATA_VERSION_COUNT_KEY: Symbol = symbol_short!("META_VCNT");
+const TRANSFER_HOOKS_KEY: Symbol = symbol_short!("XFER_HOOK");
+const TAGS_KEY: Symbol = symbol_short!("TAGS");
+const TAG_INDEX_KEY: Symbol = symbol_short!("TAG_IDX");
+const SENSITIVE_KEY: Symbol = symbol_short!("SENSITIVE");
+const BURN_PROPOSAL_KEY: Symbol = symbol_short!("BURN_PROP");
+const BURN_WINDOW_KEY: Symbol = symbol_short!("BURN_WIN");
+const BURNED_KEY: Symbol = symbol_short!("BURNED");
+const TRANSFER_COOLDOWN_KEY: Symbol = symbol_short!("XFER_CD");
+const TRANSFER_UNLOCK_KEY: Symbol = symbol_short!("XFER_UNLK");
+const COOLDOWN_EXEMPT_KEY: Symbol = symbol_short!("CD_EXEMPT");
+const MAX_PROVENANCE_KEY: Symbol = symbol_short!("MAX_PROV");
+const PROVENANCE_ARCHIVE_KEY: Symbol = symbol_short!("PROV_ARCH");
+const PROVENANCE_ARCHIVE_COUNT_KEY: Symbol = symbol_short!("PROV_ACNT");
+const PROVENANCE_TYPE_COUNT_KEY: Symbol = symbol_short!("PROV_TCNT");
+const PREVIOUS_OWNER_KEY: Symbol = symbol_short!("PREV_OWNR");
+const EVENT_HOOKS_KEY: Symbol = symbol_short!("HOOK");
+const OWNER_HISTORY_KEY: Symbol = symbol_short!("OWNR_HIST");
+const MINT_FEE_KEY: Symbol = symbol_short!("MINT_FEE");
+const MINT_FEE_PAID_KEY: Symbol = symbol_short!("MINT_PAID");
+const RELATED_KEY: Symbol = symbol_short!("RELATED");
+const TRUSTED_FORWARDER_KEY: Symbol = symbol_short!("FORWARDER");
+const SIGNING_KEY: Symbol = symbol_short!("SIGN_KEY");
+const META_NONCE_KEY: Symbol = symbol_short!("META_NONC");
+const BENEFICIAL_OWNER_KEY: Symbol = symbol_short!("BENEF_OWN");
+const SOCIALFI_KEY: Symbol = symbol_short!("SOCIALFI");
+const CREATION_BONUS_KEY: Symbol = symbol_short!("CREAT_BON");
+const CURATION_POINTS_KEY: Symbol = symbol_short!("CURAT_PTS");
+const LOCALIZED_METADATA_KEY: Symbol = symbol_short!("LANG_MD");
+const LANGUAGES_KEY: Symbol = symbol_short!("LANGS");
+const ROYALTY_ESCALATION_KEY: Symbol = symbol_short!("ROY_ESC");
+const MUSEUM_INDEX_KEY: Symbol = symbol_short!("MUS_IDX");
+const MERGE_KEY: Symbol = symbol_short!("MERGE");
+const MERGE_COUNT_KEY: Symbol = symbol_short!("MERGE_CNT");
+const MERGE_LOCK_KEY: Symbol = symbol_short!("MRG_LOCK");
+const MERGED_FROM_KEY: Symbol = symbol_short!("MERGED_F");
+
+// Longitud máxima de las notas de procedencia
+const MAX_MEMO_LENGTH: u32 = 256;
+
+// Royalties: máximo de beneficiarios por obra, tope total (10%) y base de basis points
+const MAX_ROYALTY_RECIPIENTS: u32 = 10;
+const MAX_ROYALTY_BPS: u32 = 1_000;
+const BPS_DENOMINATOR: u32 = 10000;
+
+// Vigencia por defecto de una solicitud de minteo (30 días)
+const DEFAULT_MINT_REQUEST_TTL: u64 = 30 * 86400;
+
+// Máximo de solicitudes de minteo vigentes en la cola de pendientes
+const MAX_PENDING_MINT_REQUESTS: u32 = 100;
+
+// Máximo de tokens por transferencia en lote
+const MAX_BATCH_TRANSFER: u32 = 20;
+
+// Máximo de etiquetas temáticas por token
+const MAX_TAGS: u32 = 8;
+
+// Máximo de idiomas con metadatos traducidos por token
+const MAX_LANGUAGES: u32 = 20;
+
+// Máximo de relaciones (fragmentos, pares, copias) por token
+const MAX_RELATIONS: u32 = 10;
+
+// Máximo de contratos observadores de transferencias
+const MAX_TRANSFER_HOOKS: u32 = 5;
+
+// Eventos a los que se pueden suscribir contratos externos y máximo de suscriptores por evento
+const HOOK_EVENT_TYPES: [&str; 3] = ["mint", "transfer", "burn"];
+const MAX_HOOKS_PER_EVENT: u32 = 5;
+
+// Máximo de versiones de metadatos conservadas por token (las más antiguas se descartan)
+const MAX_METADATA_VERSIONS: u32 = 20;
+
+// Plazo por defecto para que el admin confirme una quema (7 días)
+const DEFAULT_BURN_WINDOW: u64 = 7 * 86400;
+
+// Procedencia: registros recientes por defecto en la entrada principal y registros
+// por página del archivo
+const DEFAULT_MAX_PROVENANCE_LENGTH: u32 = 100;
+const PROVENANCE_ARCHIVE_PAGE_SIZE: u32 = 50;
+
+// Tamaño máximo de página para consultas paginadas
+const MAX_PAGE_SIZE: u32 = 50;
+
+// Geolocalización: coordenadas en millonésimas de grado, celdas de 1 grado
+const GEO_CELL_SIZE: i64 = 1_000_000;
+const MAX_LATITUDE: i64 = 90_000_000;
+const MAX_LONGITUDE: i64 = 180_000_000;
+const KM_PER_DEGREE: i64 = 111;
+const MAX_SEARCH_RADIUS_KM: u32 = 200;
+const MAX_SEARCH_CELLS: i64 = 25; // Celdas leídas como máximo por búsqueda de cercanía
+
+// Coseno de la latitud en millonésimas cada 10 grados (0° a 90°): un grado de longitud
+// mide KM_PER_DEGREE * cos(lat)
+const COS_BY_TEN_DEGREES: [i64; 10] = [1_000_000, 984_808, 939_693, 866_025, 766_044, 642_788, 500_000, 342_020, 173_648, 0];
 
 // Estructura para metadatos culturales
 #[derive(Clone)]
@@ -35,6 +147,61 @@ pub struct CulturalMetadata {
     pub condition: String,
     pub significance: String,
     pub museum_address: Address,
+    pub location: ArtifactLocation,
+    pub is_soulbound: bool, // Certificado intransferible (solo el admin puede revocarlo)
+}
+
+// Ubicación física del artefacto (latitud, longitud en millonésimas de grado)
+//
+// Es un enum y no un `Option<(i64, i64)>` porque los campos Option de tuplas o structs no se
+// convierten a XDR en el SDK; `get_artifact_location` la expone como Option.
+#[derive(Clone)]
+#[contracttype]
+pub enum ArtifactLocation {
+    Unknown,
+    Coordinates(i64, i64),
+}
+
+// Estructura para aprobaciones de transferencia
+#[derive(Clone)]
+#[contracttype]
+pub struct Approval {
+    pub spender: Address,
+    pub expires_at: u64, // 0 = sin expiración
+}
+
+// Estructura para royalties compartidas entre varios creadores (formato anterior a RoyaltyShare)
+#[derive(Clone)]
+#[contracttype]
+pub struct MultiRoyaltyInfo {
+    pub recipients: Vec<Address>,
+    pub percentages: Vec<u32>, // En basis points (100 = 1%)
+}
+
+// Estructura para la parte de royalties de un beneficiario (museo, comunidad, fondo, etc.)
+#[derive(Clone)]
+#[contracttype]
+pub struct RoyaltyShare {
+    pub recipient: Address,
+    pub bps: u32, // En basis points (100 = 1%)
+}
+
+// Estructura para el escalado de royalties por reventa de un token
+#[derive(Clone)]
+#[contracttype]
+pub struct RoyaltyEscalation {
+    pub royalty_escalation_bps: u32, // Basis points sumados por cada reventa (10 = +0.1%)
+    pub max_royalty_bps: u32, // Tope del royalty efectivo
+}
+
+// Estructura para el resumen de royalties de un token de un museo
+#[derive(Clone)]
+#[contracttype]
+pub struct TokenRoyaltySummary {
+    pub token_id: u32,
+    pub royalty_bps: u32, // Suma de las partes configuradas
+    pub last_sale_price: i128, // Último valor declarado en la procedencia; 0 si nunca se vendió
+    pub expected_royalty: i128, // Royalty de una venta al último precio
 }
 
 // Estructura para información de procedencia
@@ -46,6 +213,150 @@ pub struct PROVENANCERecord {
     pub to: Address,
     pub transaction_type: String,
     pub notes: String,
+    pub declared_value: Option<i128>, // Precio de venta declarado (si aplica)
+}
+
+// Estructura para atestaciones de terceros sobre un registro de procedencia
+#[derive(Clone)]
+#[contracttype]
+pub struct ProvenanceAttestation {
+    pub attestor: Address,
+    pub evidence_uri: String,
+    pub timestamp: u64,
+}
+
+// Estructura para la restricción de metadatos de objetos sensibles (sagrados, funerarios)
+#[derive(Clone)]
+#[contracttype]
+pub struct SensitivityInfo {
+    pub set_by: Address,
+    pub justification: String,
+    pub set_at: u64,
+}
+
+// Estructura para disputas de propiedad (robo o titularidad impugnada)
+#[derive(Clone)]
+#[contracttype]
+pub struct DisputeInfo {
+    pub claimant: String,
+    pub case_reference: String,
+    pub flagged_at: u64,
+}
+
+// Estado de una solicitud de minteo
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum MintRequestStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Expired,
+}
+
+// Estructura para propuestas de quema pendientes de confirmación del admin
+#[derive(Clone)]
+#[contracttype]
+pub struct BurnProposal {
+    pub proposer: Address,
+    pub reason: String,
+    pub proposed_at: u64,
+    pub expires_at: u64,
+}
+
+// Estructura para propuestas de fusión de dos tokens en uno
+#[derive(Clone)]
+#[contracttype]
+pub struct MergeProposal {
+    pub owner: Address,
+    pub token_id_a: u32,
+    pub token_id_b: u32,
+    pub new_metadata: CulturalMetadata,
+    pub proposed_at: u64,
+}
+
+// Estructura para solicitudes de minteo propuestas por museos
+#[derive(Clone)]
+#[contracttype]
+pub struct MintRequest {
+    pub id: u32,
+    pub museum: Address,
+    pub metadata: CulturalMetadata,
+    pub provenance: Vec<PROVENANCERecord>,
+    pub status: MintRequestStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub token_id: u32, // Asignado al aprobar (0 mientras está pendiente)
+    pub reason: String, // Motivo del rechazo
+}
+
+// Estructura para la comisión de minteo pagada por los museos
+#[derive(Clone)]
+#[contracttype]
+pub struct MintFee {
+    pub token: Address,
+    pub amount: i128,
+    pub treasury: Address,
+}
+
+// Estructura para informes de estado de conservación
+#[derive(Clone)]
+#[contracttype]
+pub struct ConditionReport {
+    pub reporter: Address,
+    pub condition: String,
+    pub notes: String,
+    pub timestamp: u64,
+}
+
+// Registro de procedencia junto con su número de atestaciones
+#[derive(Clone)]
+#[contracttype]
+pub struct AttestedProvenanceRecord {
+    pub record: PROVENANCERecord,
+    pub attestation_count: u32,
+}
+
+// Interfaz del contrato MuseumRegistry usada para verificar museos
+#[contractclient(name = "MuseumRegistryClient")]
+pub trait MuseumRegistryInterface {
+    fn is_verified(env: Env, museum_address: Address) -> bool;
+    fn is_curator(env: Env, museum: Address, curator: Address) -> Option<CuratorRole>;
+    fn record_museum_activity(env: Env, peer: Address, museum_address: Address, kind: Symbol, amount: i128);
+}
+
+// Rol de un curador dentro de su museo (definido en MuseumRegistry)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum CuratorRole {
+    MetadataEditor,
+    Registrar,
+}
+
+// Interfaz del contrato SocialFi usada para recompensar creación y curaduría
+#[contractclient(name = "SocialFiClient")]
+pub trait SocialFiInterface {
+    fn issue_points(env: Env, issuer: Address, user: Address, points: i128, reason: String);
+}
+
+// Interfaz que deben implementar los contratos que reciben tokens vía safe_transfer
+#[contractclient(name = "NftReceiverClient")]
+pub trait NftReceiver {
+    fn on_nft_received(env: Env, operator: Address, from: Address, token_id: u32) -> bool;
+}
+
+// Interfaz de los contratos suscritos a eventos del token
+//
+// `data` es la cuenta relevante del evento: el receptor en "mint" y "transfer", y el
+// último propietario en "burn".
+#[contractclient(name = "EventHookClient")]
+pub trait EventHook {
+    fn on_event(env: Env, event_type: String, token_id: u32, data: Address);
+}
+
+// Interfaz de los contratos observadores notificados en cada transferencia
+#[contractclient(name = "TransferHookClient")]
+pub trait TransferHook {
+    fn on_transfer(env: Env, from: Address, to: Address, token_id: u32);
 }
 
 #[contractimpl]
@@ -56,7 +367,8 @@ impl CulturalNFT {
         admin: Address, 
         name: String, 
         symbol: String,
-        museum_registry: Address
+        museum_registry: Address,
+        socialfi: Option<Address>,
     ) {
         // Verificar que no esté ya inicializado
         if env.storage().instance().has(&ADMIN_KEY) {
@@ -68,6 +380,9 @@ impl CulturalNFT {
         env.storage().instance().set(&NAME_KEY, &name);
         env.storage().instance().set(&SYMBOL_KEY, &symbol);
         env.storage().instance().set(&MUSEUM_REGISTRY_KEY, &museum_registry);
+        if let Some(socialfi) = socialfi {
+            env.storage().instance().set(&SOCIALFI_KEY, &socialfi);
+        }
         
         // Inicializar contador de tokens
         env.storage().instance().set(&TOKEN_COUNT_KEY, &0u32);
@@ -85,31 +400,11 @@ impl CulturalNFT {
         let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
         admin.require_auth();
         
-        // Verificar que el token no existe
-        let owner_key = (OWNER_KEY, token_id);
-        if env.storage().persistent().has(&owner_key) {
-            panic!("Token already exists");
-        }
-        
-        // Verificar que el museo está verificado
-        let _museum_registry: Address = env.storage().instance().get(&MUSEUM_REGISTRY_KEY).unwrap();
-        // TODO: Llamar al contrato de registry para verificar museo
-        
-        // Asignar propietario
-        env.storage().persistent().set(&owner_key, &to);
-        
-        // Guardar metadatos culturales
-        let metadata_key = (METADATA_KEY, token_id);
-        env.storage().persistent().set(&metadata_key, &cultural_metadata);
-        
-        // Guardar procedencia
-        let provenance_key = (PROVENANCE_KEY, token_id);
-        env.storage().persistent().set(&provenance_key, &provenance);
-        
-        // Incrementar contador
-        let mut count: u32 = env.storage().instance().get(&TOKEN_COUNT_KEY).unwrap_or(0);
-        count += 1;
-        env.storage().instance().set(&TOKEN_COUNT_KEY, &count);
+        let museum = cultural_metadata.museum_address.clone();
+        Self::mint_token(&env, &to, token_id, cultural_metadata, provenance);
+        Self::record_contribution(&env, token_id, &admin, "mint");
+        Self::reward_content(&env, &museum, &CREATION_BONUS_KEY, "nft_minted");
+        Self::report_mint_activity(&env, &museum);
     }
 
     /// Obtiene el propietario de un token
@@ -120,12 +415,153 @@ impl CulturalNFT {
         })
     }
 
+    /// Obtiene el propietario real del token: si está en custodia (ej. subasta del
+    /// marketplace) devuelve el beneficiario registrado por el custodio
+    pub fn beneficial_owner(env: Env, token_id: u32) -> Address {
+        if let Some(beneficiary) = env.storage().persistent().get(&(BENEFICIAL_OWNER_KEY, token_id)) {
+            return beneficiary;
+        }
+        Self::owner_of(env, token_id)
+    }
+
+    /// Registra (o borra con None) el beneficiario de un token en custodia
+    /// (solo el propietario actual, es decir, el custodio)
+    ///
+    /// La anotación se elimina automáticamente cuando el token se transfiere.
+    pub fn set_beneficial_owner(env: Env, custodian: Address, token_id: u32, beneficiary: Option<Address>) {
+        // Verificar que el caller es el custodio
+        custodian.require_auth();
+        
+        if Self::owner_of(env.clone(), token_id) != custodian {
+            panic!("Not the owner");
+        }
+        
+        let beneficial_key = (BENEFICIAL_OWNER_KEY, token_id);
+        match beneficiary {
+            Some(beneficiary) => env.storage().persistent().set(&beneficial_key, &beneficiary),
+            None => env.storage().persistent().remove(&beneficial_key),
+        }
+    }
+
+    /// Obtiene el propietario anterior: el `from` de la última transferencia registrada
+    /// (None si el token nunca se transfirió)
+    pub fn previous_owner(env: Env, token_id: u32) -> Option<Address> {
+        env.storage().persistent().get(&(PREVIOUS_OWNER_KEY, token_id))
+    }
+
+    /// Obtiene la cadena de propietarios: (propietario, desde), del más antiguo al más
+    /// reciente (paginado). Tras una quema el último registro tiene propietario None.
+    pub fn get_owner_history(env: Env, token_id: u32, offset: u32, limit: u32) -> Vec<(Option<Address>, u64)> {
+        let history = Self::owner_history(&env, token_id);
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(history.len());
+        if offset >= end {
+            return Vec::new(&env);
+        }
+        history.slice(offset..end)
+    }
+
+    /// Obtiene el propietario de un token en un instante dado
+    /// (None antes del minteo o después de la quema)
+    pub fn get_owner_at(env: Env, token_id: u32, timestamp: u64) -> Option<Address> {
+        let history = Self::owner_history(&env, token_id);
+        
+        // Búsqueda binaria del último registro con `since <= timestamp`
+        let (mut low, mut high) = (0u32, history.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if history.get(mid).unwrap().1 <= timestamp {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        
+        if low == 0 {
+            return None;
+        }
+        history.get(low - 1).unwrap().0
+    }
+
     /// Obtiene metadatos culturales del token
+    ///
+    /// Si el token está marcado como sensible solo se exponen el título y el museo.
     pub fn get_cultural_metadata(env: Env, token_id: u32) -> CulturalMetadata {
-        let metadata_key = (METADATA_KEY, token_id);
-        env.storage().persistent().get(&metadata_key).unwrap_or_else(|| {
-            panic!("Token metadata not found");
-        })
+        let metadata = Self::load_metadata(&env, token_id);
+        if Self::is_sensitive(env.clone(), token_id) {
+            return Self::redact_metadata(&env, &metadata);
+        }
+        metadata
+    }
+
+    /// Obtiene los metadatos completos de un token, incluso si es sensible
+    /// (solo el propietario o el museo del token)
+    pub fn get_cultural_metadata_full(env: Env, requester: Address, token_id: u32) -> CulturalMetadata {
+        // Verificar que el caller es el propietario o el museo
+        requester.require_auth();
+        
+        let metadata = Self::load_metadata(&env, token_id);
+        if requester != metadata.museum_address && requester != Self::owner_of(env.clone(), token_id) {
+            panic!("Not authorized to read full metadata");
+        }
+        
+        metadata
+    }
+
+    /// Marca o desmarca un token como culturalmente sensible (museo del token o admin)
+    pub fn set_sensitive(env: Env, caller: Address, token_id: u32, sensitive: bool, justification: String) {
+        // Verificar que el caller es el museo del token o el admin
+        caller.require_auth();
+        
+        let metadata = Self::load_metadata(&env, token_id);
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        if caller != metadata.museum_address && caller != admin {
+            panic!("Not the token museum or admin");
+        }
+        
+        if justification.is_empty() {
+            panic!("Justification required");
+        }
+        
+        let sensitive_key = (SENSITIVE_KEY, token_id);
+        let was_sensitive = env.storage().persistent().has(&sensitive_key);
+        if sensitive {
+            let info = SensitivityInfo {
+                set_by: caller.clone(),
+                justification,
+                set_at: env.ledger().timestamp(),
+            };
+            env.storage().persistent().set(&sensitive_key, &info);
+        } else {
+            env.storage().persistent().remove(&sensitive_key);
+        }
+        
+        // Un objeto sensible sale de los índices de cultura, período y geográfico para que
+        // no se pueda localizar por los atributos que la lectura pública oculta
+        if sensitive && !was_sensitive {
+            Self::remove_from_index(&env, &(CULTURE_INDEX_KEY, metadata.culture.clone()), token_id);
+            Self::remove_from_index(&env, &(PERIOD_INDEX_KEY, metadata.period.clone()), token_id);
+            if let ArtifactLocation::Coordinates(lat, lon) = metadata.location {
+                Self::remove_from_geo_cell(&env, token_id, lat, lon);
+            }
+        } else if !sensitive && was_sensitive {
+            Self::add_to_index(&env, &(CULTURE_INDEX_KEY, metadata.culture.clone()), token_id);
+            Self::add_to_index(&env, &(PERIOD_INDEX_KEY, metadata.period.clone()), token_id);
+            if let ArtifactLocation::Coordinates(lat, lon) = metadata.location {
+                Self::add_to_geo_cell(&env, token_id, lat, lon);
+            }
+        }
+        
+        Self::record_contribution(&env, token_id, &caller, "sensitivity");
+    }
+
+    /// Verifica si un token está marcado como sensible
+    pub fn is_sensitive(env: Env, token_id: u32) -> bool {
+        env.storage().persistent().has(&(SENSITIVE_KEY, token_id))
+    }
+
+    /// Obtiene quién marcó el token como sensible y por qué
+    pub fn get_sensitivity_info(env: Env, token_id: u32) -> Option<SensitivityInfo> {
+        env.storage().persistent().get(&(SENSITIVE_KEY, token_id))
     }
 
     /// Obtiene la procedencia del token
@@ -136,120 +572,1746 @@ impl CulturalNFT {
         })
     }
 
-    /// Transfiere el token
-    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
-        // Verificar que el caller es el propietario
-        from.require_auth();
-        
-        // Verificar que el token existe
-        let owner_key = (OWNER_KEY, token_id);
-        let current_owner: Address = env.storage().persistent().get(&owner_key).unwrap_or_else(|| {
-            panic!("Token does not exist");
+    /// Obtiene los tokens de una cultura (paginado)
+    pub fn get_tokens_by_culture(env: Env, culture: String, start: u32, limit: u32) -> Vec<u32> {
+        let index: Vec<u32> = env.storage().persistent().get(&(CULTURE_INDEX_KEY, culture)).unwrap_or_else(|| {
+            Vec::new(&env)
         });
-        
-        // Verificar que el caller es el propietario actual
-        if current_owner != from {
-            panic!("Not the owner");
-        }
-        
-        // Transferir token
-        env.storage().persistent().set(&owner_key, &to);
-        
-        // Agregar registro de procedencia
-        let provenance_key = (PROVENANCE_KEY, token_id);
-        let mut provenance: Vec<PROVENANCERecord> = env.storage().persistent().get(&provenance_key).unwrap_or_else(|| {
+        Self::paginate(&env, &index, start, limit)
+    }
+
+    /// Obtiene los tokens minteados por un museo (paginado)
+    pub fn get_tokens_by_museum(env: Env, museum: Address, start: u32, limit: u32) -> Vec<u32> {
+        let index: Vec<u32> = env.storage().persistent().get(&(MUSEUM_INDEX_KEY, museum)).unwrap_or_else(|| {
             Vec::new(&env)
         });
-        
-        let new_record = PROVENANCERecord {
-            date: env.ledger().timestamp(),
-            from: from.clone(),
-            to: to.clone(),
-            transaction_type: String::from_str(&env, "transfer"),
-            notes: String::from_str(&env, "Direct transfer"),
-        };
-        
-        provenance.push_back(new_record);
-        env.storage().persistent().set(&provenance_key, &provenance);
+        Self::paginate(&env, &index, start, limit)
     }
 
-    /// Aprueba una transferencia (para marketplace)
-    pub fn approve(env: Env, from: Address, to: Address, token_id: u32) {
-        // Verificar que el caller es el propietario
-        from.require_auth();
+    /// Obtiene los tokens de un período (paginado)
+    pub fn get_tokens_by_period(env: Env, period: String, start: u32, limit: u32) -> Vec<u32> {
+        let index: Vec<u32> = env.storage().persistent().get(&(PERIOD_INDEX_KEY, period)).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        Self::paginate(&env, &index, start, limit)
+    }
+
+    /// Actualiza la ubicación física del artefacto (solo el museo que lo minteó)
+    pub fn update_artifact_location(env: Env, museum: Address, token_id: u32, lat: i64, lon: i64) {
+        // Verificar que el caller es el museo
+        museum.require_auth();
         
-        // Verificar que el token existe y es del propietario
-        let owner_key = (OWNER_KEY, token_id);
-        let current_owner: Address = env.storage().persistent().get(&owner_key).unwrap_or_else(|| {
-            panic!("Token does not exist");
+        let metadata_key = (METADATA_KEY, token_id);
+        let mut metadata: CulturalMetadata = env.storage().persistent().get(&metadata_key).unwrap_or_else(|| {
+            panic!("Token metadata not found");
         });
         
-        if current_owner != from {
-            panic!("Not the owner");
+        if metadata.museum_address != museum {
+            panic!("Not the token museum");
         }
         
-        // Guardar aprobación
-        let approval_key = (symbol_short!("APPROVAL"), token_id);
-        env.storage().persistent().set(&approval_key, &to);
+        Self::validate_coordinates(lat, lon);
+        
+        // Mover el token de celda (los sensibles no se indexan)
+        if let ArtifactLocation::Coordinates(old_lat, old_lon) = metadata.location {
+            Self::remove_from_geo_cell(&env, token_id, old_lat, old_lon);
+        }
+        if !Self::is_sensitive(env.clone(), token_id) {
+            Self::add_to_geo_cell(&env, token_id, lat, lon);
+        }
+        
+        metadata.location = ArtifactLocation::Coordinates(lat, lon);
+        Self::save_metadata(&env, token_id, &metadata);
+        
+        Self::record_contribution(&env, token_id, &museum, "location");
     }
 
-    /// Transfiere desde una dirección aprobada
-    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
-        // Verificar que el spender está autorizado
-        spender.require_auth();
+    /// Actualiza los metadatos culturales del token (museo que lo minteó o su curador MetadataEditor)
+    ///
+    /// El museo, la ubicación y el carácter intransferible se conservan; la ubicación se cambia
+    /// con `update_artifact_location`.
+    pub fn update_cultural_metadata(env: Env, caller: Address, token_id: u32, cultural_metadata: CulturalMetadata) {
+        // Verificar que el caller es el museo o uno de sus curadores
+        caller.require_auth();
         
-        // Verificar que el token existe
-        let owner_key = (OWNER_KEY, token_id);
-        let current_owner: Address = env.storage().persistent().get(&owner_key).unwrap_or_else(|| {
-            panic!("Token does not exist");
+        let metadata_key = (METADATA_KEY, token_id);
+        let current: CulturalMetadata = env.storage().persistent().get(&metadata_key).unwrap_or_else(|| {
+            panic!("Token metadata not found");
         });
         
-        if current_owner != from {
-            panic!("Not the owner");
-        }
+        Self::ensure_museum_or_curator(&env, &caller, &current.museum_address, CuratorRole::MetadataEditor);
         
-        // Verificar aprobación
-        let approval_key = (symbol_short!("APPROVAL"), token_id);
-        let approved: Address = env.storage().persistent().get(&approval_key).unwrap_or_else(|| {
-            panic!("Not approved");
-        });
+        let mut metadata = cultural_metadata;
+        metadata.museum_address = current.museum_address.clone();
+        metadata.location = current.location.clone();
+        metadata.is_soulbound = current.is_soulbound;
         
-        if approved != spender {
-            panic!("Not approved");
+        // Reindexar si cambió la cultura o el período (los sensibles no se indexan)
+        let indexed = !Self::is_sensitive(env.clone(), token_id);
+        if indexed && metadata.culture != current.culture {
+            Self::remove_from_index(&env, &(CULTURE_INDEX_KEY, current.culture.clone()), token_id);
+            Self::add_to_index(&env, &(CULTURE_INDEX_KEY, metadata.culture.clone()), token_id);
+        }
+        if indexed && metadata.period != current.period {
+            Self::remove_from_index(&env, &(PERIOD_INDEX_KEY, current.period.clone()), token_id);
+            Self::add_to_index(&env, &(PERIOD_INDEX_KEY, metadata.period.clone()), token_id);
         }
         
-        // Transferir token
-        env.storage().persistent().set(&owner_key, &to);
+        Self::save_metadata(&env, token_id, &metadata);
         
-        // Limpiar aprobación
-        env.storage().persistent().remove(&approval_key);
+        Self::record_contribution(&env, token_id, &caller, "metadata");
+    }
+
+    /// Agrega un informe de estado de conservación (museo que lo minteó o su curador MetadataEditor)
+    pub fn add_condition_report(env: Env, caller: Address, token_id: u32, condition: String, notes: String) {
+        // Verificar que el caller es el museo o uno de sus curadores
+        caller.require_auth();
         
-        // Agregar registro de procedencia
-        let provenance_key = (PROVENANCE_KEY, token_id);
-        let mut provenance: Vec<PROVENANCERecord> = env.storage().persistent().get(&provenance_key).unwrap_or_else(|| {
+        let metadata_key = (METADATA_KEY, token_id);
+        let mut metadata: CulturalMetadata = env.storage().persistent().get(&metadata_key).unwrap_or_else(|| {
+            panic!("Token metadata not found");
+        });
+        
+        Self::ensure_museum_or_curator(&env, &caller, &metadata.museum_address, CuratorRole::MetadataEditor);
+        
+        let report_key = (CONDITION_REPORT_KEY, token_id);
+        let mut reports: Vec<ConditionReport> = env.storage().persistent().get(&report_key).unwrap_or_else(|| {
             Vec::new(&env)
         });
+        reports.push_back(ConditionReport {
+            reporter: caller.clone(),
+            condition: condition.clone(),
+            notes,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&report_key, &reports);
         
-        let new_record = PROVENANCERecord {
-            date: env.ledger().timestamp(),
-            from: from.clone(),
-            to: to.clone(),
-            transaction_type: String::from_str(&env, "transfer_from"),
-            notes: String::from_str(&env, "Approved transfer"),
-        };
+        // El último informe define el estado actual
+        metadata.condition = condition;
+        Self::save_metadata(&env, token_id, &metadata);
         
-        provenance.push_back(new_record);
-        env.storage().persistent().set(&provenance_key, &provenance);
+        Self::record_contribution(&env, token_id, &caller, "condition_report");
+        Self::reward_content(&env, &caller, &CURATION_POINTS_KEY, "curation");
     }
 
-    /// Obtiene el nombre del token
-    pub fn name(env: Env) -> String {
-        env.storage().instance().get(&NAME_KEY).unwrap()
+    /// Obtiene los informes de estado de conservación de un token
+    pub fn get_condition_reports(env: Env, token_id: u32) -> Vec<ConditionReport> {
+        env.storage().persistent().get(&(CONDITION_REPORT_KEY, token_id)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
     }
 
-    /// Obtiene el símbolo del token
-    pub fn symbol(env: Env) -> String {
-        env.storage().instance().get(&SYMBOL_KEY).unwrap()
+    /// Obtiene los metadatos tal como estaban en una versión (1 = versión del minteo)
+    pub fn get_metadata_version(env: Env, token_id: u32, version: u32) -> CulturalMetadata {
+        let count = Self::get_metadata_version_count(env.clone(), token_id);
+        if version == 0 || version > count {
+            panic!("Metadata version not found");
+        }
+        
+        let metadata: CulturalMetadata = env.storage().persistent().get(&(METADATA_VERSION_KEY, token_id, version)).unwrap_or_else(|| {
+            panic!("Metadata version pruned");
+        });
+        if Self::is_sensitive(env.clone(), token_id) {
+            return Self::redact_metadata(&env, &metadata);
+        }
+        metadata
+    }
+
+    /// Obtiene el número de versiones de metadatos de un token
+    pub fn get_metadata_version_count(env: Env, token_id: u32) -> u32 {
+        env.storage().persistent().get(&(METADATA_VERSION_COUNT_KEY, token_id)).unwrap_or(0)
+    }
+
+    /// Obtiene la cadena de atribución: (contribuyente, tipo de contribución, timestamp)
+    pub fn get_contributors(env: Env, token_id: u32) -> Vec<(Address, String, u64)> {
+        env.storage().persistent().get(&(CONTRIBUTORS_KEY, token_id)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Obtiene el número de contribuciones registradas para un token
+    pub fn get_contribution_count(env: Env, token_id: u32) -> u32 {
+        Self::get_contributors(env, token_id).len()
+    }
+
+    /// Asigna las etiquetas temáticas del token (museo que lo minteó o su curador MetadataEditor)
+    ///
+    /// Reemplaza las etiquetas anteriores y reconcilia los índices por etiqueta.
+    pub fn set_tags(env: Env, caller: Address, token_id: u32, tags: Vec<Symbol>) {
+        // Verificar que el caller es el museo o uno de sus curadores
+        caller.require_auth();
+        
+        let metadata = Self::load_metadata(&env, token_id);
+        Self::ensure_museum_or_curator(&env, &caller, &metadata.museum_address, CuratorRole::MetadataEditor);
+        
+        if tags.len() > MAX_TAGS {
+            panic!("Too many tags");
+        }
+        for i in 0..tags.len() {
+            if tags.first_index_of(tags.get(i).unwrap()) != Some(i) {
+                panic!("Duplicate tag");
+            }
+        }
+        
+        // Quitar el token de las etiquetas que ya no tiene
+        let old_tags = Self::get_tags(env.clone(), token_id);
+        for tag in old_tags.iter() {
+            if !tags.contains(&tag) {
+                Self::remove_from_index(&env, &(TAG_INDEX_KEY, tag), token_id);
+            }
+        }
+        
+        // Agregar el token a las etiquetas nuevas
+        for tag in tags.iter() {
+            if !old_tags.contains(&tag) {
+                Self::add_to_index(&env, &(TAG_INDEX_KEY, tag), token_id);
+            }
+        }
+        
+        env.storage().persistent().set(&(TAGS_KEY, token_id), &tags);
+        
+        Self::record_contribution(&env, token_id, &caller, "tags");
+    }
+
+    /// Obtiene las etiquetas temáticas de un token
+    pub fn get_tags(env: Env, token_id: u32) -> Vec<Symbol> {
+        env.storage().persistent().get(&(TAGS_KEY, token_id)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Obtiene los tokens con una etiqueta (paginado)
+    pub fn get_tokens_by_tag(env: Env, tag: Symbol, offset: u32, limit: u32) -> Vec<u32> {
+        let index: Vec<u32> = env.storage().persistent().get(&(TAG_INDEX_KEY, tag)).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        Self::paginate(&env, &index, offset, limit)
+    }
+
+    /// Guarda el título y la descripción de un token en otro idioma (museo del token
+    /// o su curador MetadataEditor); un título y descripción vacíos borran la traducción
+    ///
+    /// `lang_code` es el código ISO 639-1 del idioma reducido a u32 por el cliente.
+    pub fn set_localized_metadata(
+        env: Env,
+        caller: Address,
+        token_id: u32,
+        lang_code: u32,
+        title: String,
+        description: String,
+    ) {
+        // Verificar que el caller es el museo o uno de sus curadores
+        caller.require_auth();
+        
+        let metadata = Self::load_metadata(&env, token_id);
+        Self::ensure_museum_or_curator(&env, &caller, &metadata.museum_address, CuratorRole::MetadataEditor);
+        
+        let localized_key = (LOCALIZED_METADATA_KEY, token_id, lang_code);
+        let languages_key = (LANGUAGES_KEY, token_id);
+        let mut languages = Self::get_metadata_languages(env.clone(), token_id);
+        
+        if title.is_empty() && description.is_empty() {
+            if let Some(position) = languages.first_index_of(lang_code) {
+                languages.remove(position);
+                env.storage().persistent().set(&languages_key, &languages);
+            }
+            env.storage().persistent().remove(&localized_key);
+            return;
+        }
+        
+        if !languages.contains(lang_code) {
+            if languages.len() >= MAX_LANGUAGES {
+                panic!("Too many languages");
+            }
+            languages.push_back(lang_code);
+            env.storage().persistent().set(&languages_key, &languages);
+        }
+        env.storage().persistent().set(&localized_key, &(title, description));
+        
+        Self::record_contribution(&env, token_id, &caller, "localization");
+    }
+
+    /// Obtiene la traducción de un token en un idioma (None si no existe)
+    ///
+    /// En tokens sensibles la descripción se omite, igual que en los metadatos por defecto.
+    pub fn get_localized_metadata(env: Env, token_id: u32, lang_code: u32) -> Option<(String, String)> {
+        let localized: Option<(String, String)> = env.storage().persistent().get(&(LOCALIZED_METADATA_KEY, token_id, lang_code));
+        localized.map(|(title, description)| {
+            if Self::is_sensitive(env.clone(), token_id) {
+                (title, String::from_str(&env, ""))
+            } else {
+                (title, description)
+            }
+        })
+    }
+
+    /// Obtiene título y descripción en un idioma, o el título y la significancia de los
+    /// metadatos por defecto si no hay traducción
+    pub fn get_display_metadata(env: Env, token_id: u32, lang_code: u32) -> (String, String) {
+        if let Some(localized) = Self::get_localized_metadata(env.clone(), token_id, lang_code) {
+            return localized;
+        }
+        let metadata = Self::get_cultural_metadata(env, token_id);
+        (metadata.title, metadata.significance)
+    }
+
+    /// Obtiene los idiomas con traducción de un token
+    pub fn get_metadata_languages(env: Env, token_id: u32) -> Vec<u32> {
+        env.storage().persistent().get(&(LANGUAGES_KEY, token_id)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Relaciona dos tokens (ej. fragmentos de una pieza, pares, original y réplica)
+    ///
+    /// Puede hacerlo el admin o el museo de ambos tokens. La relación es bidireccional.
+    pub fn link_tokens(env: Env, caller: Address, token_a: u32, token_b: u32, relation: Symbol) {
+        Self::ensure_can_link(&env, &caller, token_a, token_b);
+        
+        let mut related_a = Self::get_related_tokens(env.clone(), token_a);
+        let mut related_b = Self::get_related_tokens(env.clone(), token_b);
+        
+        // Verificar que no están ya relacionados
+        if related_a.iter().any(|(token_id, _)| token_id == token_b) {
+            panic!("Tokens already linked");
+        }
+        
+        if related_a.len() >= MAX_RELATIONS || related_b.len() >= MAX_RELATIONS {
+            panic!("Too many relations");
+        }
+        
+        related_a.push_back((token_b, relation.clone()));
+        related_b.push_back((token_a, relation));
+        env.storage().persistent().set(&(RELATED_KEY, token_a), &related_a);
+        env.storage().persistent().set(&(RELATED_KEY, token_b), &related_b);
+    }
+
+    /// Elimina la relación entre dos tokens (admin o museo de ambos tokens)
+    pub fn unlink_tokens(env: Env, caller: Address, token_a: u32, token_b: u32) {
+        Self::ensure_can_link(&env, &caller, token_a, token_b);
+        
+        for (token_id, other) in [(token_a, token_b), (token_b, token_a)] {
+            let mut related = Self::get_related_tokens(env.clone(), token_id);
+            let position = related.iter().position(|(related_id, _)| related_id == other).unwrap_or_else(|| {
+                panic!("Tokens not linked");
+            });
+            related.remove(position as u32);
+            env.storage().persistent().set(&(RELATED_KEY, token_id), &related);
+        }
+    }
+
+    /// Obtiene los tokens relacionados: (token, tipo de relación)
+    pub fn get_related_tokens(env: Env, token_id: u32) -> Vec<(u32, Symbol)> {
+        env.storage().persistent().get(&(RELATED_KEY, token_id)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Obtiene la ubicación de un artefacto, o None si no tiene o es sensible
+    pub fn get_artifact_location(env: Env, token_id: u32) -> Option<(i64, i64)> {
+        if Self::is_sensitive(env.clone(), token_id) {
+            return None;
+        }
+        match Self::load_metadata(&env, token_id).location {
+            ArtifactLocation::Coordinates(lat, lon) => Some((lat, lon)),
+            ArtifactLocation::Unknown => None,
+        }
+    }
+
+    /// Obtiene los tokens cercanos a unas coordenadas (aproximación por bounding box)
+    ///
+    /// La caja se ensancha en longitud según la latitud y cruza el antimeridiano; si cubre
+    /// más de `MAX_SEARCH_CELLS` celdas la búsqueda se rechaza.
+    pub fn get_tokens_near(env: Env, lat: i64, lon: i64, radius_km: u32) -> Vec<u32> {
+        Self::validate_coordinates(lat, lon);
+        
+        if radius_km > MAX_SEARCH_RADIUS_KM {
+            panic!("Radius too large");
+        }
+        
+        // Convertir el radio a grados (1 grado de latitud ≈ 111 km)
+        let delta = (radius_km as i64) * GEO_CELL_SIZE / KM_PER_DEGREE;
+        let min_lat = (lat - delta).max(-MAX_LATITUDE);
+        let max_lat = (lat + delta).min(MAX_LATITUDE);
+        
+        // Los grados de longitud se acortan hacia los polos: escalar con el coseno del borde
+        // más cercano al polo para que la caja cubra el radio completo
+        let cos = Self::cos_millionths(min_lat.abs().max(max_lat.abs()));
+        let lon_delta = if cos == 0 {
+            MAX_LONGITUDE
+        } else {
+            (delta * 1_000_000 / cos).min(MAX_LONGITUDE)
+        };
+        
+        let first_lon_cell = (lon - lon_delta).div_euclid(GEO_CELL_SIZE);
+        let last_lon_cell = (lon + lon_delta).div_euclid(GEO_CELL_SIZE);
+        let lat_cells = max_lat.div_euclid(GEO_CELL_SIZE) - min_lat.div_euclid(GEO_CELL_SIZE) + 1;
+        if lat_cells * (last_lon_cell - first_lon_cell + 1) > MAX_SEARCH_CELLS {
+            panic!("Search area too large");
+        }
+        
+        let mut result = Vec::new(&env);
+        for cell_lat in min_lat.div_euclid(GEO_CELL_SIZE)..=max_lat.div_euclid(GEO_CELL_SIZE) {
+            for lon_cell in first_lon_cell..=last_lon_cell {
+                let cell: Vec<(u32, i64, i64)> = env.storage().persistent().get(&(GEO_CELL_KEY, cell_lat, Self::wrap_lon_cell(lon_cell))).unwrap_or_else(|| {
+                    Vec::new(&env)
+                });
+                
+                for (token_id, token_lat, token_lon) in cell.iter() {
+                    if token_lat >= min_lat && token_lat <= max_lat && Self::lon_distance(token_lon, lon) <= lon_delta {
+                        result.push_back(token_id);
+                        if result.len() >= MAX_PAGE_SIZE {
+                            return result;
+                        }
+                    }
+                }
+            }
+        }
+        
+        result
+    }
+
+    /// Configura los beneficiarios de royalties de un token (solo admin)
+    ///
+    /// La suma de las partes no puede superar `MAX_ROYALTY_BPS`.
+    pub fn set_royalty_shares(env: Env, token_id: u32, shares: Vec<RoyaltyShare>) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if !Self::exists(env.clone(), token_id) {
+            panic!("Token does not exist");
+        }
+        
+        if shares.is_empty() {
+            panic!("No royalty recipients");
+        }
+        
+        if shares.len() > MAX_ROYALTY_RECIPIENTS {
+            panic!("Too many royalty recipients");
+        }
+        
+        let mut total: u32 = 0;
+        for share in shares.iter() {
+            if share.bps == 0 {
+                panic!("Royalty share must be positive");
+            }
+            total += share.bps;
+        }
+        if total > MAX_ROYALTY_BPS {
+            panic!("Royalty shares exceed maximum");
+        }
+        
+        env.storage().persistent().set(&(ROYALTY_SHARES_KEY, token_id), &shares);
+        env.storage().persistent().remove(&(MULTI_ROYALTY_KEY, token_id));
+    }
+
+    /// Configura royalties compartidas para obras colaborativas (solo admin)
+    ///
+    /// Envoltorio de `set_royalty_shares` que se mantiene por compatibilidad: cada
+    /// destinatario recibe el porcentaje de la misma posición, con el mismo tope total.
+    pub fn set_multi_royalty_info(
+        env: Env,
+        token_id: u32,
+        recipients: Vec<Address>,
+        percentages: Vec<u32>,
+    ) {
+        if recipients.len() != percentages.len() {
+            panic!("Recipients and percentages mismatch");
+        }
+        
+        let mut shares = Vec::new(&env);
+        for (recipient, bps) in recipients.iter().zip(percentages.iter()) {
+            shares.push_back(RoyaltyShare { recipient, bps });
+        }
+        Self::set_royalty_shares(env, token_id, shares);
+    }
+
+    /// Obtiene los beneficiarios de royalties de un token (vacío si no tiene)
+    ///
+    /// Los tokens configurados con el formato anterior (`MultiRoyaltyInfo`) se leen
+    /// convertidos hasta que se vuelvan a configurar.
+    pub fn get_royalty_shares(env: Env, token_id: u32) -> Vec<RoyaltyShare> {
+        if let Some(shares) = env.storage().persistent().get(&(ROYALTY_SHARES_KEY, token_id)) {
+            return shares;
+        }
+        
+        let mut shares = Vec::new(&env);
+        let legacy: Option<MultiRoyaltyInfo> = env.storage().persistent().get(&(MULTI_ROYALTY_KEY, token_id));
+        if let Some(info) = legacy {
+            for (recipient, bps) in info.recipients.iter().zip(info.percentages.iter()) {
+                shares.push_back(RoyaltyShare { recipient, bps });
+            }
+        }
+        shares
+    }
+
+    /// Calcula los montos de royalties de una venta por beneficiario
+    ///
+    /// El total es `sale_price * suma_bps / 10000` redondeado hacia abajo. Cada parte se
+    /// redondea hacia abajo y el resto del redondeo se asigna al primer beneficiario, de modo
+    /// que los montos suman exactamente el total.
+    pub fn royalty_info(env: Env, token_id: u32, sale_price: i128) -> Vec<(Address, i128)> {
+        Self::royalty_info_for_resale(env, token_id, sale_price, 0)
+    }
+
+    /// Calcula los montos de royalties de una venta tras `sale_count` ventas previas
+    ///
+    /// Con escalado configurado el royalty efectivo es `suma_bps + sale_count *
+    /// royalty_escalation_bps`, con tope en `max_royalty_bps`; cada beneficiario conserva su
+    /// proporción del total. Sin escalado equivale a `royalty_info`.
+    pub fn royalty_info_for_resale(env: Env, token_id: u32, sale_price: i128, sale_count: u32) -> Vec<(Address, i128)> {
+        if sale_price < 0 {
+            panic!("Invalid sale price");
+        }
+        
+        let shares = Self::get_royalty_shares(env.clone(), token_id);
+        let mut result = Vec::new(&env);
+        if shares.is_empty() {
+            return result;
+        }
+        
+        let mut total_bps: u32 = 0;
+        for share in shares.iter() {
+            total_bps += share.bps;
+        }
+        
+        let effective_bps = match Self::get_royalty_escalation(env.clone(), token_id) {
+            Some(escalation) => {
+                let escalated = total_bps.saturating_add(sale_count.saturating_mul(escalation.royalty_escalation_bps));
+                escalated.min(escalation.max_royalty_bps.max(total_bps))
+            }
+            None => total_bps,
+        };
+        let total = sale_price * effective_bps as i128 / BPS_DENOMINATOR as i128;
+        
+        let mut distributed: i128 = 0;
+        for share in shares.iter() {
+            let amount = sale_price * share.bps as i128 * effective_bps as i128
+                / (total_bps as i128 * BPS_DENOMINATOR as i128);
+            distributed += amount;
+            result.push_back((share.recipient, amount));
+        }
+        
+        // El resto del redondeo va al primer beneficiario
+        let (first, first_amount) = result.get(0).unwrap();
+        result.set(0, (first, first_amount + total - distributed));
+        
+        result
+    }
+
+    /// Resume las royalties configuradas en los tokens de un museo (paginado)
+    ///
+    /// Para planificar ingresos, cada token se valora a su último precio de venta declarado;
+    /// el segundo valor es la suma de las royalties esperadas de la página.
+    pub fn get_museum_token_royalty_summary(
+        env: Env,
+        museum: Address,
+        start: u32,
+        limit: u32,
+    ) -> (Vec<TokenRoyaltySummary>, i128) {
+        let mut summary = Vec::new(&env);
+        let mut expected_total: i128 = 0;
+        for token_id in Self::get_tokens_by_museum(env.clone(), museum, start, limit).iter() {
+            let mut royalty_bps: u32 = 0;
+            for share in Self::get_royalty_shares(env.clone(), token_id).iter() {
+                royalty_bps += share.bps;
+            }
+            
+            // El último registro de procedencia con valor declarado es la última venta
+            let provenance: Vec<PROVENANCERecord> = env.storage().persistent().get(&(PROVENANCE_KEY, token_id)).unwrap_or_else(|| {
+                Vec::new(&env)
+            });
+            let last_sale_price = provenance.iter().rev().find_map(|record| record.declared_value).unwrap_or(0);
+            
+            let expected_royalty = last_sale_price * royalty_bps as i128 / BPS_DENOMINATOR as i128;
+            expected_total += expected_royalty;
+            summary.push_back(TokenRoyaltySummary {
+                token_id,
+                royalty_bps,
+                last_sale_price,
+                expected_royalty,
+            });
+        }
+        
+        (summary, expected_total)
+    }
+
+    /// Configura el escalado de royalties por reventa de un token (solo admin)
+    ///
+    /// `royalty_escalation_bps` = 0 desactiva el escalado. `max_royalty_bps` no puede
+    /// superar `MAX_ROYALTY_BPS`.
+    pub fn set_royalty_escalation(env: Env, token_id: u32, royalty_escalation_bps: u32, max_royalty_bps: u32) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if !Self::exists(env.clone(), token_id) {
+            panic!("Token does not exist");
+        }
+        
+        let escalation_key = (ROYALTY_ESCALATION_KEY, token_id);
+        if royalty_escalation_bps == 0 {
+            env.storage().persistent().remove(&escalation_key);
+            return;
+        }
+        
+        if max_royalty_bps > MAX_ROYALTY_BPS {
+            panic!("Royalty cap exceeds maximum");
+        }
+        
+        env.storage().persistent().set(&escalation_key, &RoyaltyEscalation {
+            royalty_escalation_bps,
+            max_royalty_bps,
+        });
+    }
+
+    /// Obtiene el escalado de royalties de un token (None si no tiene)
+    pub fn get_royalty_escalation(env: Env, token_id: u32) -> Option<RoyaltyEscalation> {
+        env.storage().persistent().get(&(ROYALTY_ESCALATION_KEY, token_id))
+    }
+
+    /// Transfiere el token
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u32) {
+        // Verificar que el caller es el propietario
+        from.require_auth();
+        
+        let notes = String::from_str(&env, "Direct transfer");
+        Self::move_token(&env, &from, &to, token_id, "transfer", notes, None);
+    }
+
+    /// Transfiere el token en nombre del propietario a través del forwarder de confianza
+    /// (meta-transacción: el forwarder, ej. un kiosco del museo, paga las comisiones)
+    ///
+    /// `signature` es la firma ed25519, con la clave registrada por `from`, de la
+    /// codificación XDR de `(contrato, from, to, token_id, nonce)`.
+    pub fn forwarded_transfer(
+        env: Env,
+        forwarder: Address,
+        from: Address,
+        to: Address,
+        token_id: u32,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) {
+        // Verificar que el caller es el forwarder de confianza
+        forwarder.require_auth();
+        if Self::get_trusted_forwarder(env.clone()) != Some(forwarder) {
+            panic!("Not the trusted forwarder");
+        }
+        
+        // Verificar el nonce para evitar reenvíos
+        let nonce_key = (META_NONCE_KEY, from.clone());
+        let expected_nonce: u64 = env.storage().persistent().get(&nonce_key).unwrap_or(0);
+        if nonce != expected_nonce {
+            panic!("Invalid nonce");
+        }
+        
+        // Verificar la firma del propietario
+        let public_key: BytesN<32> = env.storage().persistent().get(&(SIGNING_KEY, from.clone())).unwrap_or_else(|| {
+            panic!("Signing key not registered");
+        });
+        let message = (env.current_contract_address(), from.clone(), to.clone(), token_id, nonce).to_xdr(&env);
+        env.crypto().ed25519_verify(&public_key, &message, &signature);
+        
+        env.storage().persistent().set(&nonce_key, &(nonce + 1));
+        
+        let notes = String::from_str(&env, "Forwarded transfer");
+        Self::move_token(&env, &from, &to, token_id, "transfer", notes, None);
+    }
+
+    /// Configura el forwarder de confianza para meta-transacciones (solo admin)
+    pub fn set_trusted_forwarder(env: Env, forwarder: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().instance().set(&TRUSTED_FORWARDER_KEY, &forwarder);
+    }
+
+    /// Obtiene el forwarder de confianza
+    pub fn get_trusted_forwarder(env: Env) -> Option<Address> {
+        env.storage().instance().get(&TRUSTED_FORWARDER_KEY)
+    }
+
+    /// Registra la clave pública ed25519 con la que el propietario firma meta-transacciones
+    pub fn register_signing_key(env: Env, owner: Address, public_key: BytesN<32>) {
+        // Verificar que el caller es el propietario
+        owner.require_auth();
+        
+        env.storage().persistent().set(&(SIGNING_KEY, owner), &public_key);
+    }
+
+    /// Obtiene el siguiente nonce esperado para las meta-transacciones del propietario
+    pub fn get_meta_nonce(env: Env, owner: Address) -> u64 {
+        env.storage().persistent().get(&(META_NONCE_KEY, owner)).unwrap_or(0)
+    }
+
+    /// Configura el período de espera tras cada adquisición antes de poder volver a
+    /// transferir el token (solo el museo del token; 0 lo desactiva)
+    pub fn set_transfer_cooldown(env: Env, museum: Address, token_id: u32, cooldown: u64) {
+        // Verificar que el caller es el museo
+        museum.require_auth();
+        
+        let metadata = Self::load_metadata(&env, token_id);
+        if metadata.museum_address != museum {
+            panic!("Not the token museum");
+        }
+        
+        let cooldown_key = (TRANSFER_COOLDOWN_KEY, token_id);
+        if cooldown == 0 {
+            env.storage().persistent().remove(&cooldown_key);
+        } else {
+            env.storage().persistent().set(&cooldown_key, &cooldown);
+        }
+    }
+
+    /// Obtiene el período de espera tras cada adquisición (0 = sin espera)
+    pub fn get_transfer_cooldown(env: Env, token_id: u32) -> u64 {
+        env.storage().persistent().get(&(TRANSFER_COOLDOWN_KEY, token_id)).unwrap_or(0)
+    }
+
+    /// Marca un custodio (ej. el marketplace) cuyas adquisiciones no inician el período
+    /// de espera (solo admin)
+    pub fn set_cooldown_exempt(env: Env, custodian: Address, exempt: bool) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let exempt_key = (COOLDOWN_EXEMPT_KEY, custodian);
+        if exempt {
+            env.storage().persistent().set(&exempt_key, &true);
+        } else {
+            env.storage().persistent().remove(&exempt_key);
+        }
+    }
+
+    /// Verifica si un custodio está exento del período de espera
+    pub fn is_cooldown_exempt(env: Env, custodian: Address) -> bool {
+        env.storage().persistent().get(&(COOLDOWN_EXEMPT_KEY, custodian)).unwrap_or(false)
+    }
+
+    /// Obtiene el timestamp a partir del cual el token puede volver a transferirse
+    /// (0 si no hay espera pendiente)
+    pub fn get_transfer_unlock_time(env: Env, token_id: u32) -> u64 {
+        env.storage().persistent().get(&(TRANSFER_UNLOCK_KEY, token_id)).unwrap_or(0)
+    }
+
+    /// Transfiere varios tokens en una sola operación (ej. traspaso de colección a un nuevo custodio)
+    ///
+    /// Verifica la propiedad de todos los tokens antes de mover ninguno: si alguno no
+    /// pertenece a `from`, no se transfiere ninguno.
+    pub fn batch_transfer(env: Env, from: Address, to: Address, token_ids: Vec<u32>) {
+        // Verificar que el caller es el propietario
+        from.require_auth();
+        
+        if token_ids.is_empty() {
+            panic!("No tokens to transfer");
+        }
+        if token_ids.len() > MAX_BATCH_TRANSFER {
+            panic!("Too many tokens in batch");
+        }
+        
+        // Verificar la propiedad de todos los tokens antes de transferir
+        for i in 0..token_ids.len() {
+            let token_id = token_ids.get(i).unwrap();
+            if token_ids.first_index_of(token_id) != Some(i) {
+                panic!("Duplicate token in batch");
+            }
+            if Self::owner_of(env.clone(), token_id) != from {
+                panic!("Not the owner");
+            }
+        }
+        
+        for token_id in token_ids.iter() {
+            let notes = String::from_str(&env, "Batch transfer");
+            Self::move_token(&env, &from, &to, token_id, "transfer", notes, None);
+        }
+    }
+
+    /// Transfiere el token verificando que un contrato receptor lo acepta
+    ///
+    /// Si `to` es un contrato, se invoca su `on_nft_received` y la transferencia se revierte
+    /// salvo que devuelva `true`. Para cuentas se comporta igual que `transfer`.
+    pub fn safe_transfer(env: Env, from: Address, to: Address, token_id: u32) {
+        // Verificar que el caller es el propietario
+        from.require_auth();
+        
+        let notes = String::from_str(&env, "Safe transfer");
+        Self::move_token(&env, &from, &to, token_id, "transfer", notes, None);
+        
+        // El propietario es quien opera la transferencia
+        Self::ensure_received(&env, &from, &from, &to, token_id);
+    }
+
+    /// Transfiere desde una dirección aprobada verificando que un contrato receptor lo acepta
+    ///
+    /// Igual que `safe_transfer`, pero el receptor recibe a `spender` como operador.
+    pub fn safe_transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
+        // Verificar que el spender está autorizado
+        spender.require_auth();
+        
+        Self::check_approval(&env, &spender, &from, token_id);
+        
+        let notes = String::from_str(&env, "Safe approved transfer");
+        Self::move_token(&env, &from, &to, token_id, "transfer_from", notes, None);
+        
+        Self::ensure_received(&env, &spender, &from, &to, token_id);
+    }
+
+    /// Transfiere el token adjuntando una nota y un precio declarado a la procedencia
+    pub fn transfer_with_memo(
+        env: Env,
+        from: Address,
+        to: Address,
+        token_id: u32,
+        memo: String,
+        declared_value: Option<i128>,
+    ) {
+        // Verificar que el caller es el propietario
+        from.require_auth();
+        
+        Self::validate_memo(&memo, declared_value);
+        Self::move_token(&env, &from, &to, token_id, "transfer", memo, declared_value);
+    }
+
+    /// Aprueba una transferencia (para marketplace), sin expiración
+    pub fn approve(env: Env, from: Address, to: Address, token_id: u32) {
+        Self::approve_until(env, from, to, token_id, 0);
+    }
+
+    /// Aprueba una transferencia hasta `expires_at`, el timestamp a partir del cual la
+    /// aprobación deja de ser válida (0 = sin expiración)
+    pub fn approve_until(env: Env, from: Address, to: Address, token_id: u32, expires_at: u64) {
+        // Verificar que el caller es el propietario
+        from.require_auth();
+        
+        // Verificar que el token existe y es del propietario
+        let owner_key = (OWNER_KEY, token_id);
+        let current_owner: Address = env.storage().persistent().get(&owner_key).unwrap_or_else(|| {
+            panic!("Token does not exist");
+        });
+        
+        if current_owner != from {
+            panic!("Not the owner");
+        }
+        
+        Self::ensure_not_soulbound(&env, token_id);
+        Self::validate_expiration(&env, expires_at);
+        
+        // Guardar aprobación
+        let approval_key = (APPROVAL_KEY, token_id);
+        let approval = Approval {
+            spender: to,
+            expires_at,
+        };
+        env.storage().persistent().set(&approval_key, &approval);
+    }
+
+    /// Obtiene la dirección aprobada para un token (ignora aprobaciones expiradas)
+    pub fn get_approved(env: Env, token_id: u32) -> Option<Address> {
+        let approval_key = (APPROVAL_KEY, token_id);
+        let approval: Approval = env.storage().persistent().get(&approval_key)?;
+        
+        if Self::is_live(&env, approval.expires_at) {
+            Some(approval.spender)
+        } else {
+            None
+        }
+    }
+
+    /// Aprueba a un operador para mover todos los tokens del propietario, sin expiración
+    ///
+    /// Los tokens intransferibles quedan fuera: `transfer_from` los rechaza igualmente.
+    pub fn approve_for_all(env: Env, owner: Address, operator: Address) {
+        Self::approve_for_all_until(env, owner, operator, 0);
+    }
+
+    /// Aprueba a un operador hasta `expires_at`, el timestamp a partir del cual la
+    /// aprobación deja de ser válida (0 = sin expiración)
+    pub fn approve_for_all_until(env: Env, owner: Address, operator: Address, expires_at: u64) {
+        // Verificar que el caller es el propietario
+        owner.require_auth();
+        
+        Self::validate_expiration(&env, expires_at);
+        
+        let operator_key = (OPERATOR_KEY, owner, operator);
+        env.storage().persistent().set(&operator_key, &expires_at);
+    }
+
+    /// Revoca la aprobación de un operador
+    pub fn revoke_approval_for_all(env: Env, owner: Address, operator: Address) {
+        // Verificar que el caller es el propietario
+        owner.require_auth();
+        
+        let operator_key = (OPERATOR_KEY, owner, operator);
+        env.storage().persistent().remove(&operator_key);
+    }
+
+    /// Verifica si un operador está aprobado (y vigente) para el propietario
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        let operator_key = (OPERATOR_KEY, owner, operator);
+        match env.storage().persistent().get::<_, u64>(&operator_key) {
+            Some(expires_at) => Self::is_live(&env, expires_at),
+            None => false,
+        }
+    }
+
+    /// Transfiere desde una dirección aprobada
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32) {
+        // Verificar que el spender está autorizado
+        spender.require_auth();
+        
+        Self::check_approval(&env, &spender, &from, token_id);
+        
+        let notes = String::from_str(&env, "Approved transfer");
+        Self::move_token(&env, &from, &to, token_id, "transfer_from", notes, None);
+    }
+
+    /// Transfiere desde una dirección aprobada adjuntando una nota y un precio declarado
+    pub fn transfer_from_with_memo(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        token_id: u32,
+        memo: String,
+        declared_value: Option<i128>,
+    ) {
+        // Verificar que el spender está autorizado
+        spender.require_auth();
+        
+        Self::validate_memo(&memo, declared_value);
+        Self::check_approval(&env, &spender, &from, token_id);
+        Self::move_token(&env, &from, &to, token_id, "transfer_from", memo, declared_value);
+    }
+
+    /// Registra un contrato observador de transferencias (solo admin)
+    pub fn register_transfer_hook(env: Env, contract: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut hooks = Self::get_transfer_hooks(env.clone());
+        if hooks.contains(&contract) {
+            panic!("Transfer hook already registered");
+        }
+        if hooks.len() >= MAX_TRANSFER_HOOKS {
+            panic!("Too many transfer hooks");
+        }
+        
+        hooks.push_back(contract);
+        env.storage().instance().set(&TRANSFER_HOOKS_KEY, &hooks);
+    }
+
+    /// Elimina un contrato observador de transferencias (solo admin)
+    pub fn remove_transfer_hook(env: Env, contract: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut hooks = Self::get_transfer_hooks(env.clone());
+        let position = hooks.first_index_of(&contract).unwrap_or_else(|| {
+            panic!("Transfer hook not registered");
+        });
+        hooks.remove(position);
+        env.storage().instance().set(&TRANSFER_HOOKS_KEY, &hooks);
+    }
+
+    /// Obtiene los contratos observadores de transferencias
+    pub fn get_transfer_hooks(env: Env) -> Vec<Address> {
+        env.storage().instance().get(&TRANSFER_HOOKS_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Suscribe un contrato a un tipo de evento: "mint", "transfer" o "burn" (solo admin)
+    pub fn register_hook(env: Env, hook_contract: Address, event_type: String) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if !HOOK_EVENT_TYPES.iter().any(|name| event_type == String::from_str(&env, name)) {
+            panic!("Invalid event type");
+        }
+        
+        let mut hooks = Self::get_hooks(env.clone(), event_type.clone());
+        if hooks.contains(&hook_contract) {
+            panic!("Hook already registered");
+        }
+        if hooks.len() >= MAX_HOOKS_PER_EVENT {
+            panic!("Too many hooks for event");
+        }
+        
+        hooks.push_back(hook_contract);
+        env.storage().instance().set(&(EVENT_HOOKS_KEY, event_type), &hooks);
+    }
+
+    /// Cancela la suscripción de un contrato a un tipo de evento (solo admin)
+    pub fn unregister_hook(env: Env, hook_contract: Address, event_type: String) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut hooks = Self::get_hooks(env.clone(), event_type.clone());
+        let position = hooks.first_index_of(&hook_contract).unwrap_or_else(|| {
+            panic!("Hook not registered");
+        });
+        hooks.remove(position);
+        env.storage().instance().set(&(EVENT_HOOKS_KEY, event_type), &hooks);
+    }
+
+    /// Obtiene los contratos suscritos a un tipo de evento
+    pub fn get_hooks(env: Env, event_type: String) -> Vec<Address> {
+        env.storage().instance().get(&(EVENT_HOOKS_KEY, event_type)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Registra un investigador autorizado a atestiguar procedencia (solo admin)
+    pub fn register_researcher(env: Env, researcher: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().persistent().set(&(RESEARCHER_KEY, researcher), &true);
+    }
+
+    /// Elimina un investigador autorizado (solo admin)
+    pub fn remove_researcher(env: Env, researcher: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().persistent().remove(&(RESEARCHER_KEY, researcher));
+    }
+
+    /// Verifica si una dirección es un investigador autorizado
+    pub fn is_researcher(env: Env, researcher: Address) -> bool {
+        env.storage().persistent().get(&(RESEARCHER_KEY, researcher)).unwrap_or(false)
+    }
+
+    /// Configura el máximo de registros de procedencia recientes por token (solo admin)
+    ///
+    /// Los registros que excedan el máximo se mueven al archivo en la siguiente escritura.
+    pub fn set_max_provenance_length(env: Env, max_length: u32) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if max_length == 0 {
+            panic!("Max length must be positive");
+        }
+        
+        env.storage().instance().set(&MAX_PROVENANCE_KEY, &max_length);
+    }
+
+    /// Obtiene el máximo de registros de procedencia recientes por token
+    pub fn get_max_provenance_length(env: Env) -> u32 {
+        env.storage().instance().get(&MAX_PROVENANCE_KEY).unwrap_or(DEFAULT_MAX_PROVENANCE_LENGTH)
+    }
+
+    /// Obtiene registros archivados de procedencia, del más antiguo al más reciente (paginado)
+    pub fn get_archived_provenance(env: Env, token_id: u32, offset: u32, limit: u32) -> Vec<PROVENANCERecord> {
+        let archived = Self::get_archived_provenance_count(env.clone(), token_id);
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(archived);
+        
+        let mut result = Vec::new(&env);
+        let mut page: Vec<PROVENANCERecord> = Vec::new(&env);
+        let mut page_number = u32::MAX;
+        for index in offset..end {
+            if index / PROVENANCE_ARCHIVE_PAGE_SIZE != page_number {
+                page_number = index / PROVENANCE_ARCHIVE_PAGE_SIZE;
+                page = env.storage().persistent().get(&(PROVENANCE_ARCHIVE_KEY, token_id, page_number)).unwrap();
+            }
+            result.push_back(page.get(index % PROVENANCE_ARCHIVE_PAGE_SIZE).unwrap());
+        }
+        
+        result
+    }
+
+    /// Obtiene los registros de procedencia de un tipo, del más antiguo al más reciente,
+    /// incluyendo los archivados (paginado sobre los registros coincidentes)
+    pub fn get_provenance_filtered(
+        env: Env,
+        token_id: u32,
+        transaction_type: String,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<PROVENANCERecord> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut result = Vec::new(&env);
+        let mut skipped: u32 = 0;
+        
+        // Recorrer el archivo página a página y, al final, los registros recientes
+        let archived = Self::get_archived_provenance_count(env.clone(), token_id);
+        let pages = archived.div_ceil(PROVENANCE_ARCHIVE_PAGE_SIZE);
+        for page_number in 0..=pages {
+            let records: Vec<PROVENANCERecord> = if page_number < pages {
+                env.storage().persistent().get(&(PROVENANCE_ARCHIVE_KEY, token_id, page_number)).unwrap()
+            } else {
+                Self::get_provenance(env.clone(), token_id)
+            };
+            
+            for record in records.iter() {
+                if record.transaction_type != transaction_type {
+                    continue;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                    continue;
+                }
+                if result.len() >= limit {
+                    return result;
+                }
+                result.push_back(record);
+            }
+        }
+        
+        result
+    }
+
+    /// Obtiene cuántos registros de procedencia hay de cada tipo: (tipo, cantidad)
+    pub fn get_provenance_type_counts(env: Env, token_id: u32) -> Vec<(String, u32)> {
+        env.storage().persistent().get(&(PROVENANCE_TYPE_COUNT_KEY, token_id)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Obtiene el número de registros de procedencia archivados
+    pub fn get_archived_provenance_count(env: Env, token_id: u32) -> u32 {
+        env.storage().persistent().get(&(PROVENANCE_ARCHIVE_COUNT_KEY, token_id)).unwrap_or(0)
+    }
+
+    /// Atestigua un registro de procedencia concreto con evidencia documental
+    ///
+    /// `record_index` es la posición en el historial completo (archivados + recientes).
+    pub fn attest_provenance(
+        env: Env,
+        attestor: Address,
+        token_id: u32,
+        record_index: u32,
+        evidence_uri: String,
+    ) {
+        // Verificar que el caller es un investigador autorizado
+        attestor.require_auth();
+        if !Self::is_researcher(env.clone(), attestor.clone()) {
+            panic!("Not a registered researcher");
+        }
+        
+        // Verificar que el registro existe
+        let provenance = Self::get_provenance(env.clone(), token_id);
+        if record_index >= Self::get_archived_provenance_count(env.clone(), token_id) + provenance.len() {
+            panic!("Provenance record not found");
+        }
+        
+        let attestation_key = (ATTESTATION_KEY, token_id, record_index);
+        let mut attestations: Vec<ProvenanceAttestation> = env.storage().persistent().get(&attestation_key).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        // Un investigador atestigua cada registro una sola vez
+        for attestation in attestations.iter() {
+            if attestation.attestor == attestor {
+                panic!("Record already attested by this researcher");
+            }
+        }
+        
+        attestations.push_back(ProvenanceAttestation {
+            attestor: attestor.clone(),
+            evidence_uri,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&attestation_key, &attestations);
+        
+        Self::record_contribution(&env, token_id, &attestor, "attestation");
+    }
+
+    /// Obtiene las atestaciones de un registro de procedencia
+    pub fn get_provenance_attestations(env: Env, token_id: u32, record_index: u32) -> Vec<ProvenanceAttestation> {
+        let attestation_key = (ATTESTATION_KEY, token_id, record_index);
+        env.storage().persistent().get(&attestation_key).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Obtiene la procedencia reciente con el número de atestaciones de cada registro
+    pub fn get_attested_provenance(env: Env, token_id: u32) -> Vec<AttestedProvenanceRecord> {
+        let provenance = Self::get_provenance(env.clone(), token_id);
+        let archived = Self::get_archived_provenance_count(env.clone(), token_id);
+        
+        let mut result = Vec::new(&env);
+        for (index, record) in provenance.iter().enumerate() {
+            let attestations = Self::get_provenance_attestations(env.clone(), token_id, archived + index as u32);
+            result.push_back(AttestedProvenanceRecord {
+                record,
+                attestation_count: attestations.len(),
+            });
+        }
+        
+        result
+    }
+
+    /// Calcula el resumen verificable de la procedencia de un token
+    ///
+    /// Es el SHA-256 de la concatenación de las codificaciones XDR (`ScVal`) de:
+    /// el `token_id` (u32), los metadatos culturales completos y cada `PROVENANCERecord`
+    /// en orden cronológico, primero los archivados y luego los recientes.
+    pub fn get_provenance_digest(env: Env, token_id: u32) -> BytesN<32> {
+        let mut data = Bytes::new(&env);
+        data.append(&token_id.to_xdr(&env));
+        data.append(&Self::load_metadata(&env, token_id).to_xdr(&env));
+        
+        let archived = Self::get_archived_provenance_count(env.clone(), token_id);
+        let pages = archived.div_ceil(PROVENANCE_ARCHIVE_PAGE_SIZE);
+        for page_number in 0..pages {
+            let records: Vec<PROVENANCERecord> = env.storage().persistent().get(&(PROVENANCE_ARCHIVE_KEY, token_id, page_number)).unwrap();
+            for record in records.iter() {
+                data.append(&record.to_xdr(&env));
+            }
+        }
+        for record in Self::get_provenance(env.clone(), token_id).iter() {
+            data.append(&record.to_xdr(&env));
+        }
+        
+        env.crypto().sha256(&data).into()
+    }
+
+    /// Verifica que un resumen coincide con la procedencia actual del token
+    pub fn verify_provenance_digest(env: Env, token_id: u32, digest: BytesN<32>) -> bool {
+        Self::get_provenance_digest(env, token_id) == digest
+    }
+
+    /// Marca un token como disputado (solo admin)
+    ///
+    /// Mientras está marcado, toda transferencia requiere además la autorización del admin.
+    pub fn flag_dispute(env: Env, token_id: u32, claimant: String, case_reference: String) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let owner = Self::owner_of(env.clone(), token_id);
+        
+        let dispute_key = (DISPUTE_KEY, token_id);
+        if env.storage().persistent().has(&dispute_key) {
+            panic!("Token already disputed");
+        }
+        
+        let dispute = DisputeInfo {
+            claimant,
+            case_reference: case_reference.clone(),
+            flagged_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&dispute_key, &dispute);
+        
+        // Dejar constancia en la procedencia
+        Self::append_provenance(&env, token_id, &owner, &owner, "dispute_flagged", case_reference, None);
+    }
+
+    /// Levanta la disputa de un token (solo admin)
+    pub fn clear_dispute(env: Env, token_id: u32, resolution: String) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let dispute_key = (DISPUTE_KEY, token_id);
+        if !env.storage().persistent().has(&dispute_key) {
+            panic!("Token not disputed");
+        }
+        env.storage().persistent().remove(&dispute_key);
+        
+        // Dejar constancia en la procedencia
+        let owner = Self::owner_of(env.clone(), token_id);
+        Self::append_provenance(&env, token_id, &owner, &owner, "dispute_cleared", resolution, None);
+    }
+
+    /// Obtiene la disputa activa de un token, si existe
+    pub fn get_dispute(env: Env, token_id: u32) -> Option<DisputeInfo> {
+        env.storage().persistent().get(&(DISPUTE_KEY, token_id))
+    }
+
+    /// Verifica si un token tiene una disputa activa
+    pub fn is_disputed(env: Env, token_id: u32) -> bool {
+        env.storage().persistent().has(&(DISPUTE_KEY, token_id))
+    }
+
+    /// Propone la quema de un token (propietario o museo); el admin debe confirmarla
+    /// dentro del plazo configurado
+    pub fn propose_burn(env: Env, proposer: Address, token_id: u32, reason: String) {
+        // Verificar que el caller es el propietario o el museo del token
+        proposer.require_auth();
+        
+        let owner = Self::owner_of(env.clone(), token_id);
+        let metadata = Self::load_metadata(&env, token_id);
+        if proposer != owner && proposer != metadata.museum_address {
+            panic!("Not the token owner or museum");
+        }
+        
+        Self::validate_memo(&reason, None);
+        
+        // Solo una propuesta vigente por token
+        if let Some(existing) = Self::get_burn_proposal(env.clone(), token_id) {
+            if env.ledger().timestamp() < existing.expires_at {
+                panic!("Burn already proposed");
+            }
+        }
+        
+        let now = env.ledger().timestamp();
+        let proposal = BurnProposal {
+            proposer,
+            reason,
+            proposed_at: now,
+            expires_at: now + Self::get_burn_window(env.clone()),
+        };
+        env.storage().persistent().set(&(BURN_PROPOSAL_KEY, token_id), &proposal);
+    }
+
+    /// Confirma una propuesta de quema vigente y quema el token (solo admin)
+    pub fn confirm_burn(env: Env, token_id: u32) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let proposal = Self::get_burn_proposal(env.clone(), token_id).unwrap_or_else(|| {
+            panic!("Burn not proposed");
+        });
+        if env.ledger().timestamp() >= proposal.expires_at {
+            panic!("Burn proposal expired");
+        }
+        
+        env.storage().persistent().remove(&(BURN_PROPOSAL_KEY, token_id));
+        Self::burn_token(&env, token_id, proposal.reason);
+        Self::record_contribution(&env, token_id, &admin, "burn");
+    }
+
+    /// Revoca (quema) un token intransferible sin propuesta previa (solo admin)
+    pub fn revoke_soulbound(env: Env, token_id: u32, reason: String) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if !Self::load_metadata(&env, token_id).is_soulbound {
+            panic!("Token is not soulbound");
+        }
+        
+        env.storage().persistent().remove(&(BURN_PROPOSAL_KEY, token_id));
+        Self::burn_token(&env, token_id, reason);
+        Self::record_contribution(&env, token_id, &admin, "burn");
+    }
+
+    /// Cancela una propuesta de quema (propietario, museo o admin)
+    pub fn cancel_burn(env: Env, caller: Address, token_id: u32) {
+        caller.require_auth();
+        
+        if Self::get_burn_proposal(env.clone(), token_id).is_none() {
+            panic!("Burn not proposed");
+        }
+        
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        let owner = Self::owner_of(env.clone(), token_id);
+        let metadata = Self::load_metadata(&env, token_id);
+        if caller != admin && caller != owner && caller != metadata.museum_address {
+            panic!("Not authorized to cancel burn");
+        }
+        
+        env.storage().persistent().remove(&(BURN_PROPOSAL_KEY, token_id));
+    }
+
+    /// Obtiene la propuesta de quema de un token
+    pub fn get_burn_proposal(env: Env, token_id: u32) -> Option<BurnProposal> {
+        env.storage().persistent().get(&(BURN_PROPOSAL_KEY, token_id))
+    }
+
+    /// Configura el plazo de confirmación de quemas en segundos (solo admin)
+    pub fn set_burn_window(env: Env, window: u64) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if window == 0 {
+            panic!("Window must be positive");
+        }
+        
+        env.storage().instance().set(&BURN_WINDOW_KEY, &window);
+    }
+
+    /// Obtiene el plazo de confirmación de quemas en segundos
+    pub fn get_burn_window(env: Env) -> u64 {
+        env.storage().instance().get(&BURN_WINDOW_KEY).unwrap_or(DEFAULT_BURN_WINDOW)
+    }
+
+    /// Verifica si un token fue quemado
+    pub fn is_burned(env: Env, token_id: u32) -> bool {
+        env.storage().persistent().has(&(BURNED_KEY, token_id))
+    }
+
+    /// Propone fusionar dos tokens (ej. fragmentos de un mismo artefacto) en uno nuevo;
+    /// ambos quedan bloqueados hasta que el admin ejecute o se cancele la fusión
+    pub fn propose_merge(
+        env: Env,
+        owner: Address,
+        token_id_a: u32,
+        token_id_b: u32,
+        new_metadata: CulturalMetadata,
+    ) -> u32 {
+        // Verificar que el caller es el propietario de ambos tokens
+        owner.require_auth();
+        
+        if token_id_a == token_id_b {
+            panic!("Cannot merge a token with itself");
+        }
+        
+        for token_id in [token_id_a, token_id_b] {
+            if Self::owner_of(env.clone(), token_id) != owner {
+                panic!("Not the owner");
+            }
+            if Self::is_disputed(env.clone(), token_id) {
+                panic!("Token is disputed");
+            }
+            if Self::get_merge_lock(env.clone(), token_id).is_some() {
+                panic!("Token already locked for merge");
+            }
+        }
+        
+        let merge_id: u32 = env.storage().instance().get(&MERGE_COUNT_KEY).unwrap_or(0) + 1;
+        env.storage().instance().set(&MERGE_COUNT_KEY, &merge_id);
+        
+        let proposal = MergeProposal {
+            owner,
+            token_id_a,
+            token_id_b,
+            new_metadata,
+            proposed_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&(MERGE_KEY, merge_id), &proposal);
+        env.storage().persistent().set(&(MERGE_LOCK_KEY, token_id_a), &merge_id);
+        env.storage().persistent().set(&(MERGE_LOCK_KEY, token_id_b), &merge_id);
+        
+        merge_id
+    }
+
+    /// Ejecuta una fusión (solo admin): quema ambos tokens borrando sus datos y mintea al
+    /// propietario un nuevo token con la procedencia combinada; devuelve el ID del nuevo token
+    pub fn execute_merge(env: Env, merge_id: u32) -> u32 {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let proposal = Self::take_merge_proposal(&env, merge_id);
+        
+        // Combinar la procedencia completa de ambos tokens y dejar constancia de la fusión
+        let mut provenance = Self::full_provenance(&env, proposal.token_id_a);
+        provenance.append(&Self::full_provenance(&env, proposal.token_id_b));
+        provenance.push_back(PROVENANCERecord {
+            date: env.ledger().timestamp(),
+            from: proposal.owner.clone(),
+            to: proposal.owner.clone(),
+            transaction_type: String::from_str(&env, "merge"),
+            notes: String::from_str(&env, ""),
+            declared_value: None,
+        });
+        
+        for token_id in [proposal.token_id_a, proposal.token_id_b] {
+            Self::burn_token(&env, token_id, String::from_str(&env, "merge"));
+            Self::purge_token(&env, token_id);
+        }
+        
+        // Asignar el siguiente ID libre
+        let mut token_id = Self::total_supply(env.clone()) + 1;
+        while Self::exists(env.clone(), token_id) || Self::is_burned(env.clone(), token_id) {
+            token_id += 1;
+        }
+        
+        Self::mint_token(&env, &proposal.owner, token_id, proposal.new_metadata, provenance);
+        Self::record_contribution(&env, token_id, &admin, "merge");
+        
+        let sources = soroban_sdk::vec![&env, proposal.token_id_a, proposal.token_id_b];
+        env.storage().persistent().set(&(MERGED_FROM_KEY, token_id), &sources);
+        
+        token_id
+    }
+
+    /// Cancela una fusión pendiente y desbloquea los tokens (propietario o admin)
+    pub fn cancel_merge(env: Env, caller: Address, merge_id: u32) {
+        caller.require_auth();
+        
+        let proposal = Self::get_merge_proposal(env.clone(), merge_id).unwrap_or_else(|| {
+            panic!("Merge not proposed");
+        });
+        if caller != proposal.owner && !Self::is_admin(env.clone(), caller) {
+            panic!("Not authorized to cancel merge");
+        }
+        
+        Self::take_merge_proposal(&env, merge_id);
+    }
+
+    /// Obtiene una propuesta de fusión pendiente
+    pub fn get_merge_proposal(env: Env, merge_id: u32) -> Option<MergeProposal> {
+        env.storage().persistent().get(&(MERGE_KEY, merge_id))
+    }
+
+    /// Obtiene la fusión pendiente que bloquea un token
+    pub fn get_merge_lock(env: Env, token_id: u32) -> Option<u32> {
+        env.storage().persistent().get(&(MERGE_LOCK_KEY, token_id))
+    }
+
+    /// Obtiene los tokens de origen de un token creado por fusión
+    pub fn get_merged_from(env: Env, token_id: u32) -> Vec<u32> {
+        env.storage().persistent().get(&(MERGED_FROM_KEY, token_id)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Propone el minteo de un token (solo museos verificados); el admin lo revisa
+    ///
+    /// Puede proponerlo el museo de los metadatos o uno de sus curadores Registrar. La cola
+    /// admite hasta `MAX_PENDING_MINT_REQUESTS` solicitudes vigentes.
+    pub fn request_mint(
+        env: Env,
+        requester: Address,
+        metadata: CulturalMetadata,
+        provenance: Vec<PROVENANCERecord>,
+    ) -> u32 {
+        // Verificar que el caller es el museo o uno de sus curadores Registrar
+        requester.require_auth();
+        
+        let museum = metadata.museum_address.clone();
+        if requester != museum {
+            Self::ensure_museum_or_curator(&env, &requester, &museum, CuratorRole::Registrar);
+        }
+        
+        // Verificar que el museo está verificado
+        Self::ensure_museum_verified(&env, &museum);
+        
+        // Depurar las solicitudes expiradas antes de aceptar una nueva
+        let mut pending = Self::prune_expired_requests(&env);
+        if pending.len() >= MAX_PENDING_MINT_REQUESTS {
+            panic!("Too many pending mint requests");
+        }
+        
+        // Cobrar la comisión de minteo en custodia; si la transferencia falla no se crea la solicitud
+        let fee = Self::get_mint_fee(env.clone()).filter(|fee| fee.amount > 0);
+        if let Some(fee) = fee.clone() {
+            token::Client::new(&env, &fee.token).transfer(&requester, &env.current_contract_address(), &fee.amount);
+        }
+        
+        // Obtener nuevo ID
+        let mut count: u32 = env.storage().instance().get(&MINT_REQUEST_COUNT_KEY).unwrap_or(0);
+        count += 1;
+        env.storage().instance().set(&MINT_REQUEST_COUNT_KEY, &count);
+        if let Some(fee) = fee {
+            env.storage().persistent().set(&(MINT_FEE_PAID_KEY, count), &(fee, requester));
+        }
+        
+        let created_at = env.ledger().timestamp();
+        let request = MintRequest {
+            id: count,
+            museum,
+            metadata,
+            provenance,
+            status: MintRequestStatus::Pending,
+            created_at,
+            expires_at: created_at + Self::get_mint_request_ttl(env.clone()),
+            token_id: 0,
+            reason: String::from_str(&env, ""),
+        };
+        env.storage().persistent().set(&(MINT_REQUEST_KEY, count), &request);
+        
+        // Agregar a la cola de pendientes
+        pending.push_back(count);
+        env.storage().persistent().set(&PENDING_MINTS_KEY, &pending);
+        
+        count
+    }
+
+    /// Aprueba una solicitud de minteo y mintea el token al museo (solo admin)
+    pub fn approve_mint_request(env: Env, request_id: u32) -> u32 {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut request = Self::take_pending_request(&env, request_id);
+        if env.ledger().timestamp() >= request.expires_at {
+            panic!("Mint request expired");
+        }
+        
+        // La verificación del museo debe seguir vigente al aprobar (pudo expirar o revocarse)
+        Self::ensure_museum_verified(&env, &request.museum);
+        
+        // Asignar el siguiente ID libre
+        let mut token_id = Self::total_supply(env.clone()) + 1;
+        while Self::exists(env.clone(), token_id) || Self::is_burned(env.clone(), token_id) {
+            token_id += 1;
+        }
+        
+        Self::mint_token(&env, &request.museum, token_id, request.metadata.clone(), request.provenance.clone());
+        Self::record_contribution(&env, token_id, &request.museum, "mint");
+        Self::reward_content(&env, &request.museum, &CREATION_BONUS_KEY, "nft_minted");
+        Self::report_mint_activity(&env, &request.museum);
+        
+        request.status = MintRequestStatus::Approved;
+        request.token_id = token_id;
+        env.storage().persistent().set(&(MINT_REQUEST_KEY, request_id), &request);
+        
+        // Enviar la comisión en custodia a la tesorería
+        if let Some((fee, _payer)) = Self::take_mint_fee_paid(&env, request_id) {
+            token::Client::new(&env, &fee.token).transfer(&env.current_contract_address(), &fee.treasury, &fee.amount);
+        }
+        
+        token_id
+    }
+
+    /// Rechaza una solicitud de minteo (solo admin)
+    pub fn reject_mint_request(env: Env, request_id: u32, reason: String) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut request = Self::take_pending_request(&env, request_id);
+        request.status = MintRequestStatus::Rejected;
+        request.reason = reason;
+        env.storage().persistent().set(&(MINT_REQUEST_KEY, request_id), &request);
+        
+        // Devolver la comisión en custodia a quien la pagó
+        if let Some((fee, payer)) = Self::take_mint_fee_paid(&env, request_id) {
+            token::Client::new(&env, &fee.token).transfer(&env.current_contract_address(), &payer, &fee.amount);
+        }
+    }
+
+    /// Configura la comisión que pagan los museos por cada solicitud de minteo (solo admin)
+    ///
+    /// Se cobra al solicitar, se envía a la tesorería al aprobar y se devuelve al rechazar.
+    /// Los minteos directos del admin están exentos; `amount = 0` la desactiva.
+    pub fn set_mint_fee(env: Env, token: Address, amount: i128, treasury: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if amount < 0 {
+            panic!("Fee cannot be negative");
+        }
+        
+        env.storage().instance().set(&MINT_FEE_KEY, &MintFee { token, amount, treasury });
+    }
+
+    /// Obtiene la comisión de minteo configurada
+    pub fn get_mint_fee(env: Env) -> Option<MintFee> {
+        env.storage().instance().get(&MINT_FEE_KEY)
+    }
+
+    /// Configura los puntos SocialFi por minteo (al museo) y por informe de estado
+    /// (al autor) (solo admin; 0 desactiva la recompensa)
+    pub fn set_content_rewards(env: Env, creation_bonus: i128, curation_points: i128) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if creation_bonus < 0 || curation_points < 0 {
+            panic!("Rewards cannot be negative");
+        }
+        
+        env.storage().instance().set(&CREATION_BONUS_KEY, &creation_bonus);
+        env.storage().instance().set(&CURATION_POINTS_KEY, &curation_points);
+    }
+
+    /// Obtiene las recompensas configuradas: (bono de creación, puntos de curaduría)
+    pub fn get_content_rewards(env: Env) -> (i128, i128) {
+        let creation_bonus: i128 = env.storage().instance().get(&CREATION_BONUS_KEY).unwrap_or(0);
+        let curation_points: i128 = env.storage().instance().get(&CURATION_POINTS_KEY).unwrap_or(0);
+        (creation_bonus, curation_points)
+    }
+
+    /// Cambia el contrato MuseumRegistry consultado para verificar museos y curadores
+    /// (solo admin), p. ej. tras migrar el registro a un nuevo contrato
+    pub fn set_museum_registry(env: Env, museum_registry: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().instance().set(&MUSEUM_REGISTRY_KEY, &museum_registry);
+    }
+
+    /// Obtiene el contrato MuseumRegistry configurado
+    pub fn get_museum_registry(env: Env) -> Address {
+        env.storage().instance().get(&MUSEUM_REGISTRY_KEY).unwrap()
+    }
+
+    /// Obtiene el contrato SocialFi configurado en la inicialización
+    pub fn get_socialfi(env: Env) -> Option<Address> {
+        env.storage().instance().get(&SOCIALFI_KEY)
+    }
+
+    /// Obtiene una solicitud de minteo
+    pub fn get_mint_request(env: Env, request_id: u32) -> MintRequest {
+        env.storage().persistent().get(&(MINT_REQUEST_KEY, request_id)).unwrap_or_else(|| {
+            panic!("Mint request not found");
+        })
+    }
+
+    /// Obtiene las solicitudes pendientes y no expiradas (paginado)
+    ///
+    /// Las expiradas se retiran de la cola, como `Expired` y con su comisión devuelta,
+    /// la próxima vez que se solicita un minteo.
+    pub fn get_pending_requests(env: Env, offset: u32, limit: u32) -> Vec<MintRequest> {
+        let pending: Vec<u32> = env.storage().persistent().get(&PENDING_MINTS_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let now = env.ledger().timestamp();
+        
+        let mut page = Vec::new(&env);
+        let mut matched: u32 = 0;
+        for request_id in pending.iter() {
+            if page.len() >= limit {
+                break;
+            }
+            
+            let request = Self::get_mint_request(env.clone(), request_id);
+            if now >= request.expires_at {
+                continue;
+            }
+            
+            if matched >= offset {
+                page.push_back(request);
+            }
+            matched += 1;
+        }
+        
+        page
+    }
+
+    /// Configura la vigencia de las solicitudes de minteo en segundos (solo admin)
+    pub fn set_mint_request_ttl(env: Env, ttl: u64) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if ttl == 0 {
+            panic!("TTL must be positive");
+        }
+        
+        env.storage().instance().set(&MINT_REQUEST_TTL_KEY, &ttl);
+    }
+
+    /// Obtiene la vigencia de las solicitudes de minteo en segundos
+    pub fn get_mint_request_ttl(env: Env) -> u64 {
+        env.storage().instance().get(&MINT_REQUEST_TTL_KEY).unwrap_or(DEFAULT_MINT_REQUEST_TTL)
+    }
+
+    /// Verifica si una dirección es el admin del contrato
+    pub fn is_admin(env: Env, who: Address) -> bool {
+        let admin: Option<Address> = env.storage().instance().get(&ADMIN_KEY);
+        admin == Some(who)
+    }
+
+    /// Obtiene el nombre del token
+    pub fn name(env: Env) -> String {
+        env.storage().instance().get(&NAME_KEY).unwrap()
+    }
+
+    /// Obtiene el símbolo del token
+    pub fn symbol(env: Env) -> String {
+        env.storage().instance().get(&SYMBOL_KEY).unwrap()
     }
 
     /// Obtiene el total de tokens mintados
@@ -272,5 +2334,687 @@ impl CulturalNFT {
         (owner, metadata, provenance)
     }
 
+    /// Valida la nota y el precio declarado de una transferencia
+    fn validate_memo(memo: &String, declared_value: Option<i128>) {
+        if memo.len() > MAX_MEMO_LENGTH {
+            panic!("Memo too long");
+        }
+        
+        if let Some(value) = declared_value {
+            if value < 0 {
+                panic!("Declared value cannot be negative");
+            }
+        }
+    }
+
+    /// Escribe el estado de un nuevo token (propietario, metadatos, índices y procedencia)
+    ///
+    /// Los minteos del admin no consultan el registro; los que provienen de un museo
+    /// (`approve_mint_request`) verifican antes con `ensure_museum_verified`.
+    fn mint_token(
+        env: &Env,
+        to: &Address,
+        token_id: u32,
+        cultural_metadata: CulturalMetadata,
+        provenance: Vec<PROVENANCERecord>,
+    ) {
+        // Verificar que el token no existe ni fue quemado
+        let owner_key = (OWNER_KEY, token_id);
+        if env.storage().persistent().has(&owner_key) || env.storage().persistent().has(&(BURNED_KEY, token_id)) {
+            panic!("Token already exists");
+        }
+        
+        // Asignar propietario
+        env.storage().persistent().set(&owner_key, to);
+        Self::record_owner(env, token_id, Some(to.clone()));
+        
+        // Guardar metadatos culturales
+        Self::save_metadata(env, token_id, &cultural_metadata);
+        
+        // Indexar por cultura y período
+        Self::index_attributes(env, token_id, &cultural_metadata);
+        
+        // Indexar ubicación física si se proporciona
+        if let ArtifactLocation::Coordinates(lat, lon) = cultural_metadata.location {
+            Self::validate_coordinates(lat, lon);
+            Self::add_to_geo_cell(env, token_id, lat, lon);
+        }
+        
+        // Guardar procedencia
+        for record in provenance.iter() {
+            Self::count_provenance_type(env, token_id, &record.transaction_type);
+        }
+        Self::store_provenance(env, token_id, provenance);
+        
+        // Incrementar contador
+        let mut count: u32 = env.storage().instance().get(&TOKEN_COUNT_KEY).unwrap_or(0);
+        count += 1;
+        env.storage().instance().set(&TOKEN_COUNT_KEY, &count);
+        
+        Self::notify_event_hooks(env, "mint", token_id, to);
+    }
+
+    /// Verifica con el MuseumRegistry configurado que el museo tiene una verificación vigente
+    fn ensure_museum_verified(env: &Env, museum: &Address) {
+        let museum_registry: Address = env.storage().instance().get(&MUSEUM_REGISTRY_KEY).unwrap();
+        if !MuseumRegistryClient::new(env, &museum_registry).is_verified(museum) {
+            panic!("Museum not verified");
+        }
+    }
+
+    /// Saca de la cola las solicitudes expiradas, las marca como `Expired` y devuelve su
+    /// comisión en custodia; retorna la cola depurada
+    fn prune_expired_requests(env: &Env) -> Vec<u32> {
+        let pending: Vec<u32> = env.storage().persistent().get(&PENDING_MINTS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        let now = env.ledger().timestamp();
+        
+        let mut remaining = Vec::new(env);
+        for request_id in pending.iter() {
+            let mut request = Self::get_mint_request(env.clone(), request_id);
+            if now < request.expires_at {
+                remaining.push_back(request_id);
+                continue;
+            }
+            
+            request.status = MintRequestStatus::Expired;
+            env.storage().persistent().set(&(MINT_REQUEST_KEY, request_id), &request);
+            if let Some((fee, payer)) = Self::take_mint_fee_paid(env, request_id) {
+                token::Client::new(env, &fee.token).transfer(&env.current_contract_address(), &payer, &fee.amount);
+            }
+        }
+        
+        if remaining.len() != pending.len() {
+            env.storage().persistent().set(&PENDING_MINTS_KEY, &remaining);
+        }
+        remaining
+    }
+
+    /// Saca una solicitud de la cola de pendientes
+    fn take_pending_request(env: &Env, request_id: u32) -> MintRequest {
+        let request = Self::get_mint_request(env.clone(), request_id);
+        if request.status != MintRequestStatus::Pending {
+            panic!("Mint request not pending");
+        }
+        
+        let mut pending: Vec<u32> = env.storage().persistent().get(&PENDING_MINTS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if let Some(position) = pending.first_index_of(request_id) {
+            pending.remove(position);
+        }
+        env.storage().persistent().set(&PENDING_MINTS_KEY, &pending);
+        
+        request
+    }
+
+    /// Otorga en SocialFi la recompensa configurada bajo `amount_key`
+    ///
+    /// Es best-effort: un fallo de SocialFi (ej. este contrato no autorizado como
+    /// emisor) no revierte el minteo ni la curaduría.
+    fn reward_content(env: &Env, user: &Address, amount_key: &Symbol, reason: &str) {
+        let socialfi: Option<Address> = env.storage().instance().get(&SOCIALFI_KEY);
+        let points: i128 = env.storage().instance().get(amount_key).unwrap_or(0);
+        if let Some(socialfi) = socialfi {
+            if points > 0 {
+                let _ = SocialFiClient::new(env, &socialfi).try_issue_points(
+                    &env.current_contract_address(),
+                    user,
+                    &points,
+                    &String::from_str(env, reason),
+                );
+            }
+        }
+    }
+
+    /// Reporta el minteo a las estadísticas del museo en MuseumRegistry
+    ///
+    /// Es best-effort: si este contrato no está autorizado como par, el minteo sigue adelante.
+    fn report_mint_activity(env: &Env, museum: &Address) {
+        let museum_registry: Address = env.storage().instance().get(&MUSEUM_REGISTRY_KEY).unwrap();
+        let _ = MuseumRegistryClient::new(env, &museum_registry).try_record_museum_activity(
+            &env.current_contract_address(),
+            museum,
+            &symbol_short!("mint"),
+            &1,
+        );
+    }
+
+    /// Retira el registro de la comisión cobrada por una solicitud, si la hubo: (comisión, pagador)
+    fn take_mint_fee_paid(env: &Env, request_id: u32) -> Option<(MintFee, Address)> {
+        let paid_key = (MINT_FEE_PAID_KEY, request_id);
+        let fee: Option<(MintFee, Address)> = env.storage().persistent().get(&paid_key);
+        if fee.is_some() {
+            env.storage().persistent().remove(&paid_key);
+        }
+        fee
+    }
+
+    /// Confirma la recepción con el contrato destino; revierte si no la acepta
+    fn ensure_received(env: &Env, operator: &Address, from: &Address, to: &Address, token_id: u32) {
+        if Self::is_contract_address(to) {
+            let receiver = NftReceiverClient::new(env, to);
+            if !receiver.on_nft_received(operator, from, &token_id) {
+                panic!("Transfer rejected by receiver");
+            }
+        }
+    }
+
+    /// Indica si la dirección corresponde a un contrato (strkey con prefijo "C")
+    fn is_contract_address(address: &Address) -> bool {
+        let strkey = address.to_string();
+        let mut buffer = [0u8; 56];
+        if strkey.len() as usize != buffer.len() {
+            return false;
+        }
+        strkey.copy_into_slice(&mut buffer);
+        buffer[0] == b'C'
+    }
+
+    /// Verifica que la expiración de una aprobación no esté en el pasado
+    fn validate_expiration(env: &Env, expires_at: u64) {
+        if expires_at != 0 && expires_at <= env.ledger().timestamp() {
+            panic!("Expiration must be in the future");
+        }
+    }
+
+    /// Indica si una aprobación con la expiración dada sigue vigente
+    fn is_live(env: &Env, expires_at: u64) -> bool {
+        expires_at == 0 || env.ledger().timestamp() < expires_at
+    }
+
+    /// Verifica que el spender tiene una aprobación vigente sobre el token o es operador del propietario
+    fn check_approval(env: &Env, spender: &Address, from: &Address, token_id: u32) {
+        if Self::get_approved(env.clone(), token_id) == Some(spender.clone()) {
+            return;
+        }
+        
+        if Self::is_approved_for_all(env.clone(), from.clone(), spender.clone()) {
+            return;
+        }
+        
+        panic!("Not approved");
+    }
+
+    /// Mueve el token y agrega el registro de procedencia correspondiente
+    fn move_token(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        token_id: u32,
+        transaction_type: &str,
+        notes: String,
+        declared_value: Option<i128>,
+    ) {
+        // Verificar que el token existe
+        let owner_key = (OWNER_KEY, token_id);
+        let current_owner: Address = env.storage().persistent().get(&owner_key).unwrap_or_else(|| {
+            panic!("Token does not exist");
+        });
+        
+        // Verificar que el caller es el propietario actual
+        if current_owner != *from {
+            panic!("Not the owner");
+        }
+        
+        Self::ensure_not_soulbound(env, token_id);
+        
+        // Los tokens con una fusión pendiente no se mueven
+        if env.storage().persistent().has(&(MERGE_LOCK_KEY, token_id)) {
+            panic!("Token locked for merge");
+        }
+        
+        // Los tokens disputados requieren co-autorización del admin
+        if env.storage().persistent().has(&(DISPUTE_KEY, token_id)) {
+            let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+            admin.require_auth();
+        }
+        
+        // Verificar que terminó el período de espera tras la última adquisición
+        if env.ledger().timestamp() < Self::get_transfer_unlock_time(env.clone(), token_id) {
+            panic!("Transfer cooldown active");
+        }
+        
+        // Transferir token
+        env.storage().persistent().set(&owner_key, to);
+        env.storage().persistent().set(&(PREVIOUS_OWNER_KEY, token_id), from);
+        Self::record_owner(env, token_id, Some(to.clone()));
+        
+        // Iniciar el período de espera del nuevo propietario; los custodios exentos
+        // (ej. custodia de subastas del marketplace) no inician espera para poder liquidar
+        let cooldown = Self::get_transfer_cooldown(env.clone(), token_id);
+        if cooldown > 0 && !Self::is_cooldown_exempt(env.clone(), to.clone()) {
+            env.storage().persistent().set(&(TRANSFER_UNLOCK_KEY, token_id), &(env.ledger().timestamp() + cooldown));
+        }
+        
+        // Limpiar aprobación y beneficiario del propietario anterior
+        env.storage().persistent().remove(&(APPROVAL_KEY, token_id));
+        env.storage().persistent().remove(&(BENEFICIAL_OWNER_KEY, token_id));
+        
+        // Agregar registro de procedencia
+        Self::append_provenance(env, token_id, from, to, transaction_type, notes, declared_value);
+        
+        Self::notify_transfer_hooks(env, from, to, token_id);
+        Self::notify_event_hooks(env, "transfer", token_id, to);
+    }
+
+    /// Notifica un evento a los contratos suscritos (best-effort, como los observadores
+    /// de transferencias)
+    fn notify_event_hooks(env: &Env, event_type: &str, token_id: u32, data: &Address) {
+        let event_type = String::from_str(env, event_type);
+        let hooks = Self::get_hooks(env.clone(), event_type.clone());
+        for hook in hooks.iter() {
+            let client = EventHookClient::new(env, &hook);
+            let _ = client.try_on_event(&event_type, &token_id, data);
+        }
+    }
+
+    /// Notifica la transferencia a los observadores registrados
+    ///
+    /// Best-effort: si un observador falla, la transferencia sigue adelante.
+    fn notify_transfer_hooks(env: &Env, from: &Address, to: &Address, token_id: u32) {
+        let hooks = Self::get_transfer_hooks(env.clone());
+        for hook in hooks.iter() {
+            let client = TransferHookClient::new(env, &hook);
+            let _ = client.try_on_transfer(from, to, &token_id);
+        }
+    }
+
+    /// Agrega un registro al historial de procedencia del token
+    fn append_provenance(
+        env: &Env,
+        token_id: u32,
+        from: &Address,
+        to: &Address,
+        transaction_type: &str,
+        notes: String,
+        declared_value: Option<i128>,
+    ) {
+        let provenance_key = (PROVENANCE_KEY, token_id);
+        let mut provenance: Vec<PROVENANCERecord> = env.storage().persistent().get(&provenance_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        
+        let new_record = PROVENANCERecord {
+            date: env.ledger().timestamp(),
+            from: from.clone(),
+            to: to.clone(),
+            transaction_type: String::from_str(env, transaction_type),
+            notes,
+            declared_value,
+        };
+        
+        Self::count_provenance_type(env, token_id, &new_record.transaction_type);
+        provenance.push_back(new_record);
+        Self::store_provenance(env, token_id, provenance);
+    }
+
+    /// Incrementa el contador de registros de procedencia de un tipo
+    fn count_provenance_type(env: &Env, token_id: u32, transaction_type: &String) {
+        let counts_key = (PROVENANCE_TYPE_COUNT_KEY, token_id);
+        let mut counts: Vec<(String, u32)> = env.storage().persistent().get(&counts_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        
+        for i in 0..counts.len() {
+            let (existing_type, count) = counts.get(i).unwrap();
+            if existing_type == *transaction_type {
+                counts.set(i, (existing_type, count + 1));
+                env.storage().persistent().set(&counts_key, &counts);
+                return;
+            }
+        }
+        
+        counts.push_back((transaction_type.clone(), 1));
+        env.storage().persistent().set(&counts_key, &counts);
+    }
+
+    /// Guarda la procedencia reciente, moviendo al archivo los registros más antiguos
+    /// que excedan el máximo configurado
+    fn store_provenance(env: &Env, token_id: u32, provenance: Vec<PROVENANCERecord>) {
+        let max_length = Self::get_max_provenance_length(env.clone());
+        let mut provenance = provenance;
+        
+        if provenance.len() > max_length {
+            let overflow = provenance.len() - max_length;
+            let count_key = (PROVENANCE_ARCHIVE_COUNT_KEY, token_id);
+            let mut archived: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            
+            for record in provenance.slice(0..overflow).iter() {
+                let page_key = (PROVENANCE_ARCHIVE_KEY, token_id, archived / PROVENANCE_ARCHIVE_PAGE_SIZE);
+                let mut page: Vec<PROVENANCERecord> = env.storage().persistent().get(&page_key).unwrap_or_else(|| {
+                    Vec::new(env)
+                });
+                page.push_back(record);
+                env.storage().persistent().set(&page_key, &page);
+                archived += 1;
+            }
+            
+            env.storage().persistent().set(&count_key, &archived);
+            provenance = provenance.slice(overflow..provenance.len());
+        }
+        
+        env.storage().persistent().set(&(PROVENANCE_KEY, token_id), &provenance);
+    }
+
+    /// Quema un token: deja un registro final de procedencia y lo saca de los índices
+    ///
+    /// Los metadatos y la procedencia se conservan como registro histórico.
+    fn burn_token(env: &Env, token_id: u32, reason: String) {
+        let owner_key = (OWNER_KEY, token_id);
+        let owner: Address = env.storage().persistent().get(&owner_key).unwrap_or_else(|| {
+            panic!("Token does not exist");
+        });
+        
+        Self::append_provenance(env, token_id, &owner, &env.current_contract_address(), "burn", reason, None);
+        
+        env.storage().persistent().remove(&owner_key);
+        env.storage().persistent().remove(&(APPROVAL_KEY, token_id));
+        env.storage().persistent().remove(&(BENEFICIAL_OWNER_KEY, token_id));
+        env.storage().persistent().set(&(BURNED_KEY, token_id), &true);
+        Self::record_owner(env, token_id, None);
+        
+        // Quitar el token de los índices
+        let metadata = Self::load_metadata(env, token_id);
+        Self::remove_from_index(env, &(CULTURE_INDEX_KEY, metadata.culture.clone()), token_id);
+        Self::remove_from_index(env, &(PERIOD_INDEX_KEY, metadata.period.clone()), token_id);
+        Self::remove_from_index(env, &(MUSEUM_INDEX_KEY, metadata.museum_address.clone()), token_id);
+        if let ArtifactLocation::Coordinates(lat, lon) = metadata.location {
+            Self::remove_from_geo_cell(env, token_id, lat, lon);
+        }
+        for tag in Self::get_tags(env.clone(), token_id).iter() {
+            Self::remove_from_index(env, &(TAG_INDEX_KEY, tag), token_id);
+        }
+        env.storage().persistent().remove(&(TAGS_KEY, token_id));
+        
+        // Decrementar contador
+        let count: u32 = env.storage().instance().get(&TOKEN_COUNT_KEY).unwrap_or(0);
+        env.storage().instance().set(&TOKEN_COUNT_KEY, &count.saturating_sub(1));
+        
+        Self::notify_event_hooks(env, "burn", token_id, &owner);
+    }
+
+    /// Borra los datos que quedan de un token quemado al fusionarse; solo se conserva la
+    /// marca de quemado para que su ID no se reutilice
+    fn purge_token(env: &Env, token_id: u32) {
+        let storage = env.storage().persistent();
+        
+        // Procedencia, archivo y atestaciones
+        let archived = Self::get_archived_provenance_count(env.clone(), token_id);
+        let total = archived + Self::get_provenance(env.clone(), token_id).len();
+        for record_index in 0..total {
+            storage.remove(&(ATTESTATION_KEY, token_id, record_index));
+        }
+        for page_number in 0..archived.div_ceil(PROVENANCE_ARCHIVE_PAGE_SIZE) {
+            storage.remove(&(PROVENANCE_ARCHIVE_KEY, token_id, page_number));
+        }
+        storage.remove(&(PROVENANCE_ARCHIVE_COUNT_KEY, token_id));
+        storage.remove(&(PROVENANCE_TYPE_COUNT_KEY, token_id));
+        storage.remove(&(PROVENANCE_KEY, token_id));
+        
+        // Metadatos, versiones y traducciones
+        let versions = Self::get_metadata_version_count(env.clone(), token_id);
+        for version in versions.saturating_sub(MAX_METADATA_VERSIONS - 1).max(1)..=versions {
+            storage.remove(&(METADATA_VERSION_KEY, token_id, version));
+        }
+        storage.remove(&(METADATA_VERSION_COUNT_KEY, token_id));
+        for lang_code in Self::get_metadata_languages(env.clone(), token_id).iter() {
+            storage.remove(&(LOCALIZED_METADATA_KEY, token_id, lang_code));
+        }
+        storage.remove(&(LANGUAGES_KEY, token_id));
+        storage.remove(&(METADATA_KEY, token_id));
+        
+        // Relaciones (también del lado de los otros tokens)
+        for (other, _) in Self::get_related_tokens(env.clone(), token_id).iter() {
+            let mut related = Self::get_related_tokens(env.clone(), other);
+            if let Some(position) = related.iter().position(|(related_id, _)| related_id == token_id) {
+                related.remove(position as u32);
+                storage.set(&(RELATED_KEY, other), &related);
+            }
+        }
+        storage.remove(&(RELATED_KEY, token_id));
+        
+        // Resto del estado del token
+        storage.remove(&(OWNER_HISTORY_KEY, token_id));
+        storage.remove(&(PREVIOUS_OWNER_KEY, token_id));
+        storage.remove(&(TRANSFER_COOLDOWN_KEY, token_id));
+        storage.remove(&(TRANSFER_UNLOCK_KEY, token_id));
+        storage.remove(&(MULTI_ROYALTY_KEY, token_id));
+        storage.remove(&(ROYALTY_SHARES_KEY, token_id));
+        storage.remove(&(ROYALTY_ESCALATION_KEY, token_id));
+        storage.remove(&(SENSITIVE_KEY, token_id));
+        storage.remove(&(CONDITION_REPORT_KEY, token_id));
+        storage.remove(&(CONTRIBUTORS_KEY, token_id));
+        storage.remove(&(BURN_PROPOSAL_KEY, token_id));
+    }
+
+    /// Procedencia completa de un token (archivados + recientes), del más antiguo al más reciente
+    fn full_provenance(env: &Env, token_id: u32) -> Vec<PROVENANCERecord> {
+        let mut provenance = Vec::new(env);
+        let archived = Self::get_archived_provenance_count(env.clone(), token_id);
+        for page_number in 0..archived.div_ceil(PROVENANCE_ARCHIVE_PAGE_SIZE) {
+            let page: Vec<PROVENANCERecord> = env.storage().persistent().get(&(PROVENANCE_ARCHIVE_KEY, token_id, page_number)).unwrap();
+            provenance.append(&page);
+        }
+        provenance.append(&Self::get_provenance(env.clone(), token_id));
+        provenance
+    }
+
+    /// Saca una propuesta de fusión y desbloquea sus tokens
+    fn take_merge_proposal(env: &Env, merge_id: u32) -> MergeProposal {
+        let proposal = Self::get_merge_proposal(env.clone(), merge_id).unwrap_or_else(|| {
+            panic!("Merge not proposed");
+        });
+        env.storage().persistent().remove(&(MERGE_KEY, merge_id));
+        env.storage().persistent().remove(&(MERGE_LOCK_KEY, proposal.token_id_a));
+        env.storage().persistent().remove(&(MERGE_LOCK_KEY, proposal.token_id_b));
+        proposal
+    }
+
+    /// Lee la cadena de propietarios del token
+    fn owner_history(env: &Env, token_id: u32) -> Vec<(Option<Address>, u64)> {
+        env.storage().persistent().get(&(OWNER_HISTORY_KEY, token_id)).unwrap_or_else(|| {
+            Vec::new(env)
+        })
+    }
+
+    /// Agrega un cambio de propietario a la cadena (None marca la quema)
+    fn record_owner(env: &Env, token_id: u32, owner: Option<Address>) {
+        let mut history = Self::owner_history(env, token_id);
+        history.push_back((owner, env.ledger().timestamp()));
+        env.storage().persistent().set(&(OWNER_HISTORY_KEY, token_id), &history);
+    }
+
+    /// Verifica que el caller es el museo o uno de sus curadores con el rol indicado
+    fn ensure_museum_or_curator(env: &Env, caller: &Address, museum: &Address, role: CuratorRole) {
+        if caller == museum {
+            return;
+        }
+        
+        let museum_registry: Address = env.storage().instance().get(&MUSEUM_REGISTRY_KEY).unwrap();
+        let registry = MuseumRegistryClient::new(env, &museum_registry);
+        if registry.is_curator(museum, caller) != Some(role) {
+            panic!("Not the token museum");
+        }
+    }
+
+    /// Verifica que el caller puede relacionar dos tokens: el admin o el museo de ambos
+    fn ensure_can_link(env: &Env, caller: &Address, token_a: u32, token_b: u32) {
+        // Verificar que el caller es el museo de ambos tokens o el admin
+        caller.require_auth();
+        
+        if token_a == token_b {
+            panic!("Cannot link a token to itself");
+        }
+        
+        let museum_a = Self::load_metadata(env, token_a).museum_address;
+        let museum_b = Self::load_metadata(env, token_b).museum_address;
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        if *caller != admin && (*caller != museum_a || *caller != museum_b) {
+            panic!("Not authorized over both tokens");
+        }
+    }
+
+    /// Verifica que el token no es intransferible
+    fn ensure_not_soulbound(env: &Env, token_id: u32) {
+        if Self::load_metadata(env, token_id).is_soulbound {
+            panic!("Soulbound token");
+        }
+    }
+
+    /// Lee los metadatos completos del token sin aplicar restricciones
+    fn load_metadata(env: &Env, token_id: u32) -> CulturalMetadata {
+        env.storage().persistent().get(&(METADATA_KEY, token_id)).unwrap_or_else(|| {
+            panic!("Token metadata not found");
+        })
+    }
+
+    /// Versión pública de los metadatos de un token sensible: solo título y museo
+    fn redact_metadata(env: &Env, metadata: &CulturalMetadata) -> CulturalMetadata {
+        let empty = String::from_str(env, "");
+        CulturalMetadata {
+            title: metadata.title.clone(),
+            artist: empty.clone(),
+            period: empty.clone(),
+            culture: empty.clone(),
+            material: empty.clone(),
+            dimensions: empty.clone(),
+            condition: empty.clone(),
+            significance: empty,
+            museum_address: metadata.museum_address.clone(),
+            location: ArtifactLocation::Unknown,
+            is_soulbound: metadata.is_soulbound,
+        }
+    }
+
+    /// Guarda los metadatos actuales y una instantánea como nueva versión
+    ///
+    /// Solo se conservan las últimas `MAX_METADATA_VERSIONS` versiones.
+    fn save_metadata(env: &Env, token_id: u32, metadata: &CulturalMetadata) {
+        env.storage().persistent().set(&(METADATA_KEY, token_id), metadata);
+        
+        let count_key = (METADATA_VERSION_COUNT_KEY, token_id);
+        let version: u32 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+        env.storage().persistent().set(&(METADATA_VERSION_KEY, token_id, version), metadata);
+        env.storage().persistent().set(&count_key, &version);
+        
+        // Descartar la versión que queda fuera de la ventana
+        if version > MAX_METADATA_VERSIONS {
+            env.storage().persistent().remove(&(METADATA_VERSION_KEY, token_id, version - MAX_METADATA_VERSIONS));
+        }
+    }
+
+    /// Agrega el token a los índices de cultura, período y museo
+    fn index_attributes(env: &Env, token_id: u32, metadata: &CulturalMetadata) {
+        Self::add_to_index(env, &(CULTURE_INDEX_KEY, metadata.culture.clone()), token_id);
+        Self::add_to_index(env, &(PERIOD_INDEX_KEY, metadata.period.clone()), token_id);
+        Self::add_to_index(env, &(MUSEUM_INDEX_KEY, metadata.museum_address.clone()), token_id);
+    }
+
+    /// Agrega un token a un índice por atributo
+    fn add_to_index<K: IntoVal<Env, Val>>(env: &Env, index_key: &K, token_id: u32) {
+        let mut index: Vec<u32> = env.storage().persistent().get(index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        index.push_back(token_id);
+        env.storage().persistent().set(index_key, &index);
+    }
+
+    /// Quita un token de un índice por atributo
+    fn remove_from_index<K: IntoVal<Env, Val>>(env: &Env, index_key: &K, token_id: u32) {
+        let mut index: Vec<u32> = env.storage().persistent().get(index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if let Some(position) = index.first_index_of(token_id) {
+            index.remove(position);
+            env.storage().persistent().set(index_key, &index);
+        }
+    }
+
+    /// Agrega una entrada a la cadena de atribución del token
+    fn record_contribution(env: &Env, token_id: u32, contributor: &Address, contribution_type: &str) {
+        let contributors_key = (CONTRIBUTORS_KEY, token_id);
+        let mut contributors: Vec<(Address, String, u64)> = env.storage().persistent().get(&contributors_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        contributors.push_back((
+            contributor.clone(),
+            String::from_str(env, contribution_type),
+            env.ledger().timestamp(),
+        ));
+        env.storage().persistent().set(&contributors_key, &contributors);
+    }
+
+    /// Devuelve una página de un índice, acotada a MAX_PAGE_SIZE
+    fn paginate(env: &Env, index: &Vec<u32>, start: u32, limit: u32) -> Vec<u32> {
+        let mut page = Vec::new(env);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(index.len());
+        
+        for i in start..end {
+            page.push_back(index.get(i).unwrap());
+        }
+        
+        page
+    }
+
+    /// Valida que las coordenadas estén en rango
+    fn validate_coordinates(lat: i64, lon: i64) {
+        if !(-MAX_LATITUDE..=MAX_LATITUDE).contains(&lat) || !(-MAX_LONGITUDE..=MAX_LONGITUDE).contains(&lon) {
+            panic!("Invalid coordinates");
+        }
+    }
+
+    /// Coseno (en millonésimas) de una latitud absoluta, interpolando la tabla cada 10 grados
+    ///
+    /// La interpolación lineal queda por debajo del coseno real, así que la caja de búsqueda
+    /// resulta apenas más ancha, nunca más angosta.
+    fn cos_millionths(abs_lat: i64) -> i64 {
+        let step = 10 * GEO_CELL_SIZE;
+        let i = ((abs_lat / step) as usize).min(COS_BY_TEN_DEGREES.len() - 2);
+        let fraction = abs_lat - (i as i64) * step;
+        COS_BY_TEN_DEGREES[i] - (COS_BY_TEN_DEGREES[i] - COS_BY_TEN_DEGREES[i + 1]) * fraction / step
+    }
+
+    /// Normaliza un índice de celda de longitud a [-180, 180), cruzando el antimeridiano
+    fn wrap_lon_cell(lon_cell: i64) -> i64 {
+        let cells = 2 * MAX_LONGITUDE / GEO_CELL_SIZE;
+        (lon_cell + cells / 2).rem_euclid(cells) - cells / 2
+    }
+
+    /// Distancia en longitud entre dos puntos, por el lado más corto del globo
+    fn lon_distance(a: i64, b: i64) -> i64 {
+        let distance = (a - b).abs();
+        distance.min(2 * MAX_LONGITUDE - distance)
+    }
+
+    /// Clave de la celda geográfica de unas coordenadas
+    fn geo_cell_key(lat: i64, lon: i64) -> (Symbol, i64, i64) {
+        (GEO_CELL_KEY, lat.div_euclid(GEO_CELL_SIZE), Self::wrap_lon_cell(lon.div_euclid(GEO_CELL_SIZE)))
+    }
+
+    /// Agrega el token (con sus coordenadas) a la celda geográfica correspondiente
+    fn add_to_geo_cell(env: &Env, token_id: u32, lat: i64, lon: i64) {
+        let cell_key = Self::geo_cell_key(lat, lon);
+        let mut cell: Vec<(u32, i64, i64)> = env.storage().persistent().get(&cell_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        cell.push_back((token_id, lat, lon));
+        env.storage().persistent().set(&cell_key, &cell);
+    }
 
+    /// Quita el token de la celda geográfica de sus coordenadas
+    fn remove_from_geo_cell(env: &Env, token_id: u32, lat: i64, lon: i64) {
+        let cell_key = Self::geo_cell_key(lat, lon);
+        let mut cell: Vec<(u32, i64, i64)> = env.storage().persistent().get(&cell_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if let Some(position) = cell.iter().position(|(id, _, _)| id == token_id) {
+            cell.remove(position as u32);
+        }
+        env.storage().persistent().set(&cell_key, &cell);
+    }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,1818 @@
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Ledger, MockAuth, MockAuthInvoke};
+use soroban_sdk::{contract, contractimpl, IntoVal, Symbol};
+
+// Registro de museos simulado: responde is_verified e is_curator
+#[contract]
+pub struct MockRegistry;
+
+#[contractimpl]
+impl MockRegistry {
+    pub fn set_verified(env: Env, museum_address: Address, verified: bool) {
+        env.storage().instance().set(&museum_address, &verified);
+    }
+
+    pub fn is_verified(env: Env, museum_address: Address) -> bool {
+        env.storage().instance().get(&museum_address).unwrap_or(false)
+    }
+
+    pub fn set_curator(env: Env, museum: Address, curator: Address, role: CuratorRole) {
+        env.storage().instance().set(&(museum, curator), &role);
+    }
+
+    pub fn is_curator(env: Env, museum: Address, curator: Address) -> Option<CuratorRole> {
+        env.storage().instance().get(&(museum, curator))
+    }
+}
+
+// Observador de transferencias simulado: guarda cada notificación o falla si se le indica
+#[contract]
+pub struct MockTransferHook;
+
+#[contractimpl]
+impl MockTransferHook {
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage().instance().set(&symbol_short!("FAILING"), &failing);
+    }
+
+    pub fn on_transfer(env: Env, from: Address, to: Address, token_id: u32) {
+        if env.storage().instance().get(&symbol_short!("FAILING")).unwrap_or(false) {
+            panic!("hook failure");
+        }
+
+        let mut notifications: Vec<(Address, Address, u32)> = env.storage().instance().get(&symbol_short!("NOTIF")).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        notifications.push_back((from, to, token_id));
+        env.storage().instance().set(&symbol_short!("NOTIF"), &notifications);
+    }
+
+    pub fn notifications(env: Env) -> Vec<(Address, Address, u32)> {
+        env.storage().instance().get(&symbol_short!("NOTIF")).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+}
+
+// Suscriptor de eventos simulado: guarda cada evento recibido
+#[contract]
+pub struct MockEventHook;
+
+#[contractimpl]
+impl MockEventHook {
+    pub fn on_event(env: Env, event_type: String, token_id: u32, data: Address) {
+        let mut events: Vec<(String, u32, Address)> = env.storage().instance().get(&symbol_short!("EVENTS")).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        events.push_back((event_type, token_id, data));
+        env.storage().instance().set(&symbol_short!("EVENTS"), &events);
+    }
+
+    pub fn events(env: Env) -> Vec<(String, u32, Address)> {
+        env.storage().instance().get(&symbol_short!("EVENTS")).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+}
+
+// Receptor de NFTs simulado: acepta o rechaza según se configure y guarda cada aviso
+#[contract]
+pub struct MockNftReceiver;
+
+#[contractimpl]
+impl MockNftReceiver {
+    pub fn set_accepting(env: Env, accepting: bool) {
+        env.storage().instance().set(&symbol_short!("ACCEPT"), &accepting);
+    }
+
+    pub fn on_nft_received(env: Env, operator: Address, from: Address, token_id: u32) -> bool {
+        let mut received: Vec<(Address, Address, u32)> = env.storage().instance().get(&symbol_short!("RECEIVED")).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        received.push_back((operator, from, token_id));
+        env.storage().instance().set(&symbol_short!("RECEIVED"), &received);
+
+        env.storage().instance().get(&symbol_short!("ACCEPT")).unwrap_or(false)
+    }
+
+    pub fn received(env: Env) -> Vec<(Address, Address, u32)> {
+        env.storage().instance().get(&symbol_short!("RECEIVED")).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+}
+
+fn sample_metadata(env: &Env, museum: &Address) -> CulturalMetadata {
+    CulturalMetadata {
+        title: String::from_str(env, "Vasija ceremonial"),
+        artist: String::from_str(env, "Desconocido"),
+        period: String::from_str(env, "Precolombino"),
+        culture: String::from_str(env, "Diaguita"),
+        material: String::from_str(env, "Cerámica"),
+        dimensions: String::from_str(env, "30x20 cm"),
+        condition: String::from_str(env, "Buena"),
+        significance: String::from_str(env, "Uso ritual"),
+        museum_address: museum.clone(),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    }
+}
+
+fn setup(env: &Env) -> (CulturalNFTClient<'_>, Address, Address) {
+    let contract_id = env.register_contract(None, CulturalNFT);
+    let client = CulturalNFTClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let museum = Address::generate(env);
+    let registry = env.register_contract(None, MockRegistry);
+    MockRegistryClient::new(env, &registry).set_verified(&museum, &true);
+    client.initialize(
+        &admin,
+        &String::from_str(env, "PermaMuseum"),
+        &String::from_str(env, "PMUS"),
+        &registry,
+        &None,
+    );
+
+    (client, admin, museum)
+}
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // El propietario aprueba al spender
+    client.approve(&owner, &spender, &1);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            owner.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "approve"),
+                    (owner.clone(), spender.clone(), 1u32).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(client.get_approved(&1), Some(spender.clone()));
+
+    // El spender transfiere al comprador
+    client.transfer_from(&spender, &owner, &buyer, &1);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            spender.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "transfer_from"),
+                    (spender.clone(), owner.clone(), buyer.clone(), 1u32).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+
+    assert_eq!(client.owner_of(&1), buyer);
+    assert_eq!(client.get_approved(&1), None);
+
+    let provenance = client.get_provenance(&1);
+    assert_eq!(provenance.len(), 1);
+    let record = provenance.get(0).unwrap();
+    assert_eq!(record.transaction_type, String::from_str(&env, "transfer_from"));
+    assert_eq!(record.from, owner);
+    assert_eq!(record.to, buyer);
+
+    // La aprobación ya se consumió: un segundo transfer_from falla
+    let result = client.try_transfer_from(&spender, &buyer, &owner, &1);
+    assert!(result.is_err());
+    assert_eq!(client.owner_of(&1), buyer);
+}
+
+#[test]
+fn test_mint_request_approve() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+
+    let request_id = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert_eq!(client.get_pending_requests(&0, &10).len(), 1);
+
+    let token_id = client.approve_mint_request(&request_id);
+    assert_eq!(client.owner_of(&token_id), museum);
+
+    let request = client.get_mint_request(&request_id);
+    assert_eq!(request.status, MintRequestStatus::Approved);
+    assert_eq!(request.token_id, token_id);
+    assert_eq!(client.get_pending_requests(&0, &10).len(), 0);
+
+    // Una solicitud ya resuelta no puede aprobarse de nuevo
+    assert!(client.try_approve_mint_request(&request_id).is_err());
+}
+
+#[test]
+fn test_mint_request_reject() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+
+    let request_id = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.reject_mint_request(&request_id, &String::from_str(&env, "Incomplete provenance"));
+
+    let request = client.get_mint_request(&request_id);
+    assert_eq!(request.status, MintRequestStatus::Rejected);
+    assert_eq!(request.reason, String::from_str(&env, "Incomplete provenance"));
+    assert_eq!(client.total_supply(), 0);
+    assert!(client.try_approve_mint_request(&request_id).is_err());
+}
+
+#[test]
+fn test_mint_request_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    client.set_mint_request_ttl(&3600);
+
+    let request_id = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 3600);
+    assert_eq!(client.get_pending_requests(&0, &10).len(), 0);
+    assert!(client.try_approve_mint_request(&request_id).is_err());
+}
+
+#[test]
+fn test_expired_mint_requests_pruned_on_request() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, museum) = setup(&env);
+    let treasury = Address::generate(&env);
+    let fee_token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_client = token::Client::new(&env, &fee_token);
+    token::StellarAssetClient::new(&env, &fee_token).mint(&museum, &200);
+    client.set_mint_fee(&fee_token, &100, &treasury);
+    client.set_mint_request_ttl(&3600);
+
+    let expired_id = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 3600);
+
+    // La siguiente solicitud retira la expirada de la cola y devuelve su comisión
+    let live_id = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert_eq!(client.get_mint_request(&expired_id).status, MintRequestStatus::Expired);
+    assert_eq!(token_client.balance(&museum), 100);
+    assert_eq!(token_client.balance(&client.address), 100);
+
+    let pending = client.get_pending_requests(&0, &10);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().id, live_id);
+    assert!(client.try_approve_mint_request(&expired_id).is_err());
+    assert!(client.try_reject_mint_request(&expired_id, &String::from_str(&env, "Tarde")).is_err());
+}
+
+#[test]
+fn test_mint_request_requires_verified_museum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, _museum) = setup(&env);
+    let unverified = Address::generate(&env);
+
+    let result = client.try_request_mint(&unverified, &sample_metadata(&env, &unverified), &Vec::new(&env));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_mint_request_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, museum) = setup(&env);
+    let treasury = Address::generate(&env);
+    let fee_token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let token_client = token::Client::new(&env, &fee_token);
+    token::StellarAssetClient::new(&env, &fee_token).mint(&museum, &150);
+
+    // Desactivada: no se cobra nada
+    client.set_mint_fee(&fee_token, &0, &treasury);
+    client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert_eq!(token_client.balance(&museum), 150);
+
+    // Activada: se cobra al solicitar y va a la tesorería al aprobar
+    client.set_mint_fee(&fee_token, &100, &treasury);
+    let request_id = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert_eq!(token_client.balance(&museum), 50);
+    assert_eq!(token_client.balance(&client.address), 100);
+    client.approve_mint_request(&request_id);
+    assert_eq!(token_client.balance(&treasury), 100);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    // Sin fondos suficientes la solicitud no se crea
+    let result = client.try_request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert!(result.is_err());
+    assert_eq!(client.get_pending_requests(&0, &10).len(), 1);
+    assert_eq!(token_client.balance(&museum), 50);
+
+    // Al rechazar se devuelve la comisión
+    token::StellarAssetClient::new(&env, &fee_token).mint(&museum, &50);
+    let request_id = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert_eq!(token_client.balance(&museum), 0);
+    client.reject_mint_request(&request_id, &String::from_str(&env, "Duplicate"));
+    assert_eq!(token_client.balance(&museum), 100);
+}
+
+#[test]
+fn test_royalty_info_rounding_three_recipients() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let community = Address::generate(&env);
+    let fund = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(RoyaltyShare { recipient: museum.clone(), bps: 333 });
+    shares.push_back(RoyaltyShare { recipient: community.clone(), bps: 333 });
+    shares.push_back(RoyaltyShare { recipient: fund.clone(), bps: 334 });
+    client.set_royalty_shares(&1, &shares);
+
+    // 1001 * 1000 / 10000 = 100; las partes redondeadas suman 99 y el resto va al museo
+    let amounts = client.royalty_info(&1, &1001);
+    assert_eq!(amounts.len(), 3);
+    assert_eq!(amounts.get(0).unwrap(), (museum.clone(), 34));
+    assert_eq!(amounts.get(1).unwrap(), (community.clone(), 33));
+    assert_eq!(amounts.get(2).unwrap(), (fund.clone(), 33));
+
+    let mut total = 0;
+    for (_, amount) in amounts.iter() {
+        total += amount;
+    }
+    assert_eq!(total, 100);
+
+    // Sin royalties configuradas no hay montos
+    client.mint_cultural_nft(&owner, &2, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert!(client.royalty_info(&2, &1001).is_empty());
+}
+
+#[test]
+fn test_museum_token_royalty_summary() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let other_museum = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.mint_cultural_nft(&owner, &2, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.mint_cultural_nft(&owner, &3, &sample_metadata(&env, &other_museum), &Vec::new(&env));
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(RoyaltyShare { recipient: museum.clone(), bps: 500 });
+    client.set_royalty_shares(&1, &shares);
+    client.set_royalty_shares(&2, &shares);
+    client.set_royalty_shares(&3, &shares);
+
+    // Token 1 vendido dos veces: cuenta el último precio declarado
+    client.transfer_with_memo(&owner, &collector, &1, &String::from_str(&env, "Venta"), &Some(10_000));
+    client.transfer_with_memo(&collector, &owner, &1, &String::from_str(&env, "Reventa"), &Some(20_000));
+
+    let (summary, expected_total) = client.get_museum_token_royalty_summary(&museum, &0, &10);
+    assert_eq!(summary.len(), 2);
+    let first = summary.get(0).unwrap();
+    assert_eq!((first.token_id, first.royalty_bps, first.last_sale_price, first.expected_royalty), (1, 500, 20_000, 1_000));
+    let second = summary.get(1).unwrap();
+    assert_eq!((second.token_id, second.last_sale_price, second.expected_royalty), (2, 0, 0));
+    assert_eq!(expected_total, 1_000);
+
+    assert_eq!(client.get_tokens_by_museum(&other_museum, &0, &10).len(), 1);
+}
+
+#[test]
+fn test_royalty_shares_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(RoyaltyShare { recipient: museum.clone(), bps: 600 });
+    shares.push_back(RoyaltyShare { recipient: owner.clone(), bps: 401 });
+    assert!(client.try_set_royalty_shares(&1, &shares).is_err());
+    assert!(client.get_royalty_shares(&1).is_empty());
+}
+
+#[test]
+fn test_multi_royalty_info_wrapper() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let painter = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // Las listas de destinatarios y porcentajes deben tener el mismo largo
+    let mut recipients = Vec::new(&env);
+    recipients.push_back(museum.clone());
+    recipients.push_back(painter.clone());
+    let mut percentages = Vec::new(&env);
+    percentages.push_back(300);
+    assert!(client.try_set_multi_royalty_info(&1, &recipients, &percentages).is_err());
+
+    // Se guarda como partes de royalties, con el mismo tope que set_royalty_shares
+    percentages.push_back(200);
+    client.set_multi_royalty_info(&1, &recipients, &percentages);
+    let shares = client.get_royalty_shares(&1);
+    assert_eq!(shares.len(), 2);
+    assert_eq!(shares.get(1).unwrap().recipient, painter);
+    assert_eq!(shares.get(1).unwrap().bps, 200);
+
+    percentages.set(1, 800);
+    assert!(client.try_set_multi_royalty_info(&1, &recipients, &percentages).is_err());
+}
+
+#[test]
+fn test_attribution_chain() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let researcher = Address::generate(&env);
+
+    let mut provenance = Vec::new(&env);
+    provenance.push_back(PROVENANCERecord {
+        date: 0,
+        from: museum.clone(),
+        to: owner.clone(),
+        transaction_type: String::from_str(&env, "donation"),
+        notes: String::from_str(&env, "Donación original"),
+        declared_value: None,
+    });
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &provenance);
+
+    // Actualización de metadatos: cambia la cultura y se reindexa
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    let mut metadata = sample_metadata(&env, &museum);
+    metadata.culture = String::from_str(&env, "Inca");
+    client.update_cultural_metadata(&museum, &1, &metadata);
+    assert!(client.get_tokens_by_culture(&String::from_str(&env, "Diaguita"), &0, &10).is_empty());
+    assert_eq!(client.get_tokens_by_culture(&String::from_str(&env, "Inca"), &0, &10).len(), 1);
+
+    // Informe de conservación
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    client.add_condition_report(&museum, &1, &String::from_str(&env, "Restaurada"), &String::from_str(&env, "Limpieza"));
+    assert_eq!(client.get_cultural_metadata(&1).condition, String::from_str(&env, "Restaurada"));
+    assert_eq!(client.get_condition_reports(&1).len(), 1);
+
+    // Atestación de procedencia
+    env.ledger().with_mut(|l| l.timestamp += 10);
+    client.register_researcher(&researcher);
+    client.attest_provenance(&researcher, &1, &0, &String::from_str(&env, "ipfs://evidencia"));
+
+    let contributors = client.get_contributors(&1);
+    assert_eq!(client.get_contribution_count(&1), 4);
+    assert_eq!(contributors.get(0).unwrap(), (admin, String::from_str(&env, "mint"), 0));
+    assert_eq!(contributors.get(1).unwrap(), (museum.clone(), String::from_str(&env, "metadata"), 10));
+    assert_eq!(contributors.get(2).unwrap(), (museum, String::from_str(&env, "condition_report"), 20));
+    assert_eq!(contributors.get(3).unwrap(), (researcher, String::from_str(&env, "attestation"), 30));
+}
+
+#[test]
+fn test_metadata_versions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    let mut metadata = sample_metadata(&env, &museum);
+    metadata.title = String::from_str(&env, "Vasija ceremonial restaurada");
+    client.update_cultural_metadata(&museum, &1, &metadata);
+    client.add_condition_report(&museum, &1, &String::from_str(&env, "Excelente"), &String::from_str(&env, "Revisión anual"));
+    client.update_artifact_location(&museum, &1, &-33_450_000, &-70_660_000);
+
+    assert_eq!(client.get_metadata_version_count(&1), 4);
+
+    // La versión 1 conserva los metadatos del minteo
+    let original = client.get_metadata_version(&1, &1);
+    assert_eq!(original.title, String::from_str(&env, "Vasija ceremonial"));
+    assert_eq!(original.condition, String::from_str(&env, "Buena"));
+    assert!(matches!(original.location, ArtifactLocation::Unknown));
+
+    // get_cultural_metadata devuelve siempre la última versión
+    let latest = client.get_cultural_metadata(&1);
+    assert_eq!(latest.title, String::from_str(&env, "Vasija ceremonial restaurada"));
+    assert_eq!(latest.condition, String::from_str(&env, "Excelente"));
+    assert!(matches!(latest.location, ArtifactLocation::Coordinates(-33_450_000, -70_660_000)));
+
+    assert!(client.try_get_metadata_version(&1, &5).is_err());
+}
+
+#[test]
+fn test_transfer_hooks_notified() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    let failing = env.register_contract(None, MockTransferHook);
+    MockTransferHookClient::new(&env, &failing).set_failing(&true);
+    let hook = env.register_contract(None, MockTransferHook);
+    let hook_client = MockTransferHookClient::new(&env, &hook);
+    client.register_transfer_hook(&failing);
+    client.register_transfer_hook(&hook);
+    assert!(client.try_register_transfer_hook(&hook).is_err());
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // El observador que falla no bloquea la transferencia
+    client.transfer(&owner, &buyer, &1);
+    assert_eq!(client.owner_of(&1), buyer);
+
+    client.approve(&buyer, &spender, &1);
+    client.transfer_from(&spender, &buyer, &owner, &1);
+
+    let notifications = hook_client.notifications();
+    assert_eq!(notifications.len(), 2);
+    assert_eq!(notifications.get(0).unwrap(), (owner.clone(), buyer.clone(), 1));
+    assert_eq!(notifications.get(1).unwrap(), (buyer, owner.clone(), 1));
+
+    // Tras eliminarlo deja de recibir notificaciones
+    client.remove_transfer_hook(&hook);
+    client.transfer(&owner, &spender, &1);
+    assert_eq!(hook_client.notifications().len(), 2);
+}
+
+#[test]
+fn test_set_tags_and_retag() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.mint_cultural_nft(&owner, &2, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    let textile = Symbol::new(&env, "textile");
+    let ritual = Symbol::new(&env, "ritual");
+    let precolumbian = Symbol::new(&env, "pre_columbian");
+
+    client.set_tags(&museum, &1, &Vec::from_array(&env, [textile.clone(), ritual.clone()]));
+    client.set_tags(&museum, &2, &Vec::from_array(&env, [ritual.clone()]));
+    assert_eq!(client.get_tokens_by_tag(&ritual, &0, &10), Vec::from_array(&env, [1u32, 2]));
+    assert_eq!(client.get_tokens_by_tag(&textile, &0, &10), Vec::from_array(&env, [1u32]));
+
+    // Re-etiquetar quita el token de los índices anteriores y lo agrega a los nuevos
+    client.set_tags(&museum, &1, &Vec::from_array(&env, [ritual.clone(), precolumbian.clone()]));
+    assert_eq!(client.get_tags(&1), Vec::from_array(&env, [ritual.clone(), precolumbian.clone()]));
+    assert!(client.get_tokens_by_tag(&textile, &0, &10).is_empty());
+    assert_eq!(client.get_tokens_by_tag(&ritual, &0, &10), Vec::from_array(&env, [1u32, 2]));
+    assert_eq!(client.get_tokens_by_tag(&precolumbian, &0, &10), Vec::from_array(&env, [1u32]));
+
+    // Más de 8 etiquetas se rechaza
+    let mut too_many = Vec::new(&env);
+    for name in ["t1", "t2", "t3", "t4", "t5", "t6", "t7", "t8", "t9"] {
+        too_many.push_back(Symbol::new(&env, name));
+    }
+    assert!(client.try_set_tags(&museum, &1, &too_many).is_err());
+    assert_eq!(client.get_tags(&1).len(), 2);
+
+    // Solo el museo del token puede etiquetarlo
+    assert!(client.try_set_tags(&owner, &1, &Vec::new(&env)).is_err());
+}
+
+#[test]
+fn test_sensitive_metadata_redacted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.set_sensitive(&museum, &1, &true, &String::from_str(&env, "Objeto funerario, pedido de la comunidad"));
+    assert!(client.is_sensitive(&1));
+
+    // La lectura pública solo expone título y museo
+    let redacted = client.get_cultural_metadata(&1);
+    assert_eq!(redacted.title, String::from_str(&env, "Vasija ceremonial"));
+    assert_eq!(redacted.museum_address, museum);
+    assert_eq!(redacted.culture, String::from_str(&env, ""));
+    assert_eq!(redacted.significance, String::from_str(&env, ""));
+    assert_eq!(client.get_token_info(&1).1.material, String::from_str(&env, ""));
+    assert_eq!(client.get_metadata_version(&1, &1).period, String::from_str(&env, ""));
+
+    // Tampoco aparece en los índices de cultura y período que la lectura pública oculta
+    let culture = String::from_str(&env, "Diaguita");
+    let period = String::from_str(&env, "Precolombino");
+    assert!(client.get_tokens_by_culture(&culture, &0, &10).is_empty());
+    assert!(client.get_tokens_by_period(&period, &0, &10).is_empty());
+
+    // El propietario y el museo leen todo
+    let full = client.get_cultural_metadata_full(&owner, &1);
+    assert_eq!(full.culture, String::from_str(&env, "Diaguita"));
+    assert_eq!(full.significance, String::from_str(&env, "Uso ritual"));
+    assert_eq!(client.get_cultural_metadata_full(&museum, &1).material, String::from_str(&env, "Cerámica"));
+    assert!(client.try_get_cultural_metadata_full(&stranger, &1).is_err());
+
+    // Solo el museo o el admin pueden cambiar la marca
+    assert!(client.try_set_sensitive(&stranger, &1, &false, &String::from_str(&env, "x")).is_err());
+    client.set_sensitive(&museum, &1, &false, &String::from_str(&env, "Acuerdo con la comunidad"));
+    assert_eq!(client.get_cultural_metadata(&1).culture, String::from_str(&env, "Diaguita"));
+
+    // Al quitar la marca vuelve a los índices
+    assert_eq!(client.get_tokens_by_culture(&culture, &0, &10), Vec::from_array(&env, [1u32]));
+    assert_eq!(client.get_tokens_by_period(&period, &0, &10), Vec::from_array(&env, [1u32]));
+}
+
+#[test]
+fn test_burn_requires_confirmation_within_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.set_burn_window(&3600);
+
+    // Solo el propietario o el museo pueden proponer
+    let reason = String::from_str(&env, "Pieza destruida en incendio");
+    assert!(client.try_propose_burn(&stranger, &1, &reason).is_err());
+
+    // La propuesta vence si el admin no confirma a tiempo
+    client.propose_burn(&owner, &1, &reason);
+    env.ledger().with_mut(|l| l.timestamp += 3600);
+    assert!(client.try_confirm_burn(&1).is_err());
+    assert!(client.exists(&1));
+
+    // Una nueva propuesta confirmada dentro del plazo quema el token
+    client.propose_burn(&museum, &1, &reason);
+    env.ledger().with_mut(|l| l.timestamp += 1800);
+    client.confirm_burn(&1);
+
+    assert!(!client.exists(&1));
+    assert!(client.is_burned(&1));
+    assert_eq!(client.total_supply(), 0);
+    assert!(client.get_tokens_by_culture(&String::from_str(&env, "Diaguita"), &0, &10).is_empty());
+    let provenance = client.get_provenance(&1);
+    let last = provenance.get(provenance.len() - 1).unwrap();
+    assert_eq!(last.transaction_type, String::from_str(&env, "burn"));
+    assert_eq!(last.from, owner);
+
+    // Un token quemado no puede volver a mintearse
+    assert!(client.try_mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env)).is_err());
+}
+
+#[test]
+fn test_burn_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.propose_burn(&owner, &1, &String::from_str(&env, "Error de minteo"));
+    assert!(client.get_burn_proposal(&1).is_some());
+
+    assert!(client.try_cancel_burn(&stranger, &1).is_err());
+    client.cancel_burn(&museum, &1);
+    assert!(client.get_burn_proposal(&1).is_none());
+
+    // Sin propuesta el admin no puede quemar
+    assert!(client.try_confirm_burn(&1).is_err());
+    assert_eq!(client.owner_of(&1), owner);
+}
+
+#[test]
+fn test_batch_transfer_all_or_nothing() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let custodian = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let mut token_ids = Vec::new(&env);
+    for token_id in 1..=20u32 {
+        // El token 15 pertenece a otra cuenta
+        let owner = if token_id == 15 { other.clone() } else { museum.clone() };
+        client.mint_cultural_nft(&owner, &token_id, &sample_metadata(&env, &museum), &Vec::new(&env));
+        token_ids.push_back(token_id);
+    }
+
+    assert!(client.try_batch_transfer(&museum, &custodian, &token_ids).is_err());
+    for token_id in token_ids.iter() {
+        assert_ne!(client.owner_of(&token_id), custodian);
+        assert_eq!(client.get_provenance(&token_id).len(), 0);
+    }
+
+    // Sin el token ajeno el lote se transfiere completo
+    token_ids.remove(14);
+    client.batch_transfer(&museum, &custodian, &token_ids);
+    for token_id in token_ids.iter() {
+        assert_eq!(client.owner_of(&token_id), custodian);
+        let provenance = client.get_provenance(&token_id);
+        assert_eq!(provenance.len(), 1);
+        assert_eq!(provenance.get(0).unwrap().notes, String::from_str(&env, "Batch transfer"));
+    }
+    assert_eq!(client.owner_of(&15), other);
+
+    // Más de 20 tokens se rechaza
+    let mut too_many = Vec::new(&env);
+    for token_id in 1..=21u32 {
+        too_many.push_back(token_id);
+    }
+    assert!(client.try_batch_transfer(&custodian, &museum, &too_many).is_err());
+}
+
+#[test]
+fn test_transfer_cooldown_boundary() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let spender = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.set_transfer_cooldown(&museum, &1, &1000);
+    assert_eq!(client.get_transfer_unlock_time(&1), 0);
+
+    env.ledger().with_mut(|l| l.timestamp = 5000);
+    client.transfer(&owner, &buyer, &1);
+    assert_eq!(client.get_transfer_unlock_time(&1), 6000);
+
+    // Las aprobaciones se pueden otorgar, pero transfer_from queda bloqueado
+    client.approve(&buyer, &spender, &1);
+    env.ledger().with_mut(|l| l.timestamp = 5999);
+    assert!(client.try_transfer(&buyer, &owner, &1).is_err());
+    assert!(client.try_transfer_from(&spender, &buyer, &owner, &1).is_err());
+
+    // Justo en el instante de desbloqueo se permite
+    env.ledger().with_mut(|l| l.timestamp = 6000);
+    client.transfer_from(&spender, &buyer, &owner, &1);
+    assert_eq!(client.owner_of(&1), owner);
+    assert_eq!(client.get_transfer_unlock_time(&1), 7000);
+
+    // Un custodio exento no inicia espera y puede liquidar de inmediato
+    let escrow = Address::generate(&env);
+    client.set_cooldown_exempt(&escrow, &true);
+    env.ledger().with_mut(|l| l.timestamp = 7000);
+    client.transfer(&owner, &escrow, &1);
+    assert_eq!(client.get_transfer_unlock_time(&1), 7000);
+    client.transfer(&escrow, &owner, &1);
+    assert_eq!(client.get_transfer_unlock_time(&1), 8000);
+
+    // La quema no está sujeta al período de espera
+    client.propose_burn(&owner, &1, &String::from_str(&env, "Retiro"));
+    client.confirm_burn(&1);
+    assert!(client.is_burned(&1));
+}
+
+#[test]
+fn test_provenance_overflow_to_archive() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.mint_cultural_nft(&alice, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.set_max_provenance_length(&3);
+
+    // Cinco transferencias con un máximo de tres registros recientes
+    for i in 0..5u64 {
+        env.ledger().with_mut(|l| l.timestamp = 100 + i);
+        if i % 2 == 0 {
+            client.transfer(&alice, &bob, &1);
+        } else {
+            client.transfer(&bob, &alice, &1);
+        }
+    }
+
+    let hot = client.get_provenance(&1);
+    assert_eq!(hot.len(), 3);
+    assert_eq!(hot.get(0).unwrap().date, 102);
+    assert_eq!(hot.get(2).unwrap().date, 104);
+
+    // Los dos registros más antiguos pasaron al archivo, en orden
+    assert_eq!(client.get_archived_provenance_count(&1), 2);
+    let archived = client.get_archived_provenance(&1, &0, &10);
+    assert_eq!(archived.len(), 2);
+    assert_eq!(archived.get(0).unwrap().date, 100);
+    assert_eq!(archived.get(1).unwrap().date, 101);
+    assert_eq!(client.get_archived_provenance(&1, &1, &10).get(0).unwrap().date, 101);
+
+    // Las atestaciones usan la posición en el historial completo
+    let researcher = Address::generate(&env);
+    client.register_researcher(&researcher);
+    client.attest_provenance(&researcher, &1, &2, &String::from_str(&env, "ipfs://factura"));
+    assert_eq!(client.get_attested_provenance(&1).get(0).unwrap().attestation_count, 1);
+    assert!(client.try_attest_provenance(&researcher, &1, &5, &String::from_str(&env, "ipfs://x")).is_err());
+}
+
+#[test]
+fn test_provenance_filtered_by_type() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let conservation = |date: u64, notes: &str| PROVENANCERecord {
+        date,
+        from: museum.clone(),
+        to: museum.clone(),
+        transaction_type: String::from_str(&env, "conservation"),
+        notes: String::from_str(&env, notes),
+        declared_value: None,
+    };
+    let mut provenance = Vec::new(&env);
+    provenance.push_back(conservation(1, "Limpieza"));
+    provenance.push_back(conservation(2, "Consolidación"));
+    provenance.push_back(conservation(3, "Restauración"));
+    client.mint_cultural_nft(&alice, &1, &sample_metadata(&env, &museum), &provenance);
+
+    client.set_max_provenance_length(&2);
+    client.transfer(&alice, &bob, &1);
+    client.transfer(&bob, &alice, &1);
+
+    // Los registros filtrados incluyen los archivados
+    let conservation_records = client.get_provenance_filtered(&1, &String::from_str(&env, "conservation"), &0, &10);
+    assert_eq!(conservation_records.len(), 3);
+    assert_eq!(conservation_records.get(2).unwrap().notes, String::from_str(&env, "Restauración"));
+
+    let page = client.get_provenance_filtered(&1, &String::from_str(&env, "conservation"), &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().notes, String::from_str(&env, "Consolidación"));
+
+    let transfers = client.get_provenance_filtered(&1, &String::from_str(&env, "transfer"), &0, &10);
+    assert_eq!(transfers.len(), 2);
+    assert_eq!(transfers.get(0).unwrap().to, bob);
+
+    let counts = client.get_provenance_type_counts(&1);
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts.get(0).unwrap(), (String::from_str(&env, "conservation"), 3));
+    assert_eq!(counts.get(1).unwrap(), (String::from_str(&env, "transfer"), 2));
+}
+
+#[test]
+fn test_previous_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.mint_cultural_nft(&alice, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert_eq!(client.previous_owner(&1), None);
+
+    client.transfer(&alice, &bob, &1);
+    assert_eq!(client.previous_owner(&1), Some(alice));
+
+    client.transfer(&bob, &carol, &1);
+    assert_eq!(client.previous_owner(&1), Some(bob));
+}
+
+#[test]
+fn test_initialize_and_reinit_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, _museum) = setup(&env);
+    assert_eq!(client.name(), String::from_str(&env, "PermaMuseum"));
+    assert_eq!(client.symbol(), String::from_str(&env, "PMUS"));
+    assert_eq!(client.total_supply(), 0);
+
+    let result = client.try_initialize(
+        &admin,
+        &String::from_str(&env, "Otro"),
+        &String::from_str(&env, "OTRO"),
+        &Address::generate(&env),
+        &None,
+    );
+    assert!(result.is_err());
+    assert_eq!(client.name(), String::from_str(&env, "PermaMuseum"));
+}
+
+#[test]
+fn test_mint_requires_admin_auth() {
+    let env = Env::default();
+
+    let (client, admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let metadata = sample_metadata(&env, &museum);
+
+    // Sin autorización del admin el minteo falla
+    assert!(client.try_mint_cultural_nft(&owner, &1, &metadata, &Vec::new(&env)).is_err());
+    assert!(!client.exists(&1));
+
+    // Solo la autorización del admin permite mintear
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "mint_cultural_nft",
+                args: (owner.clone(), 1u32, metadata.clone(), Vec::<PROVENANCERecord>::new(&env)).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .mint_cultural_nft(&owner, &1, &metadata, &Vec::new(&env));
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "mint_cultural_nft"),
+                    (owner.clone(), 1u32, metadata.clone(), Vec::<PROVENANCERecord>::new(&env)).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert!(client.exists(&1));
+    assert_eq!(client.owner_of(&1), owner);
+}
+
+#[test]
+fn test_mint_duplicate_token_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    assert!(!client.exists(&7));
+    assert!(client.try_owner_of(&7).is_err());
+
+    client.mint_cultural_nft(&owner, &7, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert!(client.exists(&7));
+    assert_eq!(client.owner_of(&7), owner);
+    assert_eq!(client.total_supply(), 1);
+
+    assert!(client.try_mint_cultural_nft(&other, &7, &sample_metadata(&env, &museum), &Vec::new(&env)).is_err());
+    assert_eq!(client.owner_of(&7), owner);
+    assert_eq!(client.total_supply(), 1);
+}
+
+#[test]
+fn test_transfer_by_owner_only_and_provenance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.mint_cultural_nft(&alice, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // Quien no es propietario no puede transferir
+    assert!(client.try_transfer(&bob, &carol, &1).is_err());
+    assert_eq!(client.owner_of(&1), alice);
+
+    env.ledger().with_mut(|l| l.timestamp = 10);
+    client.transfer(&alice, &bob, &1);
+    env.ledger().with_mut(|l| l.timestamp = 20);
+    client.transfer(&bob, &carol, &1);
+    assert_eq!(client.owner_of(&1), carol);
+
+    // Cada transferencia agrega un registro de procedencia
+    let provenance = client.get_provenance(&1);
+    assert_eq!(provenance.len(), 2);
+    let first = provenance.get(0).unwrap();
+    assert_eq!((first.from, first.to, first.date), (alice, bob.clone(), 10));
+    let second = provenance.get(1).unwrap();
+    assert_eq!((second.from, second.to, second.date), (bob, carol, 20));
+    assert_eq!(second.transaction_type, String::from_str(&env, "transfer"));
+}
+
+#[test]
+fn test_event_hooks_receive_calls() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let mint = String::from_str(&env, "mint");
+    let transfer = String::from_str(&env, "transfer");
+    let burn = String::from_str(&env, "burn");
+
+    let all_events = env.register_contract(None, MockEventHook);
+    let transfers_only = env.register_contract(None, MockEventHook);
+    for event_type in [&mint, &transfer, &burn] {
+        client.register_hook(&all_events, event_type);
+    }
+    client.register_hook(&transfers_only, &transfer);
+    assert!(client.try_register_hook(&all_events, &String::from_str(&env, "list")).is_err());
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.transfer(&owner, &buyer, &1);
+    client.propose_burn(&buyer, &1, &String::from_str(&env, "Retiro"));
+    client.confirm_burn(&1);
+
+    let events = MockEventHookClient::new(&env, &all_events).events();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events.get(0).unwrap(), (mint, 1, owner));
+    assert_eq!(events.get(1).unwrap(), (transfer.clone(), 1, buyer.clone()));
+    assert_eq!(events.get(2).unwrap(), (burn, 1, buyer.clone()));
+
+    let transfer_events = MockEventHookClient::new(&env, &transfers_only).events();
+    assert_eq!(transfer_events.len(), 1);
+    assert_eq!(transfer_events.get(0).unwrap(), (transfer.clone(), 1, buyer));
+
+    // Tras cancelar la suscripción no recibe más eventos
+    client.unregister_hook(&transfers_only, &transfer);
+    assert_eq!(client.get_hooks(&transfer).len(), 1);
+
+    // Máximo de 5 suscriptores por evento
+    for _ in 0..4 {
+        client.register_hook(&env.register_contract(None, MockEventHook), &transfer);
+    }
+    assert!(client.try_register_hook(&transfers_only, &transfer).is_err());
+}
+
+#[test]
+fn test_provenance_digest_tracks_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    let before = client.get_provenance_digest(&1);
+    assert!(client.verify_provenance_digest(&1, &before));
+
+    // Una transferencia cambia el resumen y el anterior deja de verificar
+    client.transfer(&owner, &buyer, &1);
+    let after = client.get_provenance_digest(&1);
+    assert_ne!(before, after);
+    assert!(client.verify_provenance_digest(&1, &after));
+    assert!(!client.verify_provenance_digest(&1, &before));
+
+    // El resumen es determinista
+    assert_eq!(client.get_provenance_digest(&1), after);
+}
+
+#[test]
+fn test_owner_at_follows_owner_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.ledger().with_mut(|l| l.timestamp = 100);
+    client.mint_cultural_nft(&alice, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    env.ledger().with_mut(|l| l.timestamp = 200);
+    client.transfer(&alice, &bob, &1);
+    env.ledger().with_mut(|l| l.timestamp = 300);
+    client.propose_burn(&bob, &1, &String::from_str(&env, "Retiro"));
+    client.confirm_burn(&1);
+
+    // Antes del minteo no hay propietario
+    assert_eq!(client.get_owner_at(&1, &50), None);
+
+    // Entre transferencias
+    assert_eq!(client.get_owner_at(&1, &100), Some(alice.clone()));
+    assert_eq!(client.get_owner_at(&1, &199), Some(alice.clone()));
+    assert_eq!(client.get_owner_at(&1, &250), Some(bob.clone()));
+
+    // Después de la quema
+    assert_eq!(client.get_owner_at(&1, &300), None);
+    assert_eq!(client.get_owner_at(&1, &1_000), None);
+
+    let history = client.get_owner_history(&1, &0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap(), (Some(alice), 100));
+    assert_eq!(history.get(2).unwrap(), (None, 300));
+    assert_eq!(client.get_owner_history(&1, &1, &1).get(0).unwrap(), (Some(bob), 200));
+}
+
+#[test]
+fn test_is_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, museum) = setup(&env);
+
+    assert!(client.is_admin(&admin));
+    assert!(!client.is_admin(&museum));
+    assert!(!client.is_admin(&Address::generate(&env)));
+}
+
+#[test]
+fn test_soulbound_token_blocks_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let holder = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    let mut metadata = sample_metadata(&env, &museum);
+    metadata.is_soulbound = true;
+    client.mint_cultural_nft(&holder, &1, &metadata, &Vec::new(&env));
+
+    // Transferencias y aprobaciones bloqueadas
+    assert!(client.try_transfer(&holder, &other, &1).is_err());
+    assert!(client.try_approve(&holder, &other, &1).is_err());
+    client.approve_for_all(&holder, &other);
+    assert!(client.try_transfer_from(&other, &holder, &other, &1).is_err());
+    assert_eq!(client.owner_of(&1), holder);
+
+    // El museo no puede quitar el carácter intransferible
+    client.update_cultural_metadata(&museum, &1, &sample_metadata(&env, &museum));
+    assert!(client.get_cultural_metadata(&1).is_soulbound);
+
+    // El admin puede revocarlo
+    client.revoke_soulbound(&1, &String::from_str(&env, "Certificado revocado"));
+    assert!(client.is_burned(&1));
+    assert!(!client.exists(&1));
+}
+
+#[test]
+fn test_link_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let other_museum = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.mint_cultural_nft(&owner, &2, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.mint_cultural_nft(&owner, &3, &sample_metadata(&env, &other_museum), &Vec::new(&env));
+
+    let fragment = Symbol::new(&env, "fragment");
+    client.link_tokens(&museum, &1, &2, &fragment);
+
+    // Visible desde ambos tokens
+    assert_eq!(client.get_related_tokens(&1), Vec::from_array(&env, [(2u32, fragment.clone())]));
+    assert_eq!(client.get_related_tokens(&2), Vec::from_array(&env, [(1u32, fragment.clone())]));
+
+    // Duplicados (en cualquier sentido) y autorrelaciones rechazados
+    assert!(client.try_link_tokens(&museum, &1, &2, &fragment).is_err());
+    assert!(client.try_link_tokens(&museum, &2, &1, &Symbol::new(&env, "pair")).is_err());
+    assert!(client.try_link_tokens(&museum, &1, &1, &fragment).is_err());
+
+    // Un museo no puede relacionar tokens de otro museo
+    assert!(client.try_link_tokens(&museum, &1, &3, &fragment).is_err());
+
+    client.unlink_tokens(&museum, &2, &1);
+    assert_eq!(client.get_related_tokens(&1).len(), 0);
+    assert_eq!(client.get_related_tokens(&2).len(), 0);
+}
+
+#[test]
+fn test_forwarded_transfer_with_signature() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let visitor = Address::generate(&env);
+    let forwarder = Address::generate(&env);
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    let sign = |nonce: u64| {
+        let message = (client.address.clone(), owner.clone(), visitor.clone(), 1u32, nonce).to_xdr(&env);
+        let bytes: std::vec::Vec<u8> = message.iter().collect();
+        BytesN::from_array(&env, &signing_key.sign(&bytes).to_bytes())
+    };
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.register_signing_key(&owner, &public_key);
+
+    // Sin forwarder configurado se rechaza
+    assert!(client.try_forwarded_transfer(&forwarder, &owner, &visitor, &1, &0, &sign(0)).is_err());
+    client.set_trusted_forwarder(&forwarder);
+
+    // Firma para otro nonce: inválida
+    assert!(client.try_forwarded_transfer(&forwarder, &owner, &visitor, &1, &0, &sign(1)).is_err());
+
+    // Solo el forwarder autoriza la llamada; el propietario firma fuera de la cadena
+    client.forwarded_transfer(&forwarder, &owner, &visitor, &1, &0, &sign(0));
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, forwarder);
+    assert_eq!(client.owner_of(&1), visitor);
+    assert_eq!(client.get_meta_nonce(&owner), 1);
+
+    // La misma firma no puede reutilizarse
+    assert!(client.try_forwarded_transfer(&forwarder, &owner, &visitor, &1, &0, &sign(0)).is_err());
+}
+
+#[test]
+fn test_beneficial_owner_while_escrowed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let seller = Address::generate(&env);
+    let escrow = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.mint_cultural_nft(&seller, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert_eq!(client.beneficial_owner(&1), seller);
+
+    // El custodio recibe el token y registra al vendedor como beneficiario
+    client.transfer(&seller, &escrow, &1);
+    assert!(client.try_set_beneficial_owner(&seller, &1, &Some(seller.clone())).is_err());
+    client.set_beneficial_owner(&escrow, &1, &Some(seller.clone()));
+    assert_eq!(client.owner_of(&1), escrow);
+    assert_eq!(client.beneficial_owner(&1), seller);
+
+    // Al salir de custodia la anotación desaparece
+    client.transfer(&escrow, &buyer, &1);
+    assert_eq!(client.beneficial_owner(&1), buyer);
+}
+
+#[test]
+fn test_curators_act_for_their_museum() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let registry = MockRegistryClient::new(&env, &client.get_museum_registry());
+    let editor = Address::generate(&env);
+    let registrar = Address::generate(&env);
+    let owner = Address::generate(&env);
+    registry.set_curator(&museum, &editor, &CuratorRole::MetadataEditor);
+    registry.set_curator(&museum, &registrar, &CuratorRole::Registrar);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // El editor puede editar metadatos pero no proponer minteos
+    let mut metadata = sample_metadata(&env, &museum);
+    metadata.condition = String::from_str(&env, "Restaurada");
+    client.update_cultural_metadata(&editor, &1, &metadata);
+    assert_eq!(client.get_cultural_metadata(&1).condition, String::from_str(&env, "Restaurada"));
+    assert!(client.try_request_mint(&editor, &sample_metadata(&env, &museum), &Vec::new(&env)).is_err());
+
+    // El registrador puede proponer minteos pero no editar metadatos
+    let request_id = client.request_mint(&registrar, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert_eq!(client.get_mint_request(&request_id).museum, museum);
+    assert!(client.try_add_condition_report(&registrar, &1, &String::from_str(&env, "Mala"), &String::from_str(&env, "")).is_err());
+}
+
+#[test]
+fn test_localized_metadata_with_fallback() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let metadata = sample_metadata(&env, &museum);
+    client.mint_cultural_nft(&owner, &1, &metadata, &Vec::new(&env));
+
+    // Códigos ISO 639-1 reducidos a u32 por el cliente
+    let en = u32::from_be_bytes([0, 0, b'e', b'n']);
+    let qu = u32::from_be_bytes([0, 0, b'q', b'u']);
+
+    // Sin traducción se usan el título y la significancia por defecto
+    assert_eq!(client.get_localized_metadata(&1, &en), None);
+    assert_eq!(client.get_display_metadata(&1, &en), (metadata.title.clone(), metadata.significance.clone()));
+
+    let en_entry = (String::from_str(&env, "Paracas Mantle"), String::from_str(&env, "Funerary textile"));
+    let qu_entry = (String::from_str(&env, "Paracas p'acha"), String::from_str(&env, "Aya p'acha"));
+    client.set_localized_metadata(&museum, &1, &en, &en_entry.0, &en_entry.1);
+    client.set_localized_metadata(&museum, &1, &qu, &qu_entry.0, &qu_entry.1);
+    assert_eq!(client.get_localized_metadata(&1, &en), Some(en_entry.clone()));
+    assert_eq!(client.get_display_metadata(&1, &qu), qu_entry);
+    assert_eq!(client.get_metadata_languages(&1), Vec::from_array(&env, [en, qu]));
+
+    // Solo el museo (o sus curadores) puede traducir
+    assert!(client.try_set_localized_metadata(&owner, &1, &en, &en_entry.0, &en_entry.1).is_err());
+
+    // Borrar una traducción vuelve a los metadatos por defecto
+    let empty = String::from_str(&env, "");
+    client.set_localized_metadata(&museum, &1, &en, &empty, &empty);
+    assert_eq!(client.get_display_metadata(&1, &en), (metadata.title, metadata.significance));
+    assert_eq!(client.get_metadata_languages(&1), Vec::from_array(&env, [qu]));
+}
+
+#[test]
+fn test_set_museum_registry_switches_verification() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let filed_before = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // El nuevo registro aún no verificó al museo: no acepta solicitudes ni aprueba las previas
+    let new_registry = env.register_contract(None, MockRegistry);
+    client.set_museum_registry(&new_registry);
+    assert_eq!(client.get_museum_registry(), new_registry);
+    let result = client.try_request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    assert!(result.is_err());
+    assert!(client.try_approve_mint_request(&filed_before).is_err());
+
+    // Cuando el nuevo registro lo verifica, se solicita, aprueba y mintea con normalidad
+    MockRegistryClient::new(&env, &new_registry).set_verified(&museum, &true);
+    let filed_after = client.request_mint(&museum, &sample_metadata(&env, &museum), &Vec::new(&env));
+    let token_id = client.approve_mint_request(&filed_before);
+    assert_eq!(client.owner_of(&token_id), museum);
+    let token_id = client.approve_mint_request(&filed_after);
+    assert_eq!(client.owner_of(&token_id), museum);
+    assert_eq!(client.total_supply(), 2);
+}
+
+
+#[test]
+fn test_merge_fragments_into_one_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let donation = |notes: &str| {
+        Vec::from_array(&env, [PROVENANCERecord {
+            date: 0,
+            from: museum.clone(),
+            to: owner.clone(),
+            transaction_type: String::from_str(&env, "donation"),
+            notes: String::from_str(&env, notes),
+            declared_value: None,
+        }])
+    };
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &donation("Fragmento superior"));
+    client.mint_cultural_nft(&owner, &2, &sample_metadata(&env, &museum), &donation("Fragmento inferior"));
+    client.mint_cultural_nft(&buyer, &3, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.link_tokens(&admin, &1, &3, &symbol_short!("pair"));
+
+    // Solo se fusionan tokens propios
+    let mut unified = sample_metadata(&env, &museum);
+    unified.title = String::from_str(&env, "Estela reunificada");
+    assert!(client.try_propose_merge(&owner, &1, &3, &unified).is_err());
+    assert!(client.try_propose_merge(&owner, &1, &1, &unified).is_err());
+
+    // Los tokens quedan bloqueados mientras la fusión está pendiente
+    let merge_id = client.propose_merge(&owner, &1, &2, &unified);
+    assert_eq!(client.get_merge_lock(&1), Some(merge_id));
+    assert!(client.try_transfer(&owner, &buyer, &1).is_err());
+    assert!(client.try_propose_merge(&owner, &2, &1, &unified).is_err());
+
+    // La firma del propietario no basta para ejecutarla
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &owner,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "execute_merge",
+                args: (merge_id,).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_execute_merge(&merge_id);
+    assert!(result.is_err());
+
+    let new_token_id = client.execute_merge(&merge_id);
+    assert_eq!(new_token_id, 4);
+    assert_eq!(client.owner_of(&new_token_id), owner);
+    assert_eq!(client.get_cultural_metadata(&new_token_id).title, unified.title);
+    assert_eq!(client.get_merged_from(&new_token_id), Vec::from_array(&env, [1u32, 2]));
+    assert_eq!(client.total_supply(), 2);
+
+    // La procedencia combina la de ambos fragmentos y registra la fusión
+    let provenance = client.get_provenance(&new_token_id);
+    assert_eq!(provenance.len(), 3);
+    assert_eq!(provenance.get(0).unwrap().notes, String::from_str(&env, "Fragmento superior"));
+    assert_eq!(provenance.get(1).unwrap().notes, String::from_str(&env, "Fragmento inferior"));
+    assert_eq!(provenance.get(2).unwrap().transaction_type, String::from_str(&env, "merge"));
+
+    // Los tokens de origen dejan de existir y no se reutilizan sus IDs
+    for token_id in [1u32, 2] {
+        assert!(!client.exists(&token_id));
+        assert!(client.is_burned(&token_id));
+        assert!(client.try_get_cultural_metadata(&token_id).is_err());
+        assert!(client.try_get_provenance(&token_id).is_err());
+        assert_eq!(client.get_merge_lock(&token_id), None);
+    }
+    assert_eq!(client.get_related_tokens(&3).len(), 0);
+    assert!(client.get_merge_proposal(&merge_id).is_none());
+    assert!(client.try_mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env)).is_err());
+}
+
+#[test]
+fn test_cancel_merge_unlocks_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.mint_cultural_nft(&owner, &2, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    let merge_id = client.propose_merge(&owner, &1, &2, &sample_metadata(&env, &museum));
+    assert!(client.try_cancel_merge(&buyer, &merge_id).is_err());
+    client.cancel_merge(&owner, &merge_id);
+
+    assert_eq!(client.get_merge_lock(&1), None);
+    client.transfer(&owner, &buyer, &1);
+    assert_eq!(client.owner_of(&1), buyer);
+}
+
+#[test]
+fn test_transfer_with_memo_round_trips_into_provenance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    client.mint_cultural_nft(&alice, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    let memo = String::from_str(&env, "Venta privada en Lima, factura 0042");
+    client.transfer_with_memo(&alice, &bob, &1, &memo, &Some(12_500));
+    client.approve(&bob, &agent, &1);
+    let estate_memo = String::from_str(&env, "Sucesión testamentaria");
+    client.transfer_from_with_memo(&agent, &bob, &carol, &1, &estate_memo, &None);
+
+    let provenance = client.get_provenance(&1);
+    assert_eq!(provenance.len(), 2);
+    let sale = provenance.get(0).unwrap();
+    assert_eq!(sale.notes, memo);
+    assert_eq!(sale.declared_value, Some(12_500));
+    assert_eq!(sale.transaction_type, String::from_str(&env, "transfer"));
+    let estate = provenance.get(1).unwrap();
+    assert_eq!(estate.notes, estate_memo);
+    assert_eq!(estate.declared_value, None);
+    assert_eq!(estate.transaction_type, String::from_str(&env, "transfer_from"));
+}
+
+#[test]
+fn test_transfer_with_memo_rejects_long_memo_and_negative_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.mint_cultural_nft(&alice, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // 256 bytes es el máximo; 257 se rechaza
+    let longest = String::from_str(&env, &"a".repeat(256));
+    let too_long = String::from_str(&env, &"a".repeat(257));
+    assert!(client.try_transfer_with_memo(&alice, &bob, &1, &too_long, &None).is_err());
+    assert!(client.try_transfer_with_memo(&alice, &bob, &1, &String::from_str(&env, ""), &Some(-1)).is_err());
+    assert_eq!(client.owner_of(&1), alice);
+
+    client.transfer_with_memo(&alice, &bob, &1, &longest, &Some(0));
+    assert_eq!(client.get_provenance(&1).get(0).unwrap().notes, longest);
+}
+
+#[test]
+fn test_tokens_by_culture_and_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+
+    let diaguita = sample_metadata(&env, &museum);
+    let mut moche = sample_metadata(&env, &museum);
+    moche.culture = String::from_str(&env, "Moche");
+    moche.period = String::from_str(&env, "Intermedio Temprano");
+    for token_id in 1..=3u32 {
+        client.mint_cultural_nft(&owner, &token_id, &diaguita, &Vec::new(&env));
+    }
+    for token_id in 4..=5u32 {
+        client.mint_cultural_nft(&owner, &token_id, &moche, &Vec::new(&env));
+    }
+
+    let expected_diaguita = Vec::from_array(&env, [1u32, 2, 3]);
+    let expected_moche = Vec::from_array(&env, [4u32, 5]);
+    assert_eq!(client.get_tokens_by_culture(&diaguita.culture, &0, &10), expected_diaguita);
+    assert_eq!(client.get_tokens_by_culture(&moche.culture, &0, &10), expected_moche);
+    assert_eq!(client.get_tokens_by_period(&diaguita.period, &0, &10), expected_diaguita);
+    assert_eq!(client.get_tokens_by_period(&moche.period, &0, &10), expected_moche);
+    assert_eq!(client.get_tokens_by_culture(&String::from_str(&env, "Nazca"), &0, &10).len(), 0);
+
+    // Paginación
+    assert_eq!(client.get_tokens_by_culture(&diaguita.culture, &1, &1), Vec::from_array(&env, [2u32]));
+    assert_eq!(client.get_tokens_by_culture(&diaguita.culture, &2, &10), Vec::from_array(&env, [3u32]));
+    assert_eq!(client.get_tokens_by_culture(&diaguita.culture, &5, &10).len(), 0);
+
+    // Al cambiar la cultura del token, los índices se actualizan
+    client.update_cultural_metadata(&museum, &3, &moche);
+    assert_eq!(client.get_tokens_by_culture(&diaguita.culture, &0, &10), Vec::from_array(&env, [1u32, 2]));
+    assert_eq!(client.get_tokens_by_culture(&moche.culture, &0, &10), Vec::from_array(&env, [4u32, 5, 3]));
+    assert_eq!(client.get_tokens_by_period(&moche.period, &0, &10).len(), 3);
+}
+
+#[test]
+fn test_tokens_near_scale_longitude_and_wrap_antimeridian() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let place = |token_id: u32, lat: i64, lon: i64| {
+        let mut metadata = sample_metadata(&env, &museum);
+        metadata.location = ArtifactLocation::Coordinates(lat, lon);
+        client.mint_cultural_nft(&owner, &token_id, &metadata, &Vec::new(&env));
+    };
+
+    // Lima, Callao (~9 km) y Cusco (~570 km)
+    place(1, -12_046_000, -77_043_000);
+    place(2, -12_050_000, -77_120_000);
+    place(3, -13_530_000, -71_970_000);
+    assert_eq!(client.get_tokens_near(&-12_046_000, &-77_043_000, &50), Vec::from_array(&env, [1u32, 2]));
+    assert_eq!(client.get_artifact_location(&3), Some((-13_530_000, -71_970_000)));
+
+    // En Tromsø (69.65° N) un grado de longitud mide ~39 km: 1.54° al este (~59 km) entra
+    // en un radio de 80 km y 3.04° (~117 km) no
+    place(4, 69_650_000, 18_960_000);
+    place(5, 69_650_000, 20_500_000);
+    place(6, 69_650_000, 22_000_000);
+    assert_eq!(client.get_tokens_near(&69_650_000, &18_960_000, &80), Vec::from_array(&env, [4u32, 5]));
+
+    // Fiyi: la búsqueda cruza el antimeridiano (0.2° ≈ 21 km; 2° ≈ 211 km queda fuera)
+    place(7, -17_700_000, 179_900_000);
+    place(8, -17_700_000, 178_000_000);
+    assert_eq!(client.get_tokens_near(&-17_700_000, &-179_900_000, &50), Vec::from_array(&env, [7u32]));
+
+    // Un objeto sensible desaparece de la búsqueda y vuelve al desmarcarlo
+    let reason = String::from_str(&env, "Restos humanos");
+    client.set_sensitive(&admin, &2, &true, &reason);
+    assert_eq!(client.get_tokens_near(&-12_046_000, &-77_043_000, &50), Vec::from_array(&env, [1u32]));
+    assert_eq!(client.get_artifact_location(&2), None);
+    client.set_sensitive(&admin, &2, &false, &reason);
+    assert_eq!(client.get_tokens_near(&-12_046_000, &-77_043_000, &50), Vec::from_array(&env, [1u32, 2]));
+
+    // Al mover un artefacto cambia de celda
+    client.update_artifact_location(&museum, &3, &-12_100_000, &-77_000_000);
+    assert_eq!(client.get_tokens_near(&-12_046_000, &-77_043_000, &50).len(), 3);
+
+    // Radio y área de búsqueda acotados
+    assert!(client.try_get_tokens_near(&0, &0, &201).is_err());
+    assert!(client.try_get_tokens_near(&0, &0, &200).is_ok());
+    assert!(client.try_get_tokens_near(&80_000_000, &0, &200).is_err());
+}
+
+#[test]
+fn test_approval_expiry_boundary() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.mint_cultural_nft(&owner, &2, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // Una expiración en el pasado o en el instante actual no es válida
+    assert!(client.try_approve_until(&owner, &spender, &1, &1_000).is_err());
+    client.approve_until(&owner, &spender, &1, &2_000);
+    client.approve_until(&owner, &spender, &2, &2_000);
+
+    // Un segundo antes de expirar sigue vigente
+    env.ledger().with_mut(|l| l.timestamp = 1_999);
+    assert_eq!(client.get_approved(&1), Some(spender.clone()));
+    client.transfer_from(&spender, &owner, &buyer, &1);
+    assert_eq!(client.owner_of(&1), buyer);
+
+    // En el segundo de expiración ya no existe
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    assert_eq!(client.get_approved(&2), None);
+    assert!(client.try_transfer_from(&spender, &owner, &buyer, &2).is_err());
+    assert_eq!(client.owner_of(&2), owner);
+
+    // approve sin expiración sigue vigente indefinidamente
+    client.approve(&owner, &spender, &2);
+    env.ledger().with_mut(|l| l.timestamp = 1_000_000_000);
+    assert_eq!(client.get_approved(&2), Some(spender));
+}
+
+#[test]
+fn test_operator_approval_expires_between_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.mint_cultural_nft(&owner, &2, &sample_metadata(&env, &museum), &Vec::new(&env));
+
+    // El operador se aprueba al listar y la venta del segundo token llega tarde
+    client.approve_for_all_until(&owner, &operator, &1_500);
+    assert!(client.is_approved_for_all(&owner, &operator));
+    client.transfer_from(&operator, &owner, &buyer, &1);
+    assert_eq!(client.owner_of(&1), buyer);
+
+    env.ledger().with_mut(|l| l.timestamp = 1_500);
+    assert!(!client.is_approved_for_all(&owner, &operator));
+    assert!(client.try_transfer_from(&operator, &owner, &buyer, &2).is_err());
+    assert_eq!(client.owner_of(&2), owner);
+
+    // Sin expiración el operador sigue aprobado
+    client.approve_for_all(&owner, &operator);
+    env.ledger().with_mut(|l| l.timestamp = 1_000_000_000);
+    client.transfer_from(&operator, &owner, &buyer, &2);
+    assert_eq!(client.owner_of(&2), buyer);
+}
+
+#[test]
+fn test_safe_transfer_receivers() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let operator = Address::generate(&env);
+
+    let accepting_id = env.register_contract(None, MockNftReceiver);
+    let accepting = MockNftReceiverClient::new(&env, &accepting_id);
+    accepting.set_accepting(&true);
+    let rejecting_id = env.register_contract(None, MockNftReceiver);
+    let rejecting = MockNftReceiverClient::new(&env, &rejecting_id);
+    rejecting.set_accepting(&false);
+
+    for token_id in 1..=3 {
+        client.mint_cultural_nft(&owner, &token_id, &sample_metadata(&env, &museum), &Vec::new(&env));
+    }
+
+    // El propietario opera su propia transferencia
+    client.safe_transfer(&owner, &accepting_id, &1);
+    assert_eq!(client.owner_of(&1), accepting_id);
+    assert_eq!(accepting.received().get(0).unwrap(), (owner.clone(), owner.clone(), 1));
+
+    // Con safe_transfer_from el operador es el spender aprobado, no el propietario
+    client.approve(&owner, &operator, &2);
+    client.safe_transfer_from(&operator, &owner, &accepting_id, &2);
+    assert_eq!(client.owner_of(&2), accepting_id);
+    assert_eq!(accepting.received().get(1).unwrap(), (operator.clone(), owner.clone(), 2));
+
+    // Si el receptor rechaza, la transferencia se revierte por completo
+    let provenance_before = client.get_provenance(&3).len();
+    assert!(client.try_safe_transfer(&owner, &rejecting_id, &3).is_err());
+    assert_eq!(client.owner_of(&3), owner);
+    assert_eq!(client.get_provenance(&3).len(), provenance_before);
+    assert!(rejecting.received().is_empty());
+}
+
+#[test]
+fn test_attest_provenance_rules() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, _admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let researcher = Address::generate(&env);
+    let evidence = String::from_str(&env, "ipfs://archivo-notarial");
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.transfer(&owner, &collector, &1);
+    let records = client.get_provenance(&1).len();
+
+    // Solo los investigadores registrados pueden atestiguar
+    assert!(client.try_attest_provenance(&researcher, &1, &0, &evidence).is_err());
+    client.register_researcher(&researcher);
+
+    client.attest_provenance(&researcher, &1, &0, &evidence);
+    let attestations = client.get_provenance_attestations(&1, &0);
+    assert_eq!(attestations.len(), 1);
+    assert_eq!(attestations.get(0).unwrap().attestor, researcher);
+
+    // Cada investigador atestigua un registro una sola vez
+    assert!(client.try_attest_provenance(&researcher, &1, &0, &evidence).is_err());
+
+    // El índice debe apuntar a un registro existente
+    assert!(client.try_attest_provenance(&researcher, &1, &records, &evidence).is_err());
+
+    // Otro investigador sí puede atestiguar el mismo registro
+    let second = Address::generate(&env);
+    client.register_researcher(&second);
+    client.attest_provenance(&second, &1, &0, &evidence);
+    assert_eq!(client.get_attested_provenance(&1).get(0).unwrap().attestation_count, 2);
+}
+
+#[test]
+fn test_disputed_token_transfer_needs_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, admin, museum) = setup(&env);
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    client.mint_cultural_nft(&owner, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    client.flag_dispute(&1, &String::from_str(&env, "Comunidad de origen"), &String::from_str(&env, "EXP-2024-17"));
+    assert!(client.is_disputed(&1));
+    assert_eq!(client.get_dispute(&1).unwrap().case_reference, String::from_str(&env, "EXP-2024-17"));
+    assert!(client.try_flag_dispute(&1, &String::from_str(&env, "Otro"), &String::from_str(&env, "EXP-2")).is_err());
+
+    let invoke = MockAuthInvoke {
+        contract: &client.address,
+        fn_name: "transfer",
+        args: (owner.clone(), buyer.clone(), 1u32).into_val(&env),
+        sub_invokes: &[],
+    };
+
+    // Con la disputa activa, la firma del propietario no alcanza
+    assert!(client
+        .mock_auths(&[MockAuth { address: &owner, invoke: &invoke }])
+        .try_transfer(&owner, &buyer, &1)
+        .is_err());
+    assert_eq!(client.owner_of(&1), owner);
+
+    // Con la co-autorización del admin la transferencia procede
+    client
+        .mock_auths(&[MockAuth { address: &owner, invoke: &invoke }, MockAuth { address: &admin, invoke: &invoke }])
+        .transfer(&owner, &buyer, &1);
+    assert_eq!(client.owner_of(&1), buyer);
+
+    // Tras levantar la disputa basta la firma del propietario
+    env.mock_all_auths();
+    client.clear_dispute(&1, &String::from_str(&env, "Acuerdo de restitución"));
+    assert!(client.get_dispute(&1).is_none());
+    assert!(client.try_clear_dispute(&1, &String::from_str(&env, "Repetido")).is_err());
+    let buyer_auth = MockAuth {
+        address: &buyer,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "transfer",
+            args: (buyer.clone(), owner.clone(), 1u32).into_val(&env),
+            sub_invokes: &[],
+        },
+    };
+    client.mock_auths(&[buyer_auth]).transfer(&buyer, &owner, &1);
+    assert_eq!(client.owner_of(&1), owner);
+
+    // Marcar y levantar la disputa quedan en la procedencia
+    let mut flagged = false;
+    let mut cleared = false;
+    for record in client.get_provenance(&1).iter() {
+        flagged |= record.transaction_type == String::from_str(&env, "dispute_flagged");
+        cleared |= record.transaction_type == String::from_str(&env, "dispute_cleared");
+    }
+    assert!(flagged && cleared);
+}
@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+use soroban_sdk::{contract, contractclient, contractimpl, contracttype, symbol_short, token, Address, Env, String, Symbol, Vec};
 
 /// Contrato para el marketplace de NFTs culturales
 /// 
@@ -21,6 +21,94 @@ const AUCTION_COUNT_KEY: Symbol = symbol_short!("AUCT_CNT");
 const LISTING_KEY: Symbol = symbol_short!("LISTING");
 const AUCTION_KEY: Symbol = symbol_short!("AUCTION");
 const BID_KEY: Symbol = symbol_short!("BID");
+const PAYMENT_TOKEN_KEY: Symbol = symbol_short!("PAY_TKN");
+const TREASURY_KEY: Symbol = symbol_short!("TREASURY");
+const POINTS_DISCOUNT_KEY: Symbol = symbol_short!("PTS_DISC");
+const PRICE_DECIMALS_KEY: Symbol = symbol_short!("PRICE_DEC");
+const PRICE_TICK_KEY: Symbol = symbol_short!("PRICE_TCK");
+const ACTIVE_LISTINGS_KEY: Symbol = symbol_short!("ACT_LST");
+const ACTIVE_AUCTIONS_KEY: Symbol = symbol_short!("ACT_AUC");
+const VOLUME_KEY: Symbol = symbol_short!("VOLUME");
+const SALES_COUNT_KEY: Symbol = symbol_short!("SALE_CNT");
+const HIGHEST_SALE_KEY: Symbol = symbol_short!("HIGH_SALE");
+const LISTED_MUSEUM_KEY: Symbol = symbol_short!("LST_MUS");
+const LISTED_MUSEUM_COUNT_KEY: Symbol = symbol_short!("LST_MUSCT");
+const COLLECTIONS_KEY: Symbol = symbol_short!("COLLS");
+const COLLECTION_VOLUME_KEY: Symbol = symbol_short!("COLL_VOL");
+const COLLECTION_LISTINGS_KEY: Symbol = symbol_short!("COLL_LST");
+const COLLECTION_ACTIVE_KEY: Symbol = symbol_short!("COLL_ACTV");
+const WASH_WINDOW_KEY: Symbol = symbol_short!("WASH_WIN");
+const LAST_TRADE_KEY: Symbol = symbol_short!("LAST_TRD");
+const MUSEUM_REGISTRY_KEY: Symbol = symbol_short!("MUS_REG");
+const CONDITIONAL_LISTING_KEY: Symbol = symbol_short!("COND_LST");
+const MIN_BID_INCREMENT_KEY: Symbol = symbol_short!("MIN_BID");
+const RESERVE_KEY: Symbol = symbol_short!("RESERVE");
+const NOTIFICATION_KEY: Symbol = symbol_short!("NOTIF");
+const KEEPER_REWARD_KEY: Symbol = symbol_short!("KEEPR_RWD");
+const MARKET_HISTORY_KEY: Symbol = symbol_short!("MKT_HIST");
+const METADATA_CACHE_KEY: Symbol = symbol_short!("META_CACH");
+const CURVE_KEY: Symbol = symbol_short!("CURVE");
+const CURVE_COUNT_KEY: Symbol = symbol_short!("CURVE_CNT");
+const TOKEN_RATE_KEY: Symbol = symbol_short!("TKN_RATE");
+const TOKEN_VOLUME_KEY: Symbol = symbol_short!("TKN_VOL");
+const VOLUME_TOKENS_KEY: Symbol = symbol_short!("VOL_TKNS");
+const FEE_TIERS_KEY: Symbol = symbol_short!("FEE_TIERS");
+const BID_TICK_KEY: Symbol = symbol_short!("BID_TICK");
+const SELLER_LISTINGS_KEY: Symbol = symbol_short!("SELLR_LST");
+const SALE_KEY: Symbol = symbol_short!("SALE");
+const MUSEUM_VOLUME_KEY: Symbol = symbol_short!("MUS_VOL");
+const MUSEUM_SALES_KEY: Symbol = symbol_short!("MUS_SALES");
+const TOKEN_SALES_KEY: Symbol = symbol_short!("TKN_SALES");
+const COLLECTION_AUCTIONS_KEY: Symbol = symbol_short!("COLL_AUC");
+const WATCHLIST_KEY: Symbol = symbol_short!("WLIST");
+const CATEGORIES_KEY: Symbol = symbol_short!("CATEGS");
+const CATEGORY_LISTINGS_KEY: Symbol = symbol_short!("CATEG_LST");
+const USER_TRADES_KEY: Symbol = symbol_short!("USR_TRDS");
+const SELLER_ACTIVE_LISTINGS_KEY: Symbol = symbol_short!("SLR_ACTL");
+const SELLER_ACTIVE_AUCTIONS_KEY: Symbol = symbol_short!("SLR_ACTA");
+
+// Base para cálculos en basis points (10000 = 100%)
+const BPS_DENOMINATOR: i128 = 10000;
+
+// Tope de royalties por token en CulturalNFT; la comisión nunca puede superar el resto
+const MAX_ROYALTY_BPS: u32 = 1_000;
+const MAX_FEE_BPS: u32 = BPS_DENOMINATOR as u32 - MAX_ROYALTY_BPS;
+
+// Número de colecciones incluidas en el resumen de precios mínimos
+const TOP_COLLECTIONS: u32 = 5;
+
+// Listados activos revisados por colección al calcular su precio mínimo (los primeros del índice)
+const MAX_FLOOR_SCAN: u32 = 100;
+
+// Eventos conservados por token en el historial de mercado (se descartan los más antiguos)
+const MAX_MARKET_HISTORY: u32 = 100;
+
+// Vigencia de la caché de metadatos culturales, en ledgers (~1 hora)
+const METADATA_CACHE_TTL: u32 = 720;
+
+// Precisión de las tasas de conversión a la unidad de referencia (1 unidad = 10^7)
+const RATE_PRECISION: i128 = 10_000_000;
+
+// Máximo de tokens por listado con curva de precios
+const MAX_CURVE_TOKENS: u32 = 50;
+
+// Máximo de tramos en la estructura de comisiones escalonada
+const MAX_FEE_TIERS: u32 = 10;
+
+// Multiplicador máximo de precios en actualizaciones masivas (2x)
+const MAX_PRICE_MULTIPLIER_BPS: u32 = 20_000;
+
+// Tamaño máximo de página en consultas paginadas
+const MAX_PAGE_SIZE: u32 = 50;
+
+// Máximo de NFTs en la lista de seguimiento de un usuario
+const MAX_WATCHLIST_ITEMS: u32 = 50;
+
+// Máximo de categorías habilitadas para los listados
+const MAX_CATEGORIES: u32 = 20;
+
+// Máximo de operaciones conservadas en el historial de cada usuario
+const MAX_USER_TRADES: u32 = 200;
 
 // Estructura para listado de NFT
 #[derive(Clone)]
@@ -32,6 +120,28 @@ pub struct Listing {
     pub price: i128,
     pub active: bool,
     pub created_at: u64,
+    pub category: Option<Symbol>,
+}
+
+// Estructura para rebajas temporales de todos los listados de un vendedor
+#[derive(Clone)]
+#[contracttype]
+pub struct SellerSale {
+    pub discount_bps: u32,
+    pub ends_at: u64,
+}
+
+// Estructura para listados que se activan al cumplirse una condición
+#[derive(Clone)]
+#[contracttype]
+pub struct ConditionalListing {
+    pub seller: Address,
+    pub nft_contract: Address,
+    pub token_id: u32,
+    pub price: i128,
+    pub condition_type: String, // Por ahora solo "museum_verified"
+    pub museum: Address, // Museo cuya verificación activa el listado
+    pub created_at: u64,
 }
 
 // Estructura para subasta
@@ -47,6 +157,91 @@ pub struct Auction {
     pub start_time: u64,
     pub end_time: u64,
     pub active: bool,
+    pub escrowed: bool, // El NFT está en custodia del marketplace
+    pub payment_token: Option<Address>, // Token en el que se depositan las pujas
+}
+
+// Estructura para listados con precio creciente (bonding curve)
+#[derive(Clone)]
+#[contracttype]
+pub struct BondingCurveListing {
+    pub seller: Address,
+    pub nft_contract: Address,
+    pub token_ids: Vec<u32>, // Se venden en este orden
+    pub base_price: i128,
+    pub price_increment: i128, // Aumento de precio por cada venta
+    pub sold_count: u32,
+    pub active: bool,
+}
+
+// Estado de una subasta para interfaces de usuario
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum AuctionStatus {
+    Scheduled(u64), // Anunciada pero aún sin abrir (inicio de la subasta)
+    Active(i128, u64), // (puja actual, fin de la subasta)
+    EndedUnsettled, // Terminó pero falta llamar a end_auction
+    Settled, // Liquidada o cancelada
+    NotFound,
+}
+
+// Metadatos culturales tal como los expone el contrato CulturalNFT
+#[derive(Clone)]
+#[contracttype]
+pub struct CulturalMetadata {
+    pub title: String,
+    pub artist: String,
+    pub period: String,
+    pub culture: String,
+    pub material: String,
+    pub dimensions: String,
+    pub condition: String,
+    pub significance: String,
+    pub museum_address: Address,
+    pub location: ArtifactLocation,
+    pub is_soulbound: bool,
+}
+
+// Ubicación física del artefacto (latitud, longitud en millonésimas de grado)
+#[derive(Clone)]
+#[contracttype]
+pub enum ArtifactLocation {
+    Unknown,
+    Coordinates(i64, i64),
+}
+
+// Tipo de evento del historial de mercado de un token
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum MarketEventKind {
+    Listed,
+    Sold,
+    AuctionCreated,
+    BidPlaced,
+    AuctionEnded,
+    Cancelled,
+}
+
+// Estructura para eventos del historial de mercado
+#[derive(Clone)]
+#[contracttype]
+pub struct MarketEvent {
+    pub kind: MarketEventKind,
+    pub actor: Address, // Vendedor, comprador, pujador o ganador según el evento
+    pub amount: i128, // Precio, puja o 0 si no aplica
+    pub timestamp: u64,
+}
+
+// Estructura para ventas completadas en el historial de compras y ventas de cada usuario
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct SaleRecord {
+    pub nft_contract: Address,
+    pub token_id: u32,
+    pub seller: Address,
+    pub buyer: Address,
+    pub price: i128,
+    pub timestamp: u64,
 }
 
 // Estructura para puja
@@ -66,63 +261,284 @@ pub struct RoyaltyInfo {
     pub percentage: u32, // En basis points (100 = 1%)
 }
 
+// Estructura para comparar los contadores con los índices de listados y subastas
+#[derive(Clone)]
+#[contracttype]
+pub struct CounterCheck {
+    pub total_listings: u32,
+    pub indexed_listings: u32, // Listados distintos en los índices de colección
+    pub active_listings: u32,
+    pub indexed_active_listings: u32,
+    pub total_auctions: u32,
+    pub indexed_auctions: u32,
+    pub active_auctions: u32,
+    pub indexed_active_auctions: u32,
+    pub consistent: bool,
+}
+
+// Estadísticas agregadas del marketplace para dashboards
+#[derive(Clone)]
+#[contracttype]
+pub struct DashboardStats {
+    pub total_listings: u32,
+    pub active_listings: u32,
+    pub total_auctions: u32,
+    pub active_auctions: u32,
+    pub total_volume: i128,
+    pub total_sales: u32,
+    pub highest_sale: i128,
+    pub treasury_balance: i128, // Comisiones acumuladas en el token de pago vigente
+    pub total_museums_listed: u32, // Museos distintos con piezas listadas, subastadas o en curva
+    pub floor_price_map: Vec<(Address, i128)>, // Top colecciones por volumen con su precio mínimo (0 sin listados)
+}
+
+// Configuración del descuento por puntos SocialFi
+#[derive(Clone)]
+#[contracttype]
+pub struct PointsDiscount {
+    pub socialfi: Address,
+    pub points_cost: i128,
+    pub discount_bps: u32,
+}
+
+// Interfaz del contrato SocialFi usada para canjear puntos
+#[contractclient(name = "SocialFiClient")]
+pub trait SocialFiInterface {
+    fn spend_points(env: Env, spender: Address, user: Address, points: i128, reason: String);
+}
+
+// Interfaz del contrato MuseumRegistry usada para verificar museos
+#[contractclient(name = "MuseumRegistryClient")]
+pub trait MuseumRegistryInterface {
+    fn is_verified(env: Env, museum_address: Address) -> bool;
+    fn record_museum_activity(env: Env, peer: Address, museum_address: Address, kind: Symbol, amount: i128);
+}
+
+// Interfaz de los contratos que reciben avisos de pujas para un vendedor
+#[contractclient(name = "NotificationClient")]
+pub trait NotificationInterface {
+    fn on_bid_received(env: Env, seller: Address, nft_contract: Address, token_id: u32, bidder: Address, amount: i128);
+}
+
+// Interfaz del contrato CulturalNFT usada para royalties
+#[contractclient(name = "CulturalNFTClient")]
+pub trait CulturalNFTInterface {
+    fn royalty_info_for_resale(env: Env, token_id: u32, sale_price: i128, sale_count: u32) -> Vec<(Address, i128)>;
+    fn get_cultural_metadata(env: Env, token_id: u32) -> CulturalMetadata;
+    fn is_disputed(env: Env, token_id: u32) -> bool;
+    fn get_transfer_unlock_time(env: Env, token_id: u32) -> u64;
+    fn transfer(env: Env, from: Address, to: Address, token_id: u32);
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, token_id: u32);
+    fn owner_of(env: Env, token_id: u32) -> Address;
+    fn get_approved(env: Env, token_id: u32) -> Option<Address>;
+    fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool;
+    fn set_beneficial_owner(env: Env, custodian: Address, token_id: u32, beneficiary: Option<Address>);
+}
+
 #[contractimpl]
 impl Marketplace {
     /// Inicializa el contrato
-    pub fn initialize(env: Env, admin: Address, fee_percentage: u32) {
+    ///
+    /// `price_decimals` debe coincidir con los decimales del token de pago (7 para XLM).
+    /// `fee_percentage` (bps) no puede superar `MAX_FEE_BPS`, para que comisión más
+    /// royalties nunca excedan el precio.
+    pub fn initialize(env: Env, admin: Address, fee_percentage: u32, price_decimals: u32) {
         // Verificar que no esté ya inicializado
         if env.storage().instance().has(&ADMIN_KEY) {
             panic!("Contract already initialized");
         }
         
+        if fee_percentage > MAX_FEE_BPS {
+            panic!("Fee leaves no room for royalties");
+        }
+        
         // Guardar configuración inicial
         env.storage().instance().set(&ADMIN_KEY, &admin);
         env.storage().instance().set(&FEE_PERCENTAGE_KEY, &fee_percentage);
+        env.storage().instance().set(&PRICE_DECIMALS_KEY, &price_decimals);
         
         // Inicializar contadores
         env.storage().instance().set(&LISTING_COUNT_KEY, &0u32);
         env.storage().instance().set(&AUCTION_COUNT_KEY, &0u32);
     }
 
-    /// Lista un NFT para venta
+    /// Lista un NFT para venta, opcionalmente en una de las categorías habilitadas
     pub fn list_nft(
         env: Env,
         seller: Address,
         nft_contract: Address,
         token_id: u32,
         price: i128,
+        category: Option<Symbol>,
+    ) {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
+        
+        // Verificar que la categoría está habilitada
+        if let Some(category) = &category {
+            if !Self::get_categories(env.clone()).contains(category) {
+                panic!("Unknown category");
+            }
+        }
+        
+        Self::open_listing(&env, &seller, &nft_contract, token_id, price, category);
+    }
+
+    /// Habilita una categoría para los listados (solo admin)
+    pub fn add_category(env: Env, category: Symbol) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut categories = Self::get_categories(env.clone());
+        if categories.contains(&category) {
+            panic!("Category already exists");
+        }
+        if categories.len() >= MAX_CATEGORIES {
+            panic!("Too many categories");
+        }
+        
+        categories.push_back(category);
+        env.storage().instance().set(&CATEGORIES_KEY, &categories);
+    }
+
+    /// Deshabilita una categoría (solo admin); los listados existentes la conservan
+    pub fn remove_category(env: Env, category: Symbol) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut categories = Self::get_categories(env.clone());
+        let index = categories.first_index_of(&category).unwrap_or_else(|| {
+            panic!("Category not found");
+        });
+        
+        categories.remove(index);
+        env.storage().instance().set(&CATEGORIES_KEY, &categories);
+    }
+
+    /// Obtiene las categorías habilitadas
+    pub fn get_categories(env: Env) -> Vec<Symbol> {
+        env.storage().instance().get(&CATEGORIES_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Obtiene los listados activos de una categoría, en orden de publicación (paginado)
+    pub fn get_listings_by_category(env: Env, category: Symbol, offset: u32, limit: u32) -> Vec<Listing> {
+        let listed: Vec<(Address, u32)> = env.storage().persistent().get(&(CATEGORY_LISTINGS_KEY, category)).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(listed.len());
+        let mut listings = Vec::new(&env);
+        if offset >= end {
+            return listings;
+        }
+        for (nft_contract, token_id) in listed.slice(offset..end).iter() {
+            listings.push_back(Self::get_listing(env.clone(), nft_contract, token_id));
+        }
+        listings
+    }
+
+    /// Configura el contrato MuseumRegistry usado por los listados condicionales y las
+    /// estadísticas de los museos (solo admin)
+    pub fn set_museum_registry(env: Env, museum_registry: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().instance().set(&MUSEUM_REGISTRY_KEY, &museum_registry);
+    }
+
+    /// Obtiene el contrato MuseumRegistry configurado
+    pub fn get_museum_registry(env: Env) -> Option<Address> {
+        env.storage().instance().get(&MUSEUM_REGISTRY_KEY)
+    }
+
+    /// Pre-configura un listado que se activa cuando se cumple una condición
+    ///
+    /// Condición soportada: `condition_type = "museum_verified"`; el listado se activa
+    /// cuando `museum` está verificado.
+    pub fn create_conditional_listing(
+        env: Env,
+        seller: Address,
+        nft_contract: Address,
+        token_id: u32,
+        price: i128,
+        condition_type: String,
+        museum: Address,
     ) {
         // Verificar que el caller es el vendedor
         seller.require_auth();
         
+        if condition_type != String::from_str(&env, "museum_verified") {
+            panic!("Unsupported condition type");
+        }
+        
         // Verificar que el precio es positivo
         if price <= 0 {
             panic!("Price must be positive");
         }
+        Self::validate_price_tick(&env, price);
         
-        // Verificar que el NFT no está ya listado
-        let listing_key = (LISTING_KEY, nft_contract.clone(), token_id);
-        if env.storage().persistent().has(&listing_key) {
-            panic!("NFT already listed");
+        let conditional_key = (CONDITIONAL_LISTING_KEY, nft_contract.clone(), token_id);
+        if env.storage().persistent().has(&conditional_key) {
+            panic!("Conditional listing already exists");
         }
         
-        // Crear listado
-        let listing = Listing {
-            seller: seller.clone(),
-            nft_contract: nft_contract.clone(),
+        let conditional = ConditionalListing {
+            seller,
+            nft_contract,
             token_id,
             price,
-            active: true,
+            condition_type,
+            museum,
             created_at: env.ledger().timestamp(),
         };
+        env.storage().persistent().set(&conditional_key, &conditional);
+    }
+
+    /// Activa un listado condicional si su condición se cumple
+    pub fn activate_conditional_listing(env: Env, caller: Address, nft_contract: Address, token_id: u32) {
+        caller.require_auth();
         
-        // Guardar listado
-        env.storage().persistent().set(&listing_key, &listing);
+        let conditional_key = (CONDITIONAL_LISTING_KEY, nft_contract.clone(), token_id);
+        let conditional: ConditionalListing = env.storage().persistent().get(&conditional_key).unwrap_or_else(|| {
+            panic!("Conditional listing not found");
+        });
         
-        // Incrementar contador
-        let mut count: u32 = env.storage().instance().get(&LISTING_COUNT_KEY).unwrap_or(0);
-        count += 1;
-        env.storage().instance().set(&LISTING_COUNT_KEY, &count);
+        // Verificar que el museo está verificado
+        let museum_registry: Address = env.storage().instance().get(&MUSEUM_REGISTRY_KEY).unwrap_or_else(|| {
+            panic!("Museum registry not set");
+        });
+        if !MuseumRegistryClient::new(&env, &museum_registry).is_verified(&conditional.museum) {
+            panic!("Condition not met");
+        }
+        
+        env.storage().persistent().remove(&conditional_key);
+        Self::open_listing(&env, &conditional.seller, &nft_contract, token_id, conditional.price, None);
+    }
+
+    /// Cancela un listado condicional pendiente
+    pub fn cancel_conditional_listing(env: Env, seller: Address, nft_contract: Address, token_id: u32) {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
+        
+        let conditional_key = (CONDITIONAL_LISTING_KEY, nft_contract.clone(), token_id);
+        let conditional: ConditionalListing = env.storage().persistent().get(&conditional_key).unwrap_or_else(|| {
+            panic!("Conditional listing not found");
+        });
+        if conditional.seller != seller {
+            panic!("Not the seller");
+        }
+        
+        env.storage().persistent().remove(&conditional_key);
+    }
+
+    /// Obtiene un listado condicional pendiente
+    pub fn get_conditional_listing(env: Env, nft_contract: Address, token_id: u32) -> Option<ConditionalListing> {
+        env.storage().persistent().get(&(CONDITIONAL_LISTING_KEY, nft_contract, token_id))
     }
 
     /// Compra un NFT listado
@@ -135,35 +551,59 @@ impl Marketplace {
         // Verificar que el caller es el comprador
         buyer.require_auth();
         
-        // Obtener listado
-        let listing_key = (LISTING_KEY, nft_contract.clone(), token_id);
-        let mut listing: Listing = env.storage().persistent().get(&listing_key).unwrap_or_else(|| {
-            panic!("NFT not listed");
+        let listing = Self::take_listing(&env, &buyer, &nft_contract, token_id);
+        Self::ensure_deliverable(&env, &nft_contract, token_id, &listing.seller);
+        
+        let payment_token = Self::require_payment_token(&env);
+        let price = Self::sale_price(&env, &listing);
+        Self::settle_payment(&env, &payment_token, &buyer, &listing.seller, &nft_contract, token_id, price);
+        Self::record_sale(&env, &nft_contract, token_id, &listing.seller, &buyer, price);
+        
+        // Entregar el NFT en la misma llamada que la liquidación
+        CulturalNFTClient::new(&env, &nft_contract).transfer_from(&env.current_contract_address(), &listing.seller, &buyer, &token_id);
+    }
+
+    /// Compra un NFT listado canjeando puntos SocialFi por un descuento
+    pub fn buy_nft_with_points(
+        env: Env,
+        buyer: Address,
+        nft_contract: Address,
+        token_id: u32,
+    ) {
+        // Verificar que el caller es el comprador
+        buyer.require_auth();
+        
+        let discount: PointsDiscount = env.storage().instance().get(&POINTS_DISCOUNT_KEY).unwrap_or_else(|| {
+            panic!("Points discount not configured");
         });
         
-        // Verificar que el listado está activo
-        if !listing.active {
-            panic!("Listing not active");
-        }
+        let payment_token = Self::require_payment_token(&env);
         
-        // Verificar que el comprador no es el vendedor
-        if listing.seller == buyer {
-            panic!("Cannot buy your own NFT");
-        }
+        let listing = Self::take_listing(&env, &buyer, &nft_contract, token_id);
+        Self::ensure_deliverable(&env, &nft_contract, token_id, &listing.seller);
         
-        // TODO: Implementar transferencia de tokens (XLM)
-        // Por ahora solo marcamos como inactivo
+        // Canjear puntos antes de liquidar el pago
+        let socialfi = SocialFiClient::new(&env, &discount.socialfi);
+        socialfi.spend_points(
+            &env.current_contract_address(),
+            &buyer,
+            &discount.points_cost,
+            &String::from_str(&env, "marketplace_discount"),
+        );
         
-        // Marcar listado como inactivo
-        listing.active = false;
-        env.storage().persistent().set(&listing_key, &listing);
+        // Liquidar el precio reducido
+        let list_price = Self::sale_price(&env, &listing);
+        let reduction = list_price * discount.discount_bps as i128 / BPS_DENOMINATOR;
+        let price = list_price - reduction;
+        Self::settle_payment(&env, &payment_token, &buyer, &listing.seller, &nft_contract, token_id, price);
+        Self::record_sale(&env, &nft_contract, token_id, &listing.seller, &buyer, price);
         
-        // TODO: Transferir NFT al comprador
-        // TODO: Transferir pago al vendedor
-        // TODO: Distribuir royalties
+        // Entregar el NFT en la misma llamada que la liquidación
+        CulturalNFTClient::new(&env, &nft_contract).transfer_from(&env.current_contract_address(), &listing.seller, &buyer, &token_id);
     }
 
-    /// Crea una subasta
+    /// Crea una subasta que abre tras `start_delay` segundos (0 = de inmediato) y dura
+    /// `duration` segundos desde su apertura
     pub fn create_auction(
         env: Env,
         seller: Address,
@@ -171,6 +611,7 @@ impl Marketplace {
         token_id: u32,
         starting_price: i128,
         duration: u64,
+        start_delay: u64,
     ) {
         // Verificar que el caller es el vendedor
         seller.require_auth();
@@ -179,20 +620,33 @@ impl Marketplace {
         if starting_price <= 0 {
             panic!("Starting price must be positive");
         }
+        Self::validate_price_tick(&env, starting_price);
         
         // Verificar que la duración es válida
         if duration == 0 {
             panic!("Duration must be positive");
         }
         
+        // Verificar que el NFT no tiene una disputa de propiedad activa
+        Self::ensure_not_disputed(&env, &nft_contract, token_id);
+        
+        // Las pujas se depositan en el token de pago vigente al crear la subasta
+        let payment_token = Self::require_payment_token(&env);
+        
         // Verificar que el NFT no está ya en subasta
         let auction_key = (AUCTION_KEY, nft_contract.clone(), token_id);
         if env.storage().persistent().has(&auction_key) {
             panic!("NFT already in auction");
         }
         
+        // Tomar custodia del NFT (el vendedor debe haber aprobado al marketplace)
+        let marketplace = env.current_contract_address();
+        let nft = CulturalNFTClient::new(&env, &nft_contract);
+        nft.transfer_from(&marketplace, &seller, &marketplace, &token_id);
+        nft.set_beneficial_owner(&marketplace, &token_id, &Some(seller.clone()));
+        
         // Crear subasta
-        let start_time = env.ledger().timestamp();
+        let start_time = env.ledger().timestamp() + start_delay;
         let auction = Auction {
             seller: seller.clone(),
             nft_contract: nft_contract.clone(),
@@ -203,6 +657,8 @@ impl Marketplace {
             start_time,
             end_time: start_time + duration,
             active: true,
+            escrowed: true,
+            payment_token: Some(payment_token),
         };
         
         // Guardar subasta
@@ -212,6 +668,13 @@ impl Marketplace {
         let mut count: u32 = env.storage().instance().get(&AUCTION_COUNT_KEY).unwrap_or(0);
         count += 1;
         env.storage().instance().set(&AUCTION_COUNT_KEY, &count);
+        
+        // Actualizar estadísticas
+        Self::increment_counter(&env, &ACTIVE_AUCTIONS_KEY);
+        Self::adjust_seller_counter(&env, &SELLER_ACTIVE_AUCTIONS_KEY, &seller, true);
+        Self::track_listed_museum(&env, &nft_contract, token_id);
+        Self::track_collection_auction(&env, &nft_contract, token_id);
+        Self::log_market_event(&env, &nft_contract, token_id, MarketEventKind::AuctionCreated, &seller, starting_price);
     }
 
     /// Hace una puja en una subasta
@@ -230,24 +693,33 @@ impl Marketplace {
             panic!("Auction not active");
         }
         
-        // Verificar que la subasta no ha terminado
+        // Verificar que la subasta ya abrió y no ha terminado
+        if env.ledger().timestamp() < auction.start_time {
+            panic!("Auction not started");
+        }
         if env.ledger().timestamp() >= auction.end_time {
             panic!("Auction ended");
         }
         
-        // Verificar que la puja es mayor que la actual
-        if amount <= auction.current_bid {
+        // Verificar que la puja alcanza el mínimo (precio inicial o puja actual + incremento)
+        if amount < Self::min_bid(&env, &auction) {
+            if auction.current_bid == 0 {
+                panic!("Bid must be at least starting price");
+            }
             panic!("Bid must be higher than current bid");
         }
+        Self::validate_bid_tick(&env, &auction, amount);
         
-        // Verificar que la puja es mayor que el precio inicial
-        if amount < auction.starting_price {
-            panic!("Bid must be at least starting price");
-        }
+        Self::ensure_not_wash_trade(&env, &nft_contract, token_id, &auction.seller, &bidder);
         
-        // Devolver puja anterior si existe
+        // Depositar la puja y devolver la anterior si existe
+        let payment_token = auction.payment_token.clone().unwrap_or_else(|| {
+            panic!("Payment token not configured");
+        });
+        let client = token::Client::new(&env, &payment_token);
+        client.transfer(&bidder, &env.current_contract_address(), &amount);
         if auction.current_bid > 0 {
-            // TODO: Devolver tokens al pujador anterior
+            client.transfer(&env.current_contract_address(), &auction.highest_bidder, &auction.current_bid);
         }
         
         // Actualizar subasta
@@ -258,61 +730,472 @@ impl Marketplace {
         env.storage().persistent().set(&auction_key, &auction);
         
         // Guardar puja
-        let bid_key = (BID_KEY, nft_contract, token_id, bidder.clone());
+        let bid_key = (BID_KEY, nft_contract.clone(), token_id, bidder.clone());
         let bid = Bid {
             bidder: bidder.clone(),
             amount,
             timestamp: env.ledger().timestamp(),
         };
         env.storage().persistent().set(&bid_key, &bid);
+        Self::log_market_event(&env, &nft_contract, token_id, MarketEventKind::BidPlaced, &bidder, amount);
+        
+        // Avisar al vendedor si registró un contrato de notificaciones (best-effort:
+        // un contrato que falla no bloquea la puja)
+        if let Some(callback) = Self::get_notification_endpoint(env.clone(), auction.seller.clone()) {
+            let notification = NotificationClient::new(&env, &callback);
+            let _ = notification.try_on_bid_received(&auction.seller, &nft_contract, &token_id, &bidder, &amount);
+        }
     }
 
-    /// Finaliza una subasta
-    pub fn end_auction(env: Env, nft_contract: Address, token_id: u32) {
-        // Obtener subasta
-        let auction_key = (AUCTION_KEY, nft_contract.clone(), token_id);
-        let mut auction: Auction = env.storage().persistent().get(&auction_key).unwrap_or_else(|| {
-            panic!("Auction not found");
-        });
+    /// Crea un listado con curva de precios: cada compra cuesta
+    /// `base_price + sold_count * price_increment`
+    ///
+    /// Los tokens quedan en custodia del marketplace (el vendedor debe haberlo aprobado).
+    pub fn create_bonding_curve(
+        env: Env,
+        seller: Address,
+        nft_contract: Address,
+        token_ids: Vec<u32>,
+        base_price: i128,
+        price_increment: i128,
+    ) -> u32 {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
         
-        // Verificar que la subasta está activa
-        if !auction.active {
-            panic!("Auction not active");
+        if token_ids.is_empty() || token_ids.len() > MAX_CURVE_TOKENS {
+            panic!("Invalid number of tokens");
+        }
+        if base_price <= 0 {
+            panic!("Base price must be positive");
+        }
+        if price_increment < 0 {
+            panic!("Price increment cannot be negative");
         }
+        Self::validate_price_tick(&env, base_price);
+        Self::validate_price_tick(&env, price_increment);
         
-        // Verificar que la subasta ha terminado
-        if env.ledger().timestamp() < auction.end_time {
-            panic!("Auction not ended yet");
+        // Tomar custodia de los NFTs
+        let marketplace = env.current_contract_address();
+        let nft = CulturalNFTClient::new(&env, &nft_contract);
+        for token_id in token_ids.iter() {
+            Self::ensure_not_disputed(&env, &nft_contract, token_id);
+            nft.transfer_from(&marketplace, &seller, &marketplace, &token_id);
+            nft.set_beneficial_owner(&marketplace, &token_id, &Some(seller.clone()));
+            Self::track_listed_museum(&env, &nft_contract, token_id);
         }
         
-        // Marcar subasta como inactiva
-        auction.active = false;
-        env.storage().persistent().set(&auction_key, &auction);
+        // Obtener nuevo ID
+        let mut count: u32 = env.storage().instance().get(&CURVE_COUNT_KEY).unwrap_or(0);
+        count += 1;
+        env.storage().instance().set(&CURVE_COUNT_KEY, &count);
         
-        // Si hay pujas, transferir NFT al ganador
-        if auction.current_bid > 0 {
-            // TODO: Transferir NFT al ganador
-            // TODO: Transferir pago al vendedor
-            // TODO: Distribuir royalties
+        let curve = BondingCurveListing {
+            seller: seller.clone(),
+            nft_contract: nft_contract.clone(),
+            token_ids,
+            base_price,
+            price_increment,
+            sold_count: 0,
+            active: true,
+        };
+        env.storage().persistent().set(&(CURVE_KEY, count), &curve);
+        
+        Self::track_collection(&env, &nft_contract);
+        
+        count
+    }
+
+    /// Compra el siguiente token de una curva al precio actual
+    pub fn buy_from_curve(env: Env, buyer: Address, curve_id: u32) -> u32 {
+        // Verificar que el caller es el comprador
+        buyer.require_auth();
+        
+        let mut curve = Self::get_bonding_curve(env.clone(), curve_id);
+        
+        // Verificar que la curva sigue activa y le quedan tokens
+        if curve.sold_count >= curve.token_ids.len() {
+            panic!("Curve sold out");
         }
+        if !curve.active {
+            panic!("Curve not active");
+        }
+        let price = Self::curve_price(&curve);
+        
+        if curve.seller == buyer {
+            panic!("Cannot buy your own NFT");
+        }
+        
+        let payment_token = Self::require_payment_token(&env);
+        let token_id = curve.token_ids.get(curve.sold_count).unwrap();
+        Self::ensure_not_wash_trade(&env, &curve.nft_contract, token_id, &curve.seller, &buyer);
+        
+        // Avanzar la curva antes de liquidar
+        curve.sold_count += 1;
+        if curve.sold_count >= curve.token_ids.len() {
+            curve.active = false;
+        }
+        env.storage().persistent().set(&(CURVE_KEY, curve_id), &curve);
+        
+        Self::settle_payment(&env, &payment_token, &buyer, &curve.seller, &curve.nft_contract, token_id, price);
+        Self::record_sale(&env, &curve.nft_contract, token_id, &curve.seller, &buyer, price);
+        
+        let nft = CulturalNFTClient::new(&env, &curve.nft_contract);
+        nft.transfer(&env.current_contract_address(), &buyer, &token_id);
+        
+        // Publicar el nuevo precio de la curva
+        if curve.active {
+            env.events().publish(
+                (Symbol::new(&env, "curve_price"), curve_id),
+                Self::curve_price(&curve),
+            );
+        }
+        
+        token_id
     }
 
-    /// Obtiene información de un listado
-    pub fn get_listing(env: Env, nft_contract: Address, token_id: u32) -> Listing {
-        let listing_key = (LISTING_KEY, nft_contract, token_id);
-        env.storage().persistent().get(&listing_key).unwrap_or_else(|| {
-            panic!("Listing not found");
-        })
+    /// Cancela una curva y devuelve al vendedor los tokens no vendidos
+    pub fn cancel_bonding_curve(env: Env, seller: Address, curve_id: u32) {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
+        
+        let mut curve = Self::get_bonding_curve(env.clone(), curve_id);
+        if curve.seller != seller {
+            panic!("Not the seller");
+        }
+        if !curve.active {
+            panic!("Curve not active");
+        }
+        
+        curve.active = false;
+        env.storage().persistent().set(&(CURVE_KEY, curve_id), &curve);
+        
+        let nft = CulturalNFTClient::new(&env, &curve.nft_contract);
+        for token_id in curve.token_ids.slice(curve.sold_count..curve.token_ids.len()).iter() {
+            nft.transfer(&env.current_contract_address(), &seller, &token_id);
+        }
     }
 
-    /// Obtiene información de una subasta
-    pub fn get_auction(env: Env, nft_contract: Address, token_id: u32) -> Auction {
-        let auction_key = (AUCTION_KEY, nft_contract, token_id);
-        env.storage().persistent().get(&auction_key).unwrap_or_else(|| {
-            panic!("Auction not found");
+    /// Obtiene un listado con curva de precios
+    pub fn get_bonding_curve(env: Env, curve_id: u32) -> BondingCurveListing {
+        env.storage().persistent().get(&(CURVE_KEY, curve_id)).unwrap_or_else(|| {
+            panic!("Curve not found");
         })
     }
 
+    /// Obtiene el precio del siguiente token de una curva
+    pub fn get_current_curve_price(env: Env, curve_id: u32) -> i128 {
+        let curve = Self::get_bonding_curve(env, curve_id);
+        Self::curve_price(&curve)
+    }
+
+    /// Registra el contrato que recibe avisos cuando las subastas del vendedor reciben pujas
+    pub fn register_notification_endpoint(env: Env, seller: Address, callback_contract: Address) {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
+        
+        env.storage().persistent().set(&(NOTIFICATION_KEY, seller), &callback_contract);
+    }
+
+    /// Alias de `register_notification_endpoint` con el alcance del vendedor en el nombre
+    ///
+    /// `register_seller_notification_endpoint` supera el máximo de 32 caracteres de los
+    /// nombres de funciones de contrato, por eso se abrevia.
+    pub fn register_seller_notif_endpoint(env: Env, seller: Address, callback_contract: Address) {
+        Self::register_notification_endpoint(env, seller, callback_contract);
+    }
+
+    /// Elimina el contrato de notificaciones del vendedor
+    pub fn remove_notification_endpoint(env: Env, seller: Address) {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
+        
+        env.storage().persistent().remove(&(NOTIFICATION_KEY, seller));
+    }
+
+    /// Obtiene el contrato de notificaciones del vendedor
+    pub fn get_notification_endpoint(env: Env, seller: Address) -> Option<Address> {
+        env.storage().persistent().get(&(NOTIFICATION_KEY, seller))
+    }
+
+    /// Finaliza una subasta
+    ///
+    /// Quien la liquida (`keeper`) recibe el incentivo configurado, pagado de las comisiones
+    /// acumuladas, salvo que sea el vendedor o el ganador. Si no hubo pujas o no se alcanzó
+    /// la reserva, se devuelve la puja y el NFT vuelve al vendedor.
+    pub fn end_auction(env: Env, keeper: Address, nft_contract: Address, token_id: u32) {
+        // Verificar que el caller es el keeper
+        keeper.require_auth();
+        
+        // Obtener subasta
+        let auction_key = (AUCTION_KEY, nft_contract.clone(), token_id);
+        let mut auction: Auction = env.storage().persistent().get(&auction_key).unwrap_or_else(|| {
+            panic!("Auction not found");
+        });
+        
+        // Verificar que la subasta está activa
+        if !auction.active {
+            panic!("Auction not active");
+        }
+        
+        // Verificar que la subasta ha terminado
+        if env.ledger().timestamp() < auction.end_time {
+            panic!("Auction not ended yet");
+        }
+        
+        // Marcar subasta como inactiva
+        auction.active = false;
+        env.storage().persistent().set(&auction_key, &auction);
+        Self::decrement_counter(&env, &ACTIVE_AUCTIONS_KEY);
+        Self::adjust_seller_counter(&env, &SELLER_ACTIVE_AUCTIONS_KEY, &auction.seller, false);
+        
+        let marketplace = env.current_contract_address();
+        
+        Self::pay_keeper_reward(&env, &keeper, &auction);
+        
+        Self::log_market_event(&env, &nft_contract, token_id, MarketEventKind::AuctionEnded, &auction.highest_bidder, auction.current_bid);
+        
+        // Sin pujas o sin alcanzar la reserva: devolver la puja y el NFT, y cerrar sin venta
+        if !Self::reserve_met(env.clone(), nft_contract.clone(), token_id) {
+            if auction.current_bid > 0 {
+                if let Some(payment_token) = auction.payment_token.clone() {
+                    token::Client::new(&env, &payment_token).transfer(&marketplace, &auction.highest_bidder, &auction.current_bid);
+                }
+            }
+            if auction.escrowed {
+                let nft = CulturalNFTClient::new(&env, &nft_contract);
+                nft.transfer(&marketplace, &auction.seller, &token_id);
+            }
+            
+            env.events().publish(
+                (Symbol::new(&env, "auction_failed"), nft_contract, token_id),
+                auction.seller,
+            );
+            return;
+        }
+        
+        // Con pujas: pagar al vendedor con los fondos depositados y entregar el NFT al ganador
+        if let Some(payment_token) = auction.payment_token.clone() {
+            Self::settle_payment(&env, &payment_token, &marketplace, &auction.seller, &nft_contract, token_id, auction.current_bid);
+        }
+        Self::record_sale(&env, &nft_contract, token_id, &auction.seller, &auction.highest_bidder, auction.current_bid);
+        
+        if auction.escrowed {
+            let nft = CulturalNFTClient::new(&env, &nft_contract);
+            nft.transfer(&marketplace, &auction.highest_bidder, &token_id);
+        }
+    }
+
+    /// Obtiene información de un listado
+    pub fn get_listing(env: Env, nft_contract: Address, token_id: u32) -> Listing {
+        let listing_key = (LISTING_KEY, nft_contract, token_id);
+        env.storage().persistent().get(&listing_key).unwrap_or_else(|| {
+            panic!("Listing not found");
+        })
+    }
+
+    /// Obtiene un listado, o None si no existe (a diferencia de `get_listing`, no falla)
+    pub fn find_listing(env: Env, nft_contract: Address, token_id: u32) -> Option<Listing> {
+        env.storage().persistent().get(&(LISTING_KEY, nft_contract, token_id))
+    }
+
+    /// Obtiene los listados de varios tokens de un contrato en una sola llamada, en el mismo
+    /// orden que `token_ids` (None para los tokens sin listar; máximo `MAX_PAGE_SIZE`)
+    pub fn get_listings_for_tokens(env: Env, nft_contract: Address, token_ids: Vec<u32>) -> Vec<Option<Listing>> {
+        if token_ids.len() > MAX_PAGE_SIZE {
+            panic!("Too many tokens");
+        }
+        
+        let mut result = Vec::new(&env);
+        for token_id in token_ids.iter() {
+            result.push_back(Self::find_listing(env.clone(), nft_contract.clone(), token_id));
+        }
+        
+        result
+    }
+
+    /// Obtiene un listado junto con los metadatos culturales del token
+    ///
+    /// Los metadatos se guardan en caché temporal durante `METADATA_CACHE_TTL` ledgers para
+    /// evitar repetir la llamada al contrato del NFT.
+    pub fn get_listing_with_metadata(env: Env, nft_contract: Address, token_id: u32) -> (Listing, CulturalMetadata) {
+        let listing = Self::get_listing(env.clone(), nft_contract.clone(), token_id);
+        
+        let cache_key = (METADATA_CACHE_KEY, nft_contract.clone(), token_id);
+        let metadata = match env.storage().temporary().get(&cache_key) {
+            Some(metadata) => metadata,
+            None => {
+                let metadata = CulturalNFTClient::new(&env, &nft_contract).get_cultural_metadata(&token_id);
+                env.storage().temporary().set(&cache_key, &metadata);
+                env.storage().temporary().extend_ttl(&cache_key, METADATA_CACHE_TTL, METADATA_CACHE_TTL);
+                metadata
+            }
+        };
+        
+        (listing, metadata)
+    }
+
+    /// Obtiene el volumen negociado y el número de ventas de los NFTs de un museo
+    pub fn get_sale_volume_by_museum(env: Env, museum_address: Address) -> (i128, u32) {
+        let volume: i128 = env.storage().persistent().get(&(MUSEUM_VOLUME_KEY, museum_address.clone())).unwrap_or(0);
+        let sales: u32 = env.storage().persistent().get(&(MUSEUM_SALES_KEY, museum_address)).unwrap_or(0);
+        (volume, sales)
+    }
+
+    /// Obtiene cuántas veces se vendió un token en el marketplace
+    pub fn get_sale_count(env: Env, nft_contract: Address, token_id: u32) -> u32 {
+        env.storage().persistent().get(&(TOKEN_SALES_KEY, nft_contract, token_id)).unwrap_or(0)
+    }
+
+    /// Agrega un NFT a la lista de seguimiento del usuario
+    pub fn add_to_watchlist(env: Env, user: Address, nft_contract: Address, token_id: u32) {
+        // Verificar que el caller es el propio usuario
+        user.require_auth();
+        
+        let mut watchlist = Self::get_watchlist(env.clone(), user.clone());
+        let entry = (nft_contract, token_id);
+        if watchlist.contains(&entry) {
+            panic!("Already in watchlist");
+        }
+        if watchlist.len() >= MAX_WATCHLIST_ITEMS {
+            panic!("Watchlist full");
+        }
+        
+        watchlist.push_back(entry);
+        env.storage().persistent().set(&(WATCHLIST_KEY, user), &watchlist);
+    }
+
+    /// Quita un NFT de la lista de seguimiento del usuario
+    pub fn remove_from_watchlist(env: Env, user: Address, nft_contract: Address, token_id: u32) {
+        // Verificar que el caller es el propio usuario
+        user.require_auth();
+        
+        let mut watchlist = Self::get_watchlist(env.clone(), user.clone());
+        let index = watchlist.first_index_of(&(nft_contract, token_id)).unwrap_or_else(|| {
+            panic!("Not in watchlist");
+        });
+        
+        watchlist.remove(index);
+        env.storage().persistent().set(&(WATCHLIST_KEY, user), &watchlist);
+    }
+
+    /// Obtiene la lista de seguimiento de un usuario
+    pub fn get_watchlist(env: Env, user: Address) -> Vec<(Address, u32)> {
+        env.storage().persistent().get(&(WATCHLIST_KEY, user)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Obtiene la lista de seguimiento con el precio actual de cada NFT
+    ///
+    /// El precio es el de compra del listado activo (con la rebaja del vendedor aplicada)
+    /// o, si está en subasta, la puja actual o el precio inicial si aún no hay pujas.
+    /// None si el NFT no está a la venta.
+    pub fn get_watchlist_with_prices(env: Env, user: Address) -> Vec<(Address, u32, Option<i128>)> {
+        let mut result = Vec::new(&env);
+        for (nft_contract, token_id) in Self::get_watchlist(env.clone(), user).iter() {
+            let listing: Option<Listing> = env.storage().persistent().get(&(LISTING_KEY, nft_contract.clone(), token_id));
+            let auction: Option<Auction> = env.storage().persistent().get(&(AUCTION_KEY, nft_contract.clone(), token_id));
+            
+            let price = match (listing, auction) {
+                (Some(listing), _) if listing.active => Some(Self::sale_price(&env, &listing)),
+                (_, Some(auction)) if auction.active => Some(auction.current_bid.max(auction.starting_price)),
+                _ => None,
+            };
+            result.push_back((nft_contract, token_id, price));
+        }
+        
+        result
+    }
+
+    /// Obtiene información de una subasta
+    pub fn get_auction(env: Env, nft_contract: Address, token_id: u32) -> Auction {
+        let auction_key = (AUCTION_KEY, nft_contract, token_id);
+        env.storage().persistent().get(&auction_key).unwrap_or_else(|| {
+            panic!("Auction not found");
+        })
+    }
+
+    /// Obtiene una subasta, o None si no existe (a diferencia de `get_auction`, no falla)
+    pub fn find_auction(env: Env, nft_contract: Address, token_id: u32) -> Option<Auction> {
+        env.storage().persistent().get(&(AUCTION_KEY, nft_contract, token_id))
+    }
+
+    /// Obtiene la puja mínima aceptada: el precio inicial si no hay pujas, o la puja
+    /// actual más el incremento mínimo
+    pub fn next_min_bid(env: Env, nft_contract: Address, token_id: u32) -> i128 {
+        let auction = Self::get_auction(env.clone(), nft_contract, token_id);
+        Self::min_bid(&env, &auction)
+    }
+
+    /// Configura el precio de reserva oculto de una subasta (solo el vendedor, antes de la
+    /// primera puja; 0 = sin reserva)
+    ///
+    /// Si al finalizar la puja más alta no alcanza la reserva, no hay venta: se devuelve la
+    /// puja y el NFT vuelve al vendedor.
+    pub fn set_reserve_price(env: Env, seller: Address, nft_contract: Address, token_id: u32, reserve_price: i128) {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
+        
+        let auction = Self::get_auction(env.clone(), nft_contract.clone(), token_id);
+        if auction.seller != seller {
+            panic!("Not the seller");
+        }
+        if !auction.active {
+            panic!("Auction not active");
+        }
+        
+        // La reserva no puede cambiar una vez que hay pujas
+        if auction.current_bid > 0 {
+            panic!("Auction already has bids");
+        }
+        
+        let reserve_key = (RESERVE_KEY, nft_contract, token_id);
+        if reserve_price == 0 {
+            env.storage().persistent().remove(&reserve_key);
+            return;
+        }
+        if reserve_price < auction.starting_price {
+            panic!("Reserve below starting price");
+        }
+        env.storage().persistent().set(&reserve_key, &reserve_price);
+    }
+
+    /// Obtiene el precio de reserva de una subasta (0 si no tiene)
+    pub fn get_reserve_price(env: Env, nft_contract: Address, token_id: u32) -> i128 {
+        env.storage().persistent().get(&(RESERVE_KEY, nft_contract, token_id)).unwrap_or(0)
+    }
+
+    /// Verifica si la puja actual alcanza el precio de reserva (sin reserva basta una puja)
+    pub fn reserve_met(env: Env, nft_contract: Address, token_id: u32) -> bool {
+        let auction = Self::get_auction(env.clone(), nft_contract.clone(), token_id);
+        auction.current_bid > 0 && auction.current_bid >= Self::get_reserve_price(env, nft_contract, token_id)
+    }
+
+    /// Obtiene el estado de una subasta sin fallar si no existe
+    pub fn get_auction_status(env: Env, nft_contract: Address, token_id: u32) -> AuctionStatus {
+        let auction_key = (AUCTION_KEY, nft_contract, token_id);
+        let auction: Auction = match env.storage().persistent().get(&auction_key) {
+            Some(auction) => auction,
+            None => return AuctionStatus::NotFound,
+        };
+        
+        if !auction.active {
+            AuctionStatus::Settled
+        } else if env.ledger().timestamp() < auction.start_time {
+            AuctionStatus::Scheduled(auction.start_time)
+        } else if env.ledger().timestamp() < auction.end_time {
+            AuctionStatus::Active(auction.current_bid, auction.end_time)
+        } else {
+            AuctionStatus::EndedUnsettled
+        }
+    }
+
+    /// Verifica si una subasta ya abrió para recibir pujas
+    pub fn has_started(env: Env, nft_contract: Address, token_id: u32) -> bool {
+        let auction = Self::get_auction(env.clone(), nft_contract, token_id);
+        env.ledger().timestamp() >= auction.start_time
+    }
+
     /// Obtiene la puja más alta de una subasta
     pub fn get_highest_bid(env: Env, nft_contract: Address, token_id: u32) -> i128 {
         let auction = Self::get_auction(env, nft_contract, token_id);
@@ -325,6 +1208,66 @@ impl Marketplace {
         auction.highest_bidder
     }
 
+    /// Obtiene el historial de mercado de un token (listados, ventas, subastas, pujas y
+    /// cancelaciones), del más antiguo al más reciente (paginado)
+    ///
+    /// Solo se conservan los últimos `MAX_MARKET_HISTORY` eventos por token.
+    pub fn get_token_market_history(
+        env: Env,
+        nft_contract: Address,
+        token_id: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<MarketEvent> {
+        let history: Vec<MarketEvent> = env.storage().persistent().get(&(MARKET_HISTORY_KEY, nft_contract, token_id)).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(history.len());
+        if offset >= end {
+            return Vec::new(&env);
+        }
+        history.slice(offset..end)
+    }
+
+    /// Obtiene las compras y ventas del usuario entre dos fechas, inclusive, de la más
+    /// antigua a la más reciente (ej. para declaraciones de impuestos)
+    ///
+    /// Solo se conservan las últimas `MAX_USER_TRADES` operaciones por usuario.
+    pub fn get_trades_in_period(env: Env, user: Address, start_ts: u64, end_ts: u64) -> Vec<SaleRecord> {
+        let mut trades = Vec::new(&env);
+        for trade in Self::user_trades(&env, &user).iter() {
+            if trade.timestamp >= start_ts && trade.timestamp <= end_ts {
+                trades.push_back(trade);
+            }
+        }
+        trades
+    }
+
+    /// Obtiene la ganancia (o pérdida) realizada por el usuario en el período: la suma de
+    /// `precio de venta - precio de compra` de los tokens que compró y vendió dentro de él
+    pub fn get_total_gains_in_period(env: Env, user: Address, start_ts: u64, end_ts: u64) -> i128 {
+        let trades = Self::get_trades_in_period(env, user.clone(), start_ts, end_ts);
+        
+        let mut gains: i128 = 0;
+        for i in 0..trades.len() {
+            let sale = trades.get(i).unwrap();
+            if sale.seller != user {
+                continue;
+            }
+            
+            // Emparejar con la compra más reciente del mismo token
+            for j in (0..i).rev() {
+                let purchase = trades.get(j).unwrap();
+                if purchase.buyer == user && purchase.nft_contract == sale.nft_contract && purchase.token_id == sale.token_id {
+                    gains += sale.price - purchase.price;
+                    break;
+                }
+            }
+        }
+        gains
+    }
+
     /// Cancela un listado
     pub fn cancel_listing(env: Env, seller: Address, nft_contract: Address, token_id: u32) {
         // Verificar que el caller es el vendedor
@@ -349,6 +1292,92 @@ impl Marketplace {
         // Marcar como inactivo
         listing.active = false;
         env.storage().persistent().set(&listing_key, &listing);
+        Self::decrement_counter(&env, &ACTIVE_LISTINGS_KEY);
+        Self::adjust_seller_counter(&env, &SELLER_ACTIVE_LISTINGS_KEY, &seller, false);
+        Self::untrack_category_listing(&env, &listing);
+        Self::untrack_active_listing(&env, &nft_contract, token_id);
+        Self::log_market_event(&env, &nft_contract, token_id, MarketEventKind::Cancelled, &seller, listing.price);
+    }
+
+    /// Reajusta el precio de todos los listados activos del vendedor a
+    /// `precio * price_multiplier_bps / 10000` (máximo 2x); devuelve cuántos se actualizaron
+    ///
+    /// Con un tick de precios configurado, el nuevo precio se redondea hacia abajo al tick.
+    pub fn bulk_update_prices(env: Env, seller: Address, price_multiplier_bps: u32) -> u32 {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
+        
+        if price_multiplier_bps == 0 || price_multiplier_bps > MAX_PRICE_MULTIPLIER_BPS {
+            panic!("Invalid price multiplier");
+        }
+        
+        let tick = Self::get_price_tick(env.clone());
+        let index_key = (SELLER_LISTINGS_KEY, seller.clone());
+        let index: Vec<(Address, u32)> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        // Actualizar los listados activos y descartar del índice los vendidos o cancelados
+        let mut remaining = Vec::new(&env);
+        for (nft_contract, token_id) in index.iter() {
+            let listing_key = (LISTING_KEY, nft_contract.clone(), token_id);
+            let mut listing: Listing = match env.storage().persistent().get(&listing_key) {
+                Some(listing) => listing,
+                None => continue,
+            };
+            if !listing.active || listing.seller != seller {
+                continue;
+            }
+            
+            let mut price = listing.price * price_multiplier_bps as i128 / BPS_DENOMINATOR;
+            if tick > 0 {
+                price -= price % tick;
+            }
+            if price <= 0 {
+                panic!("Price must be positive");
+            }
+            
+            listing.price = price;
+            env.storage().persistent().set(&listing_key, &listing);
+            remaining.push_back((nft_contract, token_id));
+        }
+        env.storage().persistent().set(&index_key, &remaining);
+        
+        remaining.len()
+    }
+
+    /// Activa una rebaja temporal sobre todos los listados del vendedor durante `duration`
+    /// segundos (`discount_bps = 0` la cancela)
+    ///
+    /// Los precios guardados no cambian: el descuento se aplica al comprar con `buy_nft`.
+    pub fn set_sale(env: Env, seller: Address, discount_bps: u32, duration: u64) {
+        // Verificar que el caller es el vendedor
+        seller.require_auth();
+        
+        let sale_key = (SALE_KEY, seller);
+        if discount_bps == 0 {
+            env.storage().persistent().remove(&sale_key);
+            return;
+        }
+        
+        if discount_bps as i128 >= BPS_DENOMINATOR {
+            panic!("Discount must be below 100%");
+        }
+        if duration == 0 {
+            panic!("Duration must be positive");
+        }
+        
+        let sale = SellerSale {
+            discount_bps,
+            ends_at: env.ledger().timestamp() + duration,
+        };
+        env.storage().persistent().set(&sale_key, &sale);
+    }
+
+    /// Obtiene la rebaja vigente de un vendedor (None si no hay o ya terminó)
+    pub fn get_sale(env: Env, seller: Address) -> Option<SellerSale> {
+        let sale: Option<SellerSale> = env.storage().persistent().get(&(SALE_KEY, seller));
+        sale.filter(|sale| env.ledger().timestamp() < sale.ends_at)
     }
 
     /// Cancela una subasta
@@ -380,6 +1409,15 @@ impl Marketplace {
         // Marcar como inactiva
         auction.active = false;
         env.storage().persistent().set(&auction_key, &auction);
+        Self::decrement_counter(&env, &ACTIVE_AUCTIONS_KEY);
+        Self::adjust_seller_counter(&env, &SELLER_ACTIVE_AUCTIONS_KEY, &seller, false);
+        Self::log_market_event(&env, &nft_contract, token_id, MarketEventKind::Cancelled, &seller, 0);
+        
+        // Devolver el NFT en custodia al vendedor
+        if auction.escrowed {
+            let nft = CulturalNFTClient::new(&env, &nft_contract);
+            nft.transfer(&env.current_contract_address(), &seller, &token_id);
+        }
     }
 
     /// Obtiene el porcentaje de comisión del marketplace
@@ -387,6 +1425,71 @@ impl Marketplace {
         env.storage().instance().get(&FEE_PERCENTAGE_KEY).unwrap()
     }
 
+    /// Configura comisiones escalonadas por precio de venta (solo admin)
+    ///
+    /// Cada tramo es `(umbral, bps)`: se aplica a precios mayores o iguales al umbral
+    /// hasta el siguiente tramo. Por ejemplo `[(0, 250), (1_000_000, 150), (10_000_000, 75)]`.
+    /// Una lista vacía vuelve a la comisión plana. Ningún tramo puede superar `MAX_FEE_BPS`.
+    pub fn set_fee_tiers(env: Env, tiers: Vec<(i128, u32)>) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if tiers.is_empty() {
+            env.storage().instance().remove(&FEE_TIERS_KEY);
+            return;
+        }
+        
+        if tiers.len() > MAX_FEE_TIERS {
+            panic!("Too many fee tiers");
+        }
+        
+        // Verificar que los tramos empiezan en 0, con umbrales crecientes y bps válidos
+        let mut previous: Option<i128> = None;
+        for (threshold, bps) in tiers.iter() {
+            match previous {
+                None if threshold != 0 => panic!("First fee tier must start at 0"),
+                Some(prev) if threshold <= prev => panic!("Fee tier thresholds must be ascending"),
+                _ => {}
+            }
+            if bps > MAX_FEE_BPS {
+                panic!("Fee leaves no room for royalties");
+            }
+            previous = Some(threshold);
+        }
+        
+        env.storage().instance().set(&FEE_TIERS_KEY, &tiers);
+    }
+
+    /// Obtiene los tramos de comisión configurados (vacío si se usa la comisión plana)
+    pub fn get_fee_tiers(env: Env) -> Vec<(i128, u32)> {
+        env.storage().instance().get(&FEE_TIERS_KEY).unwrap_or(Vec::new(&env))
+    }
+
+    /// Calcula la comisión del marketplace para un precio de venta
+    ///
+    /// Usa el tramo cuyo umbral es el mayor que no supera el precio, o la comisión
+    /// plana si no hay tramos configurados.
+    pub fn compute_fee(env: Env, price: i128) -> i128 {
+        let tiers: Vec<(i128, u32)> = Self::get_fee_tiers(env.clone());
+        let mut fee_bps: u32 = env.storage().instance().get(&FEE_PERCENTAGE_KEY).unwrap();
+        
+        for (threshold, bps) in tiers.iter() {
+            if price < threshold {
+                break;
+            }
+            fee_bps = bps;
+        }
+        
+        price * fee_bps as i128 / BPS_DENOMINATOR
+    }
+
+    /// Verifica si una dirección es el admin del contrato
+    pub fn is_admin(env: Env, who: Address) -> bool {
+        let admin: Option<Address> = env.storage().instance().get(&ADMIN_KEY);
+        admin == Some(who)
+    }
+
     /// Obtiene el total de listados
     pub fn get_total_listings(env: Env) -> u32 {
         env.storage().instance().get(&LISTING_COUNT_KEY).unwrap_or(0)
@@ -397,6 +1500,310 @@ impl Marketplace {
         env.storage().instance().get(&AUCTION_COUNT_KEY).unwrap_or(0)
     }
 
+    /// Obtiene el número de listados activos de un vendedor
+    pub fn get_seller_active_listing_count(env: Env, seller: Address) -> u32 {
+        env.storage().persistent().get(&(SELLER_ACTIVE_LISTINGS_KEY, seller)).unwrap_or(0)
+    }
+
+    /// Obtiene el número de subastas activas de un vendedor
+    pub fn get_seller_active_auction_count(env: Env, seller: Address) -> u32 {
+        env.storage().persistent().get(&(SELLER_ACTIVE_AUCTIONS_KEY, seller)).unwrap_or(0)
+    }
+
+    /// Configura el token de pago del marketplace (solo admin)
+    pub fn set_payment_token(env: Env, payment_token: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().instance().set(&PAYMENT_TOKEN_KEY, &payment_token);
+    }
+
+    /// Obtiene el token de pago configurado
+    pub fn get_payment_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&PAYMENT_TOKEN_KEY)
+    }
+
+    /// Obtiene los decimales de los precios (para formatear en front-ends)
+    pub fn get_price_decimals(env: Env) -> u32 {
+        env.storage().instance().get(&PRICE_DECIMALS_KEY).unwrap_or(0)
+    }
+
+    /// Configura el tick mínimo de precios: los precios deben ser múltiplos (solo admin, 0 = sin tick)
+    pub fn set_price_tick(env: Env, tick: i128) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if tick < 0 {
+            panic!("Tick cannot be negative");
+        }
+        
+        env.storage().instance().set(&PRICE_TICK_KEY, &tick);
+    }
+
+    /// Obtiene el tick mínimo de precios
+    pub fn get_price_tick(env: Env) -> i128 {
+        env.storage().instance().get(&PRICE_TICK_KEY).unwrap_or(0)
+    }
+
+    /// Configura el tick de pujas: cada puja debe superar a la actual (o al precio inicial)
+    /// en un múltiplo del tick (solo admin, 0 = sin tick)
+    pub fn set_bid_tick(env: Env, tick: i128) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if tick < 0 {
+            panic!("Tick cannot be negative");
+        }
+        
+        env.storage().instance().set(&BID_TICK_KEY, &tick);
+    }
+
+    /// Obtiene el tick de pujas
+    pub fn get_bid_tick(env: Env) -> i128 {
+        env.storage().instance().get(&BID_TICK_KEY).unwrap_or(0)
+    }
+
+    /// Configura la ventana anti wash trading en segundos (solo admin, 0 = desactivada)
+    pub fn set_wash_trade_window(env: Env, window: u64) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().instance().set(&WASH_WINDOW_KEY, &window);
+    }
+
+    /// Obtiene la ventana anti wash trading en segundos
+    pub fn get_wash_trade_window(env: Env) -> u64 {
+        env.storage().instance().get(&WASH_WINDOW_KEY).unwrap_or(0)
+    }
+
+    /// Configura el incremento mínimo entre pujas (solo admin)
+    pub fn set_min_bid_increment(env: Env, increment: i128) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if increment <= 0 {
+            panic!("Increment must be positive");
+        }
+        
+        env.storage().instance().set(&MIN_BID_INCREMENT_KEY, &increment);
+    }
+
+    /// Obtiene el incremento mínimo entre pujas (por defecto 1 unidad)
+    pub fn get_min_bid_increment(env: Env) -> i128 {
+        env.storage().instance().get(&MIN_BID_INCREMENT_KEY).unwrap_or(1)
+    }
+
+    /// Configura el descuento por canje de puntos SocialFi (solo admin)
+    pub fn set_points_discount(env: Env, socialfi: Address, points_cost: i128, discount_bps: u32) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if points_cost <= 0 {
+            panic!("Points cost must be positive");
+        }
+        
+        if discount_bps == 0 || discount_bps as i128 > BPS_DENOMINATOR {
+            panic!("Invalid discount");
+        }
+        
+        let discount = PointsDiscount {
+            socialfi,
+            points_cost,
+            discount_bps,
+        };
+        env.storage().instance().set(&POINTS_DISCOUNT_KEY, &discount);
+    }
+
+    /// Obtiene la configuración del descuento por puntos
+    pub fn get_points_discount(env: Env) -> Option<PointsDiscount> {
+        env.storage().instance().get(&POINTS_DISCOUNT_KEY)
+    }
+
+    /// Configura el incentivo pagado a quien liquida una subasta vencida (solo admin; 0 lo desactiva)
+    pub fn set_keeper_reward(env: Env, reward: i128) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if reward < 0 {
+            panic!("Reward cannot be negative");
+        }
+        
+        env.storage().instance().set(&KEEPER_REWARD_KEY, &reward);
+    }
+
+    /// Obtiene el incentivo para quien liquida subastas
+    pub fn get_keeper_reward(env: Env) -> i128 {
+        env.storage().instance().get(&KEEPER_REWARD_KEY).unwrap_or(0)
+    }
+
+    /// Configura la tasa de conversión de un token de pago a la unidad de referencia
+    /// (solo admin; en unidades de `RATE_PRECISION`, 0 elimina la tasa)
+    pub fn set_token_rate(env: Env, token: Address, rate: i128) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if rate < 0 {
+            panic!("Rate cannot be negative");
+        }
+        
+        let rate_key = (TOKEN_RATE_KEY, token);
+        if rate == 0 {
+            env.storage().persistent().remove(&rate_key);
+        } else {
+            env.storage().persistent().set(&rate_key, &rate);
+        }
+    }
+
+    /// Obtiene la tasa de conversión de un token (None si no está configurada)
+    pub fn get_token_rate(env: Env, token: Address) -> Option<i128> {
+        env.storage().persistent().get(&(TOKEN_RATE_KEY, token))
+    }
+
+    /// Obtiene el volumen negociado en un token de pago
+    pub fn get_token_volume(env: Env, token: Address) -> i128 {
+        env.storage().persistent().get(&(TOKEN_VOLUME_KEY, token)).unwrap_or(0)
+    }
+
+    /// Obtiene el volumen total en la unidad de referencia, sumando el volumen de cada
+    /// token de pago convertido con su tasa (los tokens sin tasa no se incluyen)
+    pub fn get_normalized_volume(env: Env) -> i128 {
+        let tokens: Vec<Address> = env.storage().instance().get(&VOLUME_TOKENS_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        let mut total: i128 = 0;
+        for token in tokens.iter() {
+            if let Some(rate) = Self::get_token_rate(env.clone(), token.clone()) {
+                total += Self::get_token_volume(env.clone(), token) * rate / RATE_PRECISION;
+            }
+        }
+        
+        total
+    }
+
+    /// Obtiene las comisiones acumuladas por el marketplace en un token de pago
+    pub fn get_treasury_balance(env: Env, token: Address) -> i128 {
+        env.storage().persistent().get(&(TREASURY_KEY, token)).unwrap_or(0)
+    }
+
+    /// Compara los contadores de listados y subastas con los índices de colección
+    ///
+    /// Los activos deben coincidir exactamente. Los totales incluyen re-listados del mismo
+    /// token, así que solo son inconsistentes si quedan por debajo de lo indexado.
+    pub fn check_counters(env: Env) -> CounterCheck {
+        let instance = env.storage().instance();
+        let (indexed_listings, indexed_active_listings, indexed_auctions, indexed_active_auctions) = Self::count_indexed(&env);
+        let total_listings: u32 = instance.get(&LISTING_COUNT_KEY).unwrap_or(0);
+        let active_listings: u32 = instance.get(&ACTIVE_LISTINGS_KEY).unwrap_or(0);
+        let total_auctions: u32 = instance.get(&AUCTION_COUNT_KEY).unwrap_or(0);
+        let active_auctions: u32 = instance.get(&ACTIVE_AUCTIONS_KEY).unwrap_or(0);
+        
+        CounterCheck {
+            total_listings,
+            indexed_listings,
+            active_listings,
+            indexed_active_listings,
+            total_auctions,
+            indexed_auctions,
+            active_auctions,
+            indexed_active_auctions,
+            consistent: active_listings == indexed_active_listings
+                && active_auctions == indexed_active_auctions
+                && total_listings >= indexed_listings
+                && total_auctions >= indexed_auctions,
+        }
+    }
+
+    /// Reconstruye los contadores de listados y subastas desde los índices (solo admin)
+    ///
+    /// Herramienta de reparación: los activos se recalculan y los totales se elevan al
+    /// número indexado si quedaron por debajo. Recorre todas las colecciones.
+    pub fn recount_counters(env: Env) -> CounterCheck {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let report = Self::check_counters(env.clone());
+        let instance = env.storage().instance();
+        instance.set(&LISTING_COUNT_KEY, &report.total_listings.max(report.indexed_listings));
+        instance.set(&ACTIVE_LISTINGS_KEY, &report.indexed_active_listings);
+        instance.set(&AUCTION_COUNT_KEY, &report.total_auctions.max(report.indexed_auctions));
+        instance.set(&ACTIVE_AUCTIONS_KEY, &report.indexed_active_auctions);
+        
+        Self::check_counters(env)
+    }
+
+    /// Cuenta listados y subastas (totales, activos) en los índices de colección
+    fn count_indexed(env: &Env) -> (u32, u32, u32, u32) {
+        let collections: Vec<Address> = env.storage().instance().get(&COLLECTIONS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        
+        let (mut listings, mut active_listings, mut auctions, mut active_auctions) = (0u32, 0u32, 0u32, 0u32);
+        for nft_contract in collections.iter() {
+            let listed: Vec<u32> = env.storage().persistent().get(&(COLLECTION_LISTINGS_KEY, nft_contract.clone())).unwrap_or_else(|| {
+                Vec::new(env)
+            });
+            for token_id in listed.iter() {
+                let listing: Option<Listing> = env.storage().persistent().get(&(LISTING_KEY, nft_contract.clone(), token_id));
+                if let Some(listing) = listing {
+                    listings += 1;
+                    if listing.active {
+                        active_listings += 1;
+                    }
+                }
+            }
+            
+            let auctioned: Vec<u32> = env.storage().persistent().get(&(COLLECTION_AUCTIONS_KEY, nft_contract.clone())).unwrap_or_else(|| {
+                Vec::new(env)
+            });
+            for token_id in auctioned.iter() {
+                let auction: Option<Auction> = env.storage().persistent().get(&(AUCTION_KEY, nft_contract.clone(), token_id));
+                if let Some(auction) = auction {
+                    auctions += 1;
+                    if auction.active {
+                        active_auctions += 1;
+                    }
+                }
+            }
+        }
+        
+        (listings, active_listings, auctions, active_auctions)
+    }
+
+    /// Obtiene todas las estadísticas del marketplace en una sola llamada
+    pub fn get_dashboard_stats(env: Env) -> DashboardStats {
+        let instance = env.storage().instance();
+        
+        DashboardStats {
+            total_listings: instance.get(&LISTING_COUNT_KEY).unwrap_or(0),
+            active_listings: instance.get(&ACTIVE_LISTINGS_KEY).unwrap_or(0),
+            total_auctions: instance.get(&AUCTION_COUNT_KEY).unwrap_or(0),
+            active_auctions: instance.get(&ACTIVE_AUCTIONS_KEY).unwrap_or(0),
+            total_volume: instance.get(&VOLUME_KEY).unwrap_or(0),
+            total_sales: instance.get(&SALES_COUNT_KEY).unwrap_or(0),
+            highest_sale: instance.get(&HIGHEST_SALE_KEY).unwrap_or(0),
+            treasury_balance: Self::get_payment_token(env.clone())
+                .map(|token| Self::get_treasury_balance(env.clone(), token))
+                .unwrap_or(0),
+            total_museums_listed: instance.get(&LISTED_MUSEUM_COUNT_KEY).unwrap_or(0),
+            floor_price_map: Self::top_collection_floors(&env),
+        }
+    }
+
+    /// Acepta NFTs recibidos vía `safe_transfer` para custodiarlos (escrow)
+    pub fn on_nft_received(_env: Env, _operator: Address, _from: Address, _token_id: u32) -> bool {
+        true
+    }
+
     /// Distribuye royalties automáticamente
     pub fn distribute_royalties(
         _env: Env,
@@ -408,4 +1815,600 @@ impl Marketplace {
         // Esto requeriría integración con el contrato de NFT
         // para obtener información de royalties
     }
+
+    /// Incrementa un contador de instancia
+    fn increment_counter(env: &Env, key: &Symbol) {
+        let count: u32 = env.storage().instance().get(key).unwrap_or(0);
+        env.storage().instance().set(key, &(count + 1));
+    }
+
+    /// Decrementa un contador de instancia
+    fn decrement_counter(env: &Env, key: &Symbol) {
+        let count: u32 = env.storage().instance().get(key).unwrap_or(0);
+        env.storage().instance().set(key, &count.saturating_sub(1));
+    }
+
+    /// Incrementa o decrementa un contador por vendedor
+    fn adjust_seller_counter(env: &Env, key: &Symbol, seller: &Address, increment: bool) {
+        let counter_key = (key.clone(), seller.clone());
+        let count: u32 = env.storage().persistent().get(&counter_key).unwrap_or(0);
+        let count = if increment { count + 1 } else { count.saturating_sub(1) };
+        env.storage().persistent().set(&counter_key, &count);
+    }
+
+    /// Registra el museo que minteó el token para el conteo de museos distintos con piezas
+    /// en venta
+    ///
+    /// Como `record_museum_volume`, es best-effort: los tokens sin metadatos culturales no
+    /// se atribuyen a ningún museo.
+    fn track_listed_museum(env: &Env, nft_contract: &Address, token_id: u32) {
+        let museum = match CulturalNFTClient::new(env, nft_contract).try_get_cultural_metadata(&token_id) {
+            Ok(Ok(metadata)) => metadata.museum_address,
+            _ => return,
+        };
+        
+        let museum_key = (LISTED_MUSEUM_KEY, museum);
+        if !env.storage().persistent().has(&museum_key) {
+            env.storage().persistent().set(&museum_key, &true);
+            Self::increment_counter(env, &LISTED_MUSEUM_COUNT_KEY);
+        }
+    }
+
+    /// Registra una colección (contrato NFT) operada en el marketplace
+    fn track_collection(env: &Env, nft_contract: &Address) {
+        let mut collections: Vec<Address> = env.storage().instance().get(&COLLECTIONS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if !collections.contains(nft_contract) {
+            collections.push_back(nft_contract.clone());
+            env.storage().instance().set(&COLLECTIONS_KEY, &collections);
+        }
+    }
+
+    /// Paga al keeper el incentivo de liquidación desde la tesorería del token de la subasta,
+    /// limitado a su saldo; si ese saldo está vacío la liquidación sigue sin incentivo
+    fn pay_keeper_reward(env: &Env, keeper: &Address, auction: &Auction) {
+        let payment_token = match auction.payment_token.clone() {
+            Some(payment_token) => payment_token,
+            None => return,
+        };
+        
+        // El vendedor y el ganador ya tienen incentivo para liquidar
+        if *keeper == auction.seller || (auction.current_bid > 0 && *keeper == auction.highest_bidder) {
+            return;
+        }
+        
+        let treasury = Self::get_treasury_balance(env.clone(), payment_token.clone());
+        if treasury <= 0 {
+            return;
+        }
+        
+        let reward = Self::get_keeper_reward(env.clone()).min(treasury);
+        if reward <= 0 {
+            return;
+        }
+        
+        env.storage().persistent().set(&(TREASURY_KEY, payment_token.clone()), &(treasury - reward));
+        token::Client::new(env, &payment_token).transfer(&env.current_contract_address(), keeper, &reward);
+    }
+
+    /// Calcula el precio del siguiente token de una curva activa
+    fn curve_price(curve: &BondingCurveListing) -> i128 {
+        if !curve.active {
+            panic!("Curve not active");
+        }
+        curve.base_price + curve.sold_count as i128 * curve.price_increment
+    }
+
+    /// Calcula la puja mínima aceptada por una subasta
+    fn min_bid(env: &Env, auction: &Auction) -> i128 {
+        if auction.current_bid == 0 {
+            auction.starting_price
+        } else {
+            auction.current_bid + Self::get_min_bid_increment(env.clone())
+        }
+    }
+
+    /// Verifica que la puja sube en múltiplos del tick de pujas respecto de la puja
+    /// actual (o del precio inicial si aún no hay pujas)
+    fn validate_bid_tick(env: &Env, auction: &Auction, amount: i128) {
+        let tick = Self::get_bid_tick(env.clone());
+        let reference = if auction.current_bid == 0 {
+            auction.starting_price
+        } else {
+            auction.current_bid
+        };
+        if tick > 0 && (amount - reference) % tick != 0 {
+            panic!("Bid must be a multiple of the bid tick");
+        }
+    }
+
+    /// Crea un listado activo tras validar precio, disputas y duplicados
+    fn open_listing(
+        env: &Env,
+        seller: &Address,
+        nft_contract: &Address,
+        token_id: u32,
+        price: i128,
+        category: Option<Symbol>,
+    ) {
+        // Verificar que el precio es positivo
+        if price <= 0 {
+            panic!("Price must be positive");
+        }
+        Self::validate_price_tick(env, price);
+        
+        // Verificar que el NFT no tiene una disputa de propiedad activa
+        Self::ensure_not_disputed(env, nft_contract, token_id);
+        
+        // Verificar que el vendedor es el propietario y aprobó al marketplace
+        Self::ensure_deliverable(env, nft_contract, token_id, seller);
+        
+        // Verificar que el NFT no está en período de espera tras su última adquisición
+        let nft = CulturalNFTClient::new(env, nft_contract);
+        if env.ledger().timestamp() < nft.get_transfer_unlock_time(&token_id) {
+            panic!("NFT in transfer cooldown");
+        }
+        
+        // Verificar que el NFT no está ya listado (se permite relistar tras una venta o cancelación)
+        let listing_key = (LISTING_KEY, nft_contract.clone(), token_id);
+        let existing: Option<Listing> = env.storage().persistent().get(&listing_key);
+        if existing.map(|listing| listing.active).unwrap_or(false) {
+            panic!("NFT already listed");
+        }
+        
+        // Crear listado
+        let listing = Listing {
+            seller: seller.clone(),
+            nft_contract: nft_contract.clone(),
+            token_id,
+            price,
+            active: true,
+            created_at: env.ledger().timestamp(),
+            category: category.clone(),
+        };
+        
+        // Guardar listado
+        env.storage().persistent().set(&listing_key, &listing);
+        
+        // Incrementar contador
+        let mut count: u32 = env.storage().instance().get(&LISTING_COUNT_KEY).unwrap_or(0);
+        count += 1;
+        env.storage().instance().set(&LISTING_COUNT_KEY, &count);
+        
+        // Actualizar estadísticas
+        Self::increment_counter(env, &ACTIVE_LISTINGS_KEY);
+        Self::adjust_seller_counter(env, &SELLER_ACTIVE_LISTINGS_KEY, seller, true);
+        Self::track_listed_museum(env, nft_contract, token_id);
+        Self::track_collection_listing(env, nft_contract, token_id);
+        Self::track_active_listing(env, nft_contract, token_id);
+        Self::track_seller_listing(env, seller, nft_contract, token_id);
+        if let Some(category) = category {
+            Self::track_category_listing(env, category, nft_contract, token_id);
+        }
+        Self::log_market_event(env, nft_contract, token_id, MarketEventKind::Listed, seller, price);
+    }
+
+    /// Agrega el token al índice de listados de su colección
+    fn track_collection_listing(env: &Env, nft_contract: &Address, token_id: u32) {
+        Self::track_collection(env, nft_contract);
+        
+        let index_key = (COLLECTION_LISTINGS_KEY, nft_contract.clone());
+        let mut listed: Vec<u32> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if !listed.contains(token_id) {
+            listed.push_back(token_id);
+            env.storage().persistent().set(&index_key, &listed);
+        }
+    }
+
+    /// Agrega el token al índice de listados activos de su colección, usado para el precio mínimo
+    fn track_active_listing(env: &Env, nft_contract: &Address, token_id: u32) {
+        let index_key = (COLLECTION_ACTIVE_KEY, nft_contract.clone());
+        let mut listed: Vec<u32> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        listed.push_back(token_id);
+        env.storage().persistent().set(&index_key, &listed);
+    }
+
+    /// Quita del índice de listados activos de su colección un listado que deja de estar activo
+    fn untrack_active_listing(env: &Env, nft_contract: &Address, token_id: u32) {
+        let index_key = (COLLECTION_ACTIVE_KEY, nft_contract.clone());
+        let mut listed: Vec<u32> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if let Some(index) = listed.first_index_of(token_id) {
+            listed.remove(index);
+            env.storage().persistent().set(&index_key, &listed);
+        }
+    }
+
+    /// Agrega la subasta al índice de su colección
+    fn track_collection_auction(env: &Env, nft_contract: &Address, token_id: u32) {
+        Self::track_collection(env, nft_contract);
+        
+        let index_key = (COLLECTION_AUCTIONS_KEY, nft_contract.clone());
+        let mut auctioned: Vec<u32> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if !auctioned.contains(token_id) {
+            auctioned.push_back(token_id);
+            env.storage().persistent().set(&index_key, &auctioned);
+        }
+    }
+
+    /// Agrega el listado al índice del vendedor (las entradas inactivas se depuran en
+    /// `bulk_update_prices`)
+    fn track_seller_listing(env: &Env, seller: &Address, nft_contract: &Address, token_id: u32) {
+        let index_key = (SELLER_LISTINGS_KEY, seller.clone());
+        let mut listed: Vec<(Address, u32)> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        let entry = (nft_contract.clone(), token_id);
+        if !listed.contains(&entry) {
+            listed.push_back(entry);
+            env.storage().persistent().set(&index_key, &listed);
+        }
+    }
+
+    /// Agrega el listado al índice de su categoría
+    fn track_category_listing(env: &Env, category: Symbol, nft_contract: &Address, token_id: u32) {
+        let index_key = (CATEGORY_LISTINGS_KEY, category);
+        let mut listed: Vec<(Address, u32)> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        listed.push_back((nft_contract.clone(), token_id));
+        env.storage().persistent().set(&index_key, &listed);
+    }
+
+    /// Quita del índice de su categoría un listado que deja de estar activo
+    fn untrack_category_listing(env: &Env, listing: &Listing) {
+        let category = match &listing.category {
+            Some(category) => category.clone(),
+            None => return,
+        };
+        
+        let index_key = (CATEGORY_LISTINGS_KEY, category);
+        let mut listed: Vec<(Address, u32)> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if let Some(index) = listed.first_index_of(&(listing.nft_contract.clone(), listing.token_id)) {
+            listed.remove(index);
+            env.storage().persistent().set(&index_key, &listed);
+        }
+    }
+
+    /// Precio de compra de un listado con la rebaja vigente del vendedor aplicada
+    fn sale_price(env: &Env, listing: &Listing) -> i128 {
+        match Self::get_sale(env.clone(), listing.seller.clone()) {
+            Some(sale) => listing.price - listing.price * sale.discount_bps as i128 / BPS_DENOMINATOR,
+            None => listing.price,
+        }
+    }
+
+    /// Rechaza la reventa del mismo token entre las mismas dos partes dentro de la ventana configurada
+    fn ensure_not_wash_trade(env: &Env, nft_contract: &Address, token_id: u32, seller: &Address, buyer: &Address) {
+        let window = Self::get_wash_trade_window(env.clone());
+        if window == 0 {
+            return;
+        }
+        
+        let now = env.ledger().timestamp();
+        for (from, to) in [(seller, buyer), (buyer, seller)] {
+            let trade_key = (LAST_TRADE_KEY, nft_contract.clone(), token_id, from.clone(), to.clone());
+            if let Some(last_trade) = env.storage().persistent().get::<_, u64>(&trade_key) {
+                if now < last_trade + window {
+                    panic!("Wash trading cooldown active for these parties");
+                }
+            }
+        }
+    }
+
+    /// Acumula las estadísticas de una venta completada
+    fn record_sale(
+        env: &Env,
+        nft_contract: &Address,
+        token_id: u32,
+        seller: &Address,
+        buyer: &Address,
+        price: i128,
+    ) {
+        // Registrar la operación entre estas dos partes (anti wash trading)
+        let trade_key = (LAST_TRADE_KEY, nft_contract.clone(), token_id, seller.clone(), buyer.clone());
+        env.storage().persistent().set(&trade_key, &env.ledger().timestamp());
+        
+        let volume: i128 = env.storage().instance().get(&VOLUME_KEY).unwrap_or(0);
+        env.storage().instance().set(&VOLUME_KEY, &(volume + price));
+        
+        Self::increment_counter(env, &SALES_COUNT_KEY);
+        
+        let sale_count_key = (TOKEN_SALES_KEY, nft_contract.clone(), token_id);
+        let sale_count: u32 = env.storage().persistent().get(&sale_count_key).unwrap_or(0);
+        env.storage().persistent().set(&sale_count_key, &(sale_count + 1));
+        
+        let highest: i128 = env.storage().instance().get(&HIGHEST_SALE_KEY).unwrap_or(0);
+        if price > highest {
+            env.storage().instance().set(&HIGHEST_SALE_KEY, &price);
+        }
+        
+        let collection_key = (COLLECTION_VOLUME_KEY, nft_contract.clone());
+        let collection_volume: i128 = env.storage().persistent().get(&collection_key).unwrap_or(0);
+        env.storage().persistent().set(&collection_key, &(collection_volume + price));
+        
+        Self::record_museum_volume(env, nft_contract, token_id, price);
+        Self::log_market_event(env, nft_contract, token_id, MarketEventKind::Sold, buyer, price);
+        
+        let trade = SaleRecord {
+            nft_contract: nft_contract.clone(),
+            token_id,
+            seller: seller.clone(),
+            buyer: buyer.clone(),
+            price,
+            timestamp: env.ledger().timestamp(),
+        };
+        Self::record_user_trade(env, seller, &trade);
+        Self::record_user_trade(env, buyer, &trade);
+    }
+
+    /// Lee el historial de compras y ventas de un usuario
+    fn user_trades(env: &Env, user: &Address) -> Vec<SaleRecord> {
+        env.storage().persistent().get(&(USER_TRADES_KEY, user.clone())).unwrap_or_else(|| {
+            Vec::new(env)
+        })
+    }
+
+    /// Agrega una venta al historial del usuario, descartando la más antigua si se supera el máximo
+    fn record_user_trade(env: &Env, user: &Address, trade: &SaleRecord) {
+        let mut trades = Self::user_trades(env, user);
+        trades.push_back(trade.clone());
+        if trades.len() > MAX_USER_TRADES {
+            trades.pop_front();
+        }
+        env.storage().persistent().set(&(USER_TRADES_KEY, user.clone()), &trades);
+    }
+
+    /// Suma la venta al volumen del museo que minteó el token y la reporta al registro
+    ///
+    /// Es best-effort: si el contrato NFT no expone metadatos culturales la venta no se
+    /// atribuye a ningún museo.
+    fn record_museum_volume(env: &Env, nft_contract: &Address, token_id: u32, price: i128) {
+        let nft = CulturalNFTClient::new(env, nft_contract);
+        let museum = match nft.try_get_cultural_metadata(&token_id) {
+            Ok(Ok(metadata)) => metadata.museum_address,
+            _ => return,
+        };
+        
+        let volume_key = (MUSEUM_VOLUME_KEY, museum.clone());
+        let volume: i128 = env.storage().persistent().get(&volume_key).unwrap_or(0);
+        env.storage().persistent().set(&volume_key, &(volume + price));
+        
+        let sales_key = (MUSEUM_SALES_KEY, museum.clone());
+        let sales: u32 = env.storage().persistent().get(&sales_key).unwrap_or(0);
+        env.storage().persistent().set(&sales_key, &(sales + 1));
+        
+        // Reportar la venta a las estadísticas del museo en el registro, si está configurado
+        if let Some(museum_registry) = Self::get_museum_registry(env.clone()) {
+            let _ = MuseumRegistryClient::new(env, &museum_registry).try_record_museum_activity(
+                &env.current_contract_address(),
+                &museum,
+                &symbol_short!("sale"),
+                &price,
+            );
+        }
+    }
+
+    /// Agrega un evento al historial de mercado del token, descartando el más antiguo
+    /// si se supera el máximo
+    fn log_market_event(
+        env: &Env,
+        nft_contract: &Address,
+        token_id: u32,
+        kind: MarketEventKind,
+        actor: &Address,
+        amount: i128,
+    ) {
+        let history_key = (MARKET_HISTORY_KEY, nft_contract.clone(), token_id);
+        let mut history: Vec<MarketEvent> = env.storage().persistent().get(&history_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        
+        history.push_back(MarketEvent {
+            kind,
+            actor: actor.clone(),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+        if history.len() > MAX_MARKET_HISTORY {
+            history.pop_front();
+        }
+        
+        env.storage().persistent().set(&history_key, &history);
+    }
+
+    /// Acumula el volumen negociado en un token de pago
+    fn record_token_volume(env: &Env, payment_token: &Address, price: i128) {
+        let mut tokens: Vec<Address> = env.storage().instance().get(&VOLUME_TOKENS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if !tokens.contains(payment_token) {
+            tokens.push_back(payment_token.clone());
+            env.storage().instance().set(&VOLUME_TOKENS_KEY, &tokens);
+        }
+        
+        let volume = Self::get_token_volume(env.clone(), payment_token.clone());
+        env.storage().persistent().set(&(TOKEN_VOLUME_KEY, payment_token.clone()), &(volume + price));
+    }
+
+    /// Calcula el precio mínimo de las colecciones con mayor volumen
+    ///
+    /// Solo recorre el índice de listados activos de cada colección, acotado a `MAX_FLOOR_SCAN`.
+    fn top_collection_floors(env: &Env) -> Vec<(Address, i128)> {
+        let collections: Vec<Address> = env.storage().instance().get(&COLLECTIONS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        
+        // Ordenar por volumen (inserción en una lista acotada)
+        let mut top: Vec<(Address, i128)> = Vec::new(env);
+        for nft_contract in collections.iter() {
+            let volume: i128 = env.storage().persistent().get(&(COLLECTION_VOLUME_KEY, nft_contract.clone())).unwrap_or(0);
+            
+            let mut position = top.len();
+            for i in 0..top.len() {
+                if volume > top.get(i).unwrap().1 {
+                    position = i;
+                    break;
+                }
+            }
+            
+            if position < TOP_COLLECTIONS {
+                top.insert(position, (nft_contract, volume));
+                if top.len() > TOP_COLLECTIONS {
+                    top.pop_back();
+                }
+            }
+        }
+        
+        // Calcular el precio mínimo entre los listados activos
+        let mut result = Vec::new(env);
+        for (nft_contract, _) in top.iter() {
+            let listed: Vec<u32> = env.storage().persistent().get(&(COLLECTION_ACTIVE_KEY, nft_contract.clone())).unwrap_or_else(|| {
+                Vec::new(env)
+            });
+            
+            let mut floor_price: i128 = 0;
+            for token_id in listed.iter().take(MAX_FLOOR_SCAN as usize) {
+                let listing_key = (LISTING_KEY, nft_contract.clone(), token_id);
+                if let Some(listing) = env.storage().persistent().get::<_, Listing>(&listing_key) {
+                    if listing.active && (floor_price == 0 || listing.price < floor_price) {
+                        floor_price = listing.price;
+                    }
+                }
+            }
+            result.push_back((nft_contract, floor_price));
+        }
+        
+        result
+    }
+
+    /// Verifica que el precio es múltiplo del tick configurado
+    fn validate_price_tick(env: &Env, price: i128) {
+        let tick = Self::get_price_tick(env.clone());
+        if tick > 0 && price % tick != 0 {
+            panic!("Price must be a multiple of the price tick");
+        }
+    }
+
+    /// Rechaza NFTs con una disputa de propiedad activa en el contrato de origen
+    fn ensure_not_disputed(env: &Env, nft_contract: &Address, token_id: u32) {
+        let nft = CulturalNFTClient::new(env, nft_contract);
+        if nft.is_disputed(&token_id) {
+            panic!("NFT ownership is disputed");
+        }
+    }
+
+    /// Valida un listado para la compra y lo marca como inactivo
+    /// Verifica que el vendedor sigue siendo propietario del NFT y que el marketplace tiene
+    /// una aprobación vigente (del token o como operador) para entregarlo
+    fn ensure_deliverable(env: &Env, nft_contract: &Address, token_id: u32, seller: &Address) {
+        let nft = CulturalNFTClient::new(env, nft_contract);
+        if nft.owner_of(&token_id) != *seller {
+            panic!("Seller does not own NFT");
+        }
+        
+        let marketplace = env.current_contract_address();
+        if nft.get_approved(&token_id) != Some(marketplace.clone()) && !nft.is_approved_for_all(seller, &marketplace) {
+            panic!("Marketplace not approved");
+        }
+    }
+
+    fn take_listing(env: &Env, buyer: &Address, nft_contract: &Address, token_id: u32) -> Listing {
+        // Obtener listado
+        let listing_key = (LISTING_KEY, nft_contract.clone(), token_id);
+        let mut listing: Listing = env.storage().persistent().get(&listing_key).unwrap_or_else(|| {
+            panic!("NFT not listed");
+        });
+        
+        // Verificar que el listado está activo
+        if !listing.active {
+            panic!("Listing not active");
+        }
+        
+        // Verificar que el comprador no es el vendedor
+        if listing.seller == *buyer {
+            panic!("Cannot buy your own NFT");
+        }
+        
+        Self::ensure_not_wash_trade(env, nft_contract, token_id, &listing.seller, buyer);
+        
+        // Marcar listado como inactivo
+        listing.active = false;
+        env.storage().persistent().set(&listing_key, &listing);
+        Self::decrement_counter(env, &ACTIVE_LISTINGS_KEY);
+        Self::adjust_seller_counter(env, &SELLER_ACTIVE_LISTINGS_KEY, &listing.seller, false);
+        Self::untrack_category_listing(env, &listing);
+        Self::untrack_active_listing(env, nft_contract, token_id);
+        
+        listing
+    }
+
+    /// Obtiene el token de pago configurado; sin él no se puede cobrar ninguna venta
+    fn require_payment_token(env: &Env) -> Address {
+        Self::get_payment_token(env.clone()).unwrap_or_else(|| {
+            panic!("Payment token not configured");
+        })
+    }
+
+    /// Cobra el precio al pagador: la comisión queda en el marketplace, las royalties
+    /// van a los creadores y el resto al vendedor
+    ///
+    /// El pagador puede ser el propio marketplace cuando liquida fondos depositados en subastas.
+    fn settle_payment(
+        env: &Env,
+        payment_token: &Address,
+        payer: &Address,
+        seller: &Address,
+        nft_contract: &Address,
+        token_id: u32,
+        price: i128,
+    ) {
+        let fee = Self::compute_fee(env.clone(), price);
+        
+        Self::record_token_volume(env, payment_token, price);
+        
+        let client = token::Client::new(env, payment_token);
+        if fee > 0 {
+            if *payer != env.current_contract_address() {
+                client.transfer(payer, &env.current_contract_address(), &fee);
+            }
+            
+            // La tesorería se lleva por token: las comisiones de tokens distintos no se suman
+            let treasury = Self::get_treasury_balance(env.clone(), payment_token.clone());
+            env.storage().persistent().set(&(TREASURY_KEY, payment_token.clone()), &(treasury + fee));
+        }
+        
+        // Distribuir royalties entre los creadores; el porcentaje puede escalar con las reventas
+        let mut royalties_paid: i128 = 0;
+        let nft = CulturalNFTClient::new(env, nft_contract);
+        let sale_count = Self::get_sale_count(env.clone(), nft_contract.clone(), token_id);
+        let royalties = nft.royalty_info_for_resale(&token_id, &price, &sale_count);
+        for (_, amount) in royalties.iter() {
+            royalties_paid += amount;
+        }
+        if fee + royalties_paid > price {
+            panic!("Royalties and fee exceed price");
+        }
+        
+        for (recipient, amount) in royalties.iter() {
+            if amount > 0 {
+                client.transfer(payer, &recipient, &amount);
+            }
+        }
+        
+        client.transfer(payer, seller, &(price - fee - royalties_paid));
+    }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,1160 @@
+extern crate std;
+
+use crate::{AuctionStatus, MarketEventKind, Marketplace, MarketplaceClient, SaleRecord, RATE_PRECISION};
+use cultural_nft::{ArtifactLocation, CulturalMetadata, CulturalNFT, CulturalNFTClient, RoyaltyShare};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String, Vec};
+
+// Contrato de notificaciones simulado: guarda cada aviso de puja recibido
+#[contract]
+pub struct MockNotification;
+
+#[contractimpl]
+impl MockNotification {
+    pub fn on_bid_received(env: Env, seller: Address, nft_contract: Address, token_id: u32, bidder: Address, amount: i128) {
+        let mut bids: Vec<(Address, Address, u32, Address, i128)> = env.storage().instance().get(&symbol_short!("BIDS")).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        bids.push_back((seller, nft_contract, token_id, bidder, amount));
+        env.storage().instance().set(&symbol_short!("BIDS"), &bids);
+    }
+
+    pub fn bids(env: Env) -> Vec<(Address, Address, u32, Address, i128)> {
+        env.storage().instance().get(&symbol_short!("BIDS")).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+}
+
+fn sample_metadata(env: &Env, museum: &Address) -> CulturalMetadata {
+    CulturalMetadata {
+        title: String::from_str(env, "Huaco retrato"),
+        artist: String::from_str(env, "Desconocido"),
+        period: String::from_str(env, "Moche"),
+        culture: String::from_str(env, "Moche"),
+        material: String::from_str(env, "Cerámica"),
+        dimensions: String::from_str(env, "25x18 cm"),
+        condition: String::from_str(env, "Bueno"),
+        significance: String::from_str(env, "Retrato de un gobernante"),
+        museum_address: museum.clone(),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    }
+}
+
+// Despliega un CulturalNFT con `count` tokens del vendedor y un marketplace (comisión 2.5%)
+// que cobra en un token de pago recién creado
+fn setup(env: &Env, count: u32) -> (CulturalNFTClient<'_>, MarketplaceClient<'_>, Address, Address) {
+    let admin = Address::generate(env);
+    let seller = Address::generate(env);
+    
+    let nft_id = env.register_contract(None, CulturalNFT);
+    let nft = CulturalNFTClient::new(env, &nft_id);
+    nft.initialize(
+        &admin,
+        &String::from_str(env, "PermaMuseum"),
+        &String::from_str(env, "PMUS"),
+        &Address::generate(env),
+        &None,
+    );
+    let metadata = sample_metadata(env, &Address::generate(env));
+    for token_id in 1..=count {
+        nft.mint_cultural_nft(&seller, &token_id, &metadata, &Vec::new(env));
+    }
+    
+    let market_id = env.register_contract(None, Marketplace);
+    let market = MarketplaceClient::new(env, &market_id);
+    market.initialize(&admin, &250, &7);
+    market.set_payment_token(&env.register_stellar_asset_contract_v2(admin.clone()).address());
+    
+    (nft, market, admin, seller)
+}
+
+// Acredita `amount` del token de pago del marketplace a `who`
+fn fund(env: &Env, market: &MarketplaceClient, who: &Address, amount: i128) {
+    StellarAssetClient::new(env, &market.get_payment_token().unwrap()).mint(who, &amount);
+}
+
+#[test]
+fn test_bids_follow_bid_tick() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 1);
+    let bidder = Address::generate(&env);
+    fund(&env, &market, &bidder, 2_400);
+    market.set_bid_tick(&100);
+    
+    nft.approve(&seller, &market.address, &1);
+    market.create_auction(&seller, &nft.address, &1, &1_000, &86400, &0);
+    
+    // La primera puja se mide desde el precio inicial
+    assert!(market.try_bid(&bidder, &nft.address, &1, &1_050).is_err());
+    market.bid(&bidder, &nft.address, &1, &1_100);
+    
+    // Las siguientes, desde la puja actual
+    assert!(market.try_bid(&bidder, &nft.address, &1, &1_250).is_err());
+    market.bid(&bidder, &nft.address, &1, &1_300);
+}
+
+#[test]
+fn test_bulk_price_update_and_sale() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 3);
+    let buyer = Address::generate(&env);
+    fund(&env, &market, &buyer, 450);
+    nft.approve_for_all(&seller, &market.address);
+    
+    for token_id in 1..=3u32 {
+        market.list_nft(&seller, &nft.address, &token_id, &(token_id as i128 * 1_000), &None);
+    }
+    market.cancel_listing(&seller, &nft.address, &3);
+    
+    // Rebaja del 10% en todos los listados activos
+    assert_eq!(market.bulk_update_prices(&seller, &9_000), 2);
+    assert_eq!(market.get_listing(&nft.address, &1).price, 900);
+    assert_eq!(market.get_listing(&nft.address, &2).price, 1_800);
+    assert_eq!(market.get_listing(&nft.address, &3).price, 3_000);
+    
+    // Multiplicadores fuera de rango
+    assert!(market.try_bulk_update_prices(&seller, &0).is_err());
+    assert!(market.try_bulk_update_prices(&seller, &20_001).is_err());
+    
+    // Una rebaja temporal se aplica al comprar sin cambiar el precio listado
+    market.set_sale(&seller, &5_000, &3600);
+    assert_eq!(market.get_sale(&seller).unwrap().discount_bps, 5_000);
+    market.buy_nft(&buyer, &nft.address, &1);
+    let history = market.get_token_market_history(&nft.address, &1, &0, &10);
+    assert_eq!(history.get(history.len() - 1).unwrap().amount, 450);
+    assert_eq!(market.get_listing(&nft.address, &2).price, 1_800);
+}
+
+#[test]
+fn test_sale_volume_by_museum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 0);
+    let buyer = Address::generate(&env);
+    fund(&env, &market, &buyer, 4_200);
+    let museum = Address::generate(&env);
+    let other_museum = Address::generate(&env);
+    
+    nft.mint_cultural_nft(&seller, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    nft.mint_cultural_nft(&seller, &2, &sample_metadata(&env, &museum), &Vec::new(&env));
+    nft.mint_cultural_nft(&seller, &3, &sample_metadata(&env, &other_museum), &Vec::new(&env));
+    nft.approve_for_all(&seller, &market.address);
+    assert_eq!(market.get_sale_volume_by_museum(&museum), (0, 0));
+    
+    for (token_id, price) in [(1u32, 1_000i128), (2, 2_500), (3, 700)] {
+        market.list_nft(&seller, &nft.address, &token_id, &price, &None);
+        market.buy_nft(&buyer, &nft.address, &token_id);
+    }
+    
+    assert_eq!(market.get_sale_volume_by_museum(&museum), (3_500, 2));
+    assert_eq!(market.get_sale_volume_by_museum(&other_museum), (700, 1));
+}
+
+#[test]
+fn test_royalty_escalates_with_each_resale() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 0);
+    let museum = Address::generate(&env);
+    nft.mint_cultural_nft(&seller, &1, &sample_metadata(&env, &museum), &Vec::new(&env));
+    
+    // 2% base, +0.1% por reventa, tope 2.5%
+    let mut shares = Vec::new(&env);
+    shares.push_back(RoyaltyShare { recipient: museum.clone(), bps: 200 });
+    nft.set_royalty_shares(&1, &shares);
+    nft.set_royalty_escalation(&1, &10, &250);
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    market.set_payment_token(&asset.address());
+    
+    // Venta inicial + 5 reventas + una más ya con el tope alcanzado
+    let price = 100_000i128;
+    let mut expected_royalty = 2_000i128;
+    let mut holder = seller.clone();
+    for sale in 0..7u32 {
+        let buyer = Address::generate(&env);
+        StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &price);
+        
+        let museum_before = token.balance(&museum);
+        nft.approve_for_all(&holder, &market.address);
+        market.list_nft(&holder, &nft.address, &1, &price, &None);
+        market.buy_nft(&buyer, &nft.address, &1);
+        assert_eq!(nft.owner_of(&1), buyer);
+        holder = buyer;
+        
+        assert_eq!(token.balance(&museum) - museum_before, expected_royalty);
+        assert_eq!(market.get_sale_count(&nft.address, &1), sale + 1);
+        if sale < 5 {
+            expected_royalty += 100;
+        }
+    }
+}
+
+#[test]
+fn test_recount_repairs_drifted_counters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 3);
+    nft.approve_for_all(&seller, &market.address);
+    
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    market.list_nft(&seller, &nft.address, &2, &1_000, &None);
+    market.cancel_listing(&seller, &nft.address, &2);
+    nft.approve(&seller, &market.address, &3);
+    market.create_auction(&seller, &nft.address, &3, &500, &3600, &0);
+    
+    let check = market.check_counters();
+    assert!(check.consistent);
+    assert_eq!((check.indexed_listings, check.indexed_active_listings), (2, 1));
+    assert_eq!((check.indexed_auctions, check.indexed_active_auctions), (1, 1));
+    
+    // Desincronizar los contadores directamente en el storage del contrato
+    env.as_contract(&market.address, || {
+        env.storage().instance().set(&symbol_short!("ACT_LST"), &7u32);
+        env.storage().instance().set(&symbol_short!("AUCT_CNT"), &0u32);
+    });
+    let check = market.check_counters();
+    assert!(!check.consistent);
+    assert_eq!(check.active_listings, 7);
+    
+    let repaired = market.recount_counters();
+    assert!(repaired.consistent);
+    assert_eq!(repaired.active_listings, 1);
+    assert_eq!(repaired.total_auctions, 1);
+    assert_eq!(market.get_total_listings(), 2);
+    assert_eq!(market.get_dashboard_stats().active_listings, 1);
+}
+
+#[test]
+fn test_watchlist_with_current_prices() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 3);
+    let collector = Address::generate(&env);
+    nft.approve_for_all(&seller, &market.address);
+    
+    for token_id in 1..=3u32 {
+        market.add_to_watchlist(&collector, &nft.address, &token_id);
+    }
+    assert!(market.try_add_to_watchlist(&collector, &nft.address, &1).is_err());
+    
+    market.list_nft(&seller, &nft.address, &1, &1_500, &None);
+    market.list_nft(&seller, &nft.address, &3, &900, &None);
+    
+    let watchlist = market.get_watchlist_with_prices(&collector);
+    assert_eq!(watchlist.len(), 3);
+    assert_eq!(watchlist.get(0).unwrap(), (nft.address.clone(), 1, Some(1_500)));
+    assert_eq!(watchlist.get(1).unwrap(), (nft.address.clone(), 2, None));
+    assert_eq!(watchlist.get(2).unwrap(), (nft.address.clone(), 3, Some(900)));
+    
+    market.remove_from_watchlist(&collector, &nft.address, &2);
+    assert_eq!(market.get_watchlist(&collector).len(), 2);
+    assert!(market.try_remove_from_watchlist(&collector, &nft.address, &2).is_err());
+}
+
+#[test]
+fn test_scheduled_auction_rejects_early_bids() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 1);
+    let bidder = Address::generate(&env);
+    fund(&env, &market, &bidder, 500);
+    
+    // Subasta anunciada para dentro de una hora, con un día de duración
+    env.ledger().with_mut(|ledger| ledger.timestamp = 1_000);
+    nft.approve(&seller, &market.address, &1);
+    market.create_auction(&seller, &nft.address, &1, &500, &86400, &3600);
+    let auction = market.get_auction(&nft.address, &1);
+    assert_eq!(auction.start_time, 4_600);
+    assert_eq!(auction.end_time, 4_600 + 86400);
+    assert!(!market.has_started(&nft.address, &1));
+    assert_eq!(market.get_auction_status(&nft.address, &1), AuctionStatus::Scheduled(4_600));
+    
+    // Pujar antes de la apertura falla
+    assert!(market.try_bid(&bidder, &nft.address, &1, &500).is_err());
+    
+    // Al llegar la apertura se aceptan pujas
+    env.ledger().with_mut(|ledger| ledger.timestamp = 4_600);
+    assert!(market.has_started(&nft.address, &1));
+    market.bid(&bidder, &nft.address, &1, &500);
+    assert_eq!(market.get_highest_bid(&nft.address, &1), 500);
+}
+
+#[test]
+fn test_listings_by_category() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 4);
+    let buyer = Address::generate(&env);
+    fund(&env, &market, &buyer, 1_000);
+    nft.approve_for_all(&seller, &market.address);
+    
+    let textiles = symbol_short!("textiles");
+    let ceramics = symbol_short!("ceramics");
+    market.add_category(&textiles);
+    market.add_category(&ceramics);
+    
+    // Solo se aceptan categorías habilitadas por el admin
+    assert!(market.try_list_nft(&seller, &nft.address, &1, &1_000, &Some(symbol_short!("comics"))).is_err());
+    
+    market.list_nft(&seller, &nft.address, &1, &1_000, &Some(textiles.clone()));
+    market.list_nft(&seller, &nft.address, &2, &2_000, &Some(ceramics.clone()));
+    market.list_nft(&seller, &nft.address, &3, &3_000, &Some(textiles.clone()));
+    market.list_nft(&seller, &nft.address, &4, &4_000, &None);
+    
+    let listings = market.get_listings_by_category(&textiles, &0, &10);
+    assert_eq!(listings.len(), 2);
+    assert_eq!(listings.get(0).unwrap().token_id, 1);
+    assert_eq!(listings.get(1).unwrap().token_id, 3);
+    assert_eq!(market.get_listings_by_category(&textiles, &1, &10).get(0).unwrap().token_id, 3);
+    
+    let listings = market.get_listings_by_category(&ceramics, &0, &10);
+    assert_eq!(listings.len(), 1);
+    assert_eq!(listings.get(0).unwrap().category, Some(ceramics.clone()));
+    
+    // Los listados vendidos o cancelados salen del índice
+    market.buy_nft(&buyer, &nft.address, &1);
+    market.cancel_listing(&seller, &nft.address, &2);
+    let listings = market.get_listings_by_category(&textiles, &0, &10);
+    assert_eq!(listings.len(), 1);
+    assert_eq!(listings.get(0).unwrap().token_id, 3);
+    assert_eq!(market.get_listings_by_category(&ceramics, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_trades_in_period_for_tax_reporting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, museum) = setup(&env, 3);
+    let trader = Address::generate(&env);
+    let collector = Address::generate(&env);
+    fund(&env, &market, &trader, 3_500);
+    fund(&env, &market, &collector, 3_300);
+    nft.approve_for_all(&museum, &market.address);
+    nft.approve_for_all(&trader, &market.address);
+    
+    let trade = |timestamp: u64, seller: &Address, buyer: &Address, token_id: u32, price: i128| {
+        env.ledger().with_mut(|ledger| ledger.timestamp = timestamp);
+        market.list_nft(seller, &nft.address, &token_id, &price, &None);
+        market.buy_nft(buyer, &nft.address, &token_id);
+    };
+    
+    // Año 1: compra dos piezas y revende una con ganancia
+    trade(100, &museum, &trader, 1, 1_000);
+    trade(200, &museum, &trader, 2, 2_000);
+    trade(300, &trader, &collector, 1, 1_600);
+    // Año 2: revende la otra con pérdida y compra una tercera
+    trade(1_100, &trader, &collector, 2, 1_700);
+    trade(1_200, &museum, &trader, 3, 500);
+    
+    let year_one = market.get_trades_in_period(&trader, &0, &999);
+    assert_eq!(year_one.len(), 3);
+    assert_eq!(
+        year_one.get(2).unwrap(),
+        SaleRecord {
+            nft_contract: nft.address.clone(),
+            token_id: 1,
+            seller: trader.clone(),
+            buyer: collector.clone(),
+            price: 1_600,
+            timestamp: 300,
+        }
+    );
+    assert_eq!(market.get_trades_in_period(&trader, &1_000, &1_999).len(), 2);
+    assert_eq!(market.get_trades_in_period(&trader, &300, &1_100).len(), 2);
+    assert_eq!(market.get_trades_in_period(&collector, &0, &u64::MAX).len(), 2);
+    
+    // Solo cuentan los tokens comprados y vendidos dentro del período
+    assert_eq!(market.get_total_gains_in_period(&trader, &0, &999), 600);
+    assert_eq!(market.get_total_gains_in_period(&trader, &1_000, &1_999), 0);
+    assert_eq!(market.get_total_gains_in_period(&trader, &0, &u64::MAX), 300);
+    assert_eq!(market.get_total_gains_in_period(&museum, &0, &u64::MAX), 0);
+}
+
+#[test]
+fn test_seller_active_counts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 4);
+    let buyer = Address::generate(&env);
+    fund(&env, &market, &buyer, 1_000);
+    nft.approve_for_all(&seller, &market.address);
+    
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    market.list_nft(&seller, &nft.address, &2, &2_000, &None);
+    nft.approve(&seller, &market.address, &3);
+    market.create_auction(&seller, &nft.address, &3, &500, &86400, &0);
+    nft.approve(&seller, &market.address, &4);
+    market.create_auction(&seller, &nft.address, &4, &500, &86400, &0);
+    assert_eq!(market.get_seller_active_listing_count(&seller), 2);
+    assert_eq!(market.get_seller_active_auction_count(&seller), 2);
+    
+    // Vender un listado y cancelar una subasta reducen los contadores del vendedor
+    market.buy_nft(&buyer, &nft.address, &1);
+    market.cancel_auction(&seller, &nft.address, &4);
+    assert_eq!(market.get_seller_active_listing_count(&seller), 1);
+    assert_eq!(market.get_seller_active_auction_count(&seller), 1);
+    
+    market.cancel_listing(&seller, &nft.address, &2);
+    assert_eq!(market.get_seller_active_listing_count(&seller), 0);
+    assert_eq!(market.get_seller_active_listing_count(&buyer), 0);
+}
+
+#[test]
+fn test_find_listing_and_auction_without_panicking() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 2);
+    nft.approve_for_all(&seller, &market.address);
+    
+    assert!(market.find_listing(&nft.address, &1).is_none());
+    assert!(market.find_auction(&nft.address, &2).is_none());
+    
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    nft.approve(&seller, &market.address, &2);
+    market.create_auction(&seller, &nft.address, &2, &500, &86400, &0);
+    
+    assert_eq!(market.find_listing(&nft.address, &1).unwrap().price, 1_000);
+    assert_eq!(market.find_auction(&nft.address, &2).unwrap().starting_price, 500);
+    assert!(market.find_listing(&nft.address, &2).is_none());
+    assert!(market.find_auction(&nft.address, &1).is_none());
+    
+    // Las versiones que fallan se conservan
+    assert!(market.try_get_listing(&nft.address, &2).is_err());
+}
+
+#[test]
+fn test_get_listings_for_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 3);
+    nft.approve_for_all(&seller, &market.address);
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    market.list_nft(&seller, &nft.address, &3, &3_000, &None);
+    
+    // Resultados en el orden pedido, con None para los tokens sin listar
+    let listings = market.get_listings_for_tokens(&nft.address, &Vec::from_array(&env, [3u32, 2, 1, 99]));
+    assert_eq!(listings.len(), 4);
+    assert_eq!(listings.get(0).unwrap().unwrap().price, 3_000);
+    assert!(listings.get(1).unwrap().is_none());
+    assert_eq!(listings.get(2).unwrap().unwrap().price, 1_000);
+    assert!(listings.get(3).unwrap().is_none());
+    
+    assert_eq!(market.get_listings_for_tokens(&nft.address, &Vec::new(&env)).len(), 0);
+    
+    // La entrada está acotada
+    let mut too_many = Vec::new(&env);
+    for token_id in 0..51u32 {
+        too_many.push_back(token_id);
+    }
+    assert!(market.try_get_listings_for_tokens(&nft.address, &too_many).is_err());
+}
+
+#[test]
+fn test_listing_requires_ownership_and_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 2);
+    let thief = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &5_000);
+    market.set_payment_token(&asset.address());
+    
+    // Nadie puede listar un token ajeno, ni el dueño sin aprobar al marketplace
+    nft.approve_for_all(&thief, &market.address);
+    assert!(market.try_list_nft(&thief, &nft.address, &1, &1_000, &None).is_err());
+    assert!(market.try_list_nft(&seller, &nft.address, &1, &1_000, &None).is_err());
+    
+    nft.approve(&seller, &market.address, &1);
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    
+    // La compra cobra y entrega el token en la misma llamada
+    market.buy_nft(&buyer, &nft.address, &1);
+    assert_eq!(nft.owner_of(&1), buyer);
+    assert_eq!(token.balance(&buyer), 4_000);
+    assert_eq!(token.balance(&seller), 975);
+    
+    // Si el vendedor mueve el token después de listarlo, la compra falla sin cobrar
+    nft.approve(&seller, &market.address, &2);
+    market.list_nft(&seller, &nft.address, &2, &1_000, &None);
+    nft.transfer(&seller, &thief, &2);
+    assert!(market.try_buy_nft(&buyer, &nft.address, &2).is_err());
+    assert_eq!(token.balance(&buyer), 4_000);
+    assert_eq!(nft.owner_of(&2), thief);
+}
+
+#[test]
+fn test_three_creator_royalty_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 1);
+    let buyer = Address::generate(&env);
+    let creators = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &100_000);
+    market.set_payment_token(&asset.address());
+    
+    // 5% + 3% + 2% entre los tres creadores
+    let mut shares = Vec::new(&env);
+    for (creator, bps) in creators.iter().zip([500u32, 300, 200]) {
+        shares.push_back(RoyaltyShare { recipient: creator.clone(), bps });
+    }
+    nft.set_royalty_shares(&1, &shares);
+    
+    nft.approve(&seller, &market.address, &1);
+    market.list_nft(&seller, &nft.address, &1, &100_000, &None);
+    market.buy_nft(&buyer, &nft.address, &1);
+    
+    assert_eq!(token.balance(&creators[0]), 5_000);
+    assert_eq!(token.balance(&creators[1]), 3_000);
+    assert_eq!(token.balance(&creators[2]), 2_000);
+    assert_eq!(token.balance(&market.address), 2_500);
+    assert_eq!(token.balance(&seller), 100_000 - 10_000 - 2_500);
+    assert_eq!(nft.owner_of(&1), buyer);
+}
+
+#[test]
+fn test_fee_setters_leave_room_for_royalties() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_nft, market, admin, _seller) = setup(&env, 0);
+    
+    // La comisión más el tope de royalties (10%) no puede superar el 100%
+    let mut tiers = Vec::new(&env);
+    tiers.push_back((0i128, 9_000u32));
+    market.set_fee_tiers(&tiers);
+    tiers.push_back((1_000_000i128, 9_001u32));
+    assert!(market.try_set_fee_tiers(&tiers).is_err());
+    assert_eq!(market.get_fee_tiers().len(), 1);
+    
+    let other_id = env.register_contract(None, Marketplace);
+    let other = MarketplaceClient::new(&env, &other_id);
+    assert!(other.try_initialize(&admin, &9_001, &7).is_err());
+    other.initialize(&admin, &9_000, &7);
+}
+
+#[test]
+fn test_end_auction_without_bids_returns_escrowed_nft() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 1);
+    let keeper = Address::generate(&env);
+    
+    nft.approve(&seller, &market.address, &1);
+    market.create_auction(&seller, &nft.address, &1, &1_000, &3600, &0);
+    assert_eq!(nft.owner_of(&1), market.address);
+    
+    // No se puede cerrar antes de tiempo
+    assert!(market.try_end_auction(&keeper, &nft.address, &1).is_err());
+    
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    market.end_auction(&keeper, &nft.address, &1);
+    
+    // Sin pujas el NFT vuelve al vendedor y no se registra venta
+    assert_eq!(nft.owner_of(&1), seller);
+    assert!(!market.get_auction(&nft.address, &1).active);
+    assert_eq!(market.get_sale_count(&nft.address, &1), 0);
+    assert!(market.try_end_auction(&keeper, &nft.address, &1).is_err());
+}
+
+#[test]
+fn test_price_decimals_and_price_tick() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 2);
+    
+    // Los decimales se fijan al inicializar, igual que los del token de pago
+    assert_eq!(market.get_price_decimals(), 7);
+    let other_id = env.register_contract(None, Marketplace);
+    let other = MarketplaceClient::new(&env, &other_id);
+    other.initialize(&admin, &250, &2);
+    assert_eq!(other.get_price_decimals(), 2);
+    
+    // Tick de 0.001 unidades con 7 decimales
+    assert!(market.try_set_price_tick(&-1).is_err());
+    market.set_price_tick(&10_000);
+    assert_eq!(market.get_price_tick(), 10_000);
+    
+    nft.approve_for_all(&seller, &market.address);
+    assert!(market.try_list_nft(&seller, &nft.address, &1, &10_500, &None).is_err());
+    market.list_nft(&seller, &nft.address, &1, &20_000, &None);
+    assert!(market.try_create_auction(&seller, &nft.address, &2, &15_000, &3600, &0).is_err());
+    
+    // Con tick 0 cualquier precio es válido
+    market.set_price_tick(&0);
+    market.create_auction(&seller, &nft.address, &2, &15_001, &3600, &0);
+}
+
+#[test]
+fn test_dashboard_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 4);
+    let buyer = Address::generate(&env);
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &10_000);
+    market.set_payment_token(&asset.address());
+    
+    let empty = market.get_dashboard_stats();
+    assert_eq!(empty.total_listings, 0);
+    assert!(empty.floor_price_map.is_empty());
+    
+    // Un segundo vendedor ofrece una pieza de otro museo
+    let other_seller = Address::generate(&env);
+    nft.mint_cultural_nft(&other_seller, &5, &sample_metadata(&env, &Address::generate(&env)), &Vec::new(&env));
+    
+    nft.approve_for_all(&seller, &market.address);
+    nft.approve_for_all(&other_seller, &market.address);
+    market.list_nft(&seller, &nft.address, &1, &4_000, &None);
+    market.list_nft(&seller, &nft.address, &2, &1_500, &None);
+    market.list_nft(&seller, &nft.address, &3, &2_500, &None);
+    market.list_nft(&other_seller, &nft.address, &5, &3_000, &None);
+    market.create_auction(&seller, &nft.address, &4, &1_000, &3600, &0);
+    market.buy_nft(&buyer, &nft.address, &1);
+    
+    let stats = market.get_dashboard_stats();
+    assert_eq!(stats.total_listings, 4);
+    assert_eq!(stats.active_listings, 3);
+    assert_eq!(stats.total_auctions, 1);
+    assert_eq!(stats.active_auctions, 1);
+    assert_eq!(stats.total_volume, 4_000);
+    assert_eq!(stats.total_sales, 1);
+    assert_eq!(stats.highest_sale, 4_000);
+    assert_eq!(stats.treasury_balance, 100);
+    
+    // Se cuentan los museos de las piezas, no los vendedores
+    assert_eq!(stats.total_museums_listed, 2);
+    
+    // La colección aparece con el menor precio entre los listados activos
+    assert_eq!(stats.floor_price_map, soroban_sdk::vec![&env, (nft.address.clone(), 1_500i128)]);
+    
+    // Al cancelar el listado más barato el mínimo pasa al siguiente
+    market.cancel_listing(&seller, &nft.address, &2);
+    let stats = market.get_dashboard_stats();
+    assert_eq!(stats.floor_price_map, soroban_sdk::vec![&env, (nft.address.clone(), 2_500i128)]);
+}
+
+#[test]
+fn test_treasury_kept_per_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 2);
+    let buyer = Address::generate(&env);
+    let first_token = market.get_payment_token().unwrap();
+    fund(&env, &market, &buyer, 4_000);
+    
+    nft.approve_for_all(&seller, &market.address);
+    market.list_nft(&seller, &nft.address, &1, &4_000, &None);
+    market.list_nft(&seller, &nft.address, &2, &2_000, &None);
+    market.buy_nft(&buyer, &nft.address, &1);
+    
+    // Tras cambiar de token, las comisiones nuevas van a su propio saldo
+    let second_token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    market.set_payment_token(&second_token);
+    fund(&env, &market, &buyer, 2_000);
+    market.buy_nft(&buyer, &nft.address, &2);
+    
+    assert_eq!(market.get_treasury_balance(&first_token), 100);
+    assert_eq!(market.get_treasury_balance(&second_token), 50);
+    assert_eq!(TokenClient::new(&env, &first_token).balance(&market.address), 100);
+    assert_eq!(TokenClient::new(&env, &second_token).balance(&market.address), 50);
+    
+    // El dashboard muestra la tesorería del token de pago vigente
+    assert_eq!(market.get_dashboard_stats().treasury_balance, 50);
+}
+    
+#[test]
+fn test_wash_trade_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 1);
+    let partner = Address::generate(&env);
+    let collector = Address::generate(&env);
+    fund(&env, &market, &partner, 1_000);
+    fund(&env, &market, &seller, 1_200);
+    fund(&env, &market, &collector, 1_300);
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+    
+    market.set_wash_trade_window(&3600);
+    assert_eq!(market.get_wash_trade_window(), 3600);
+    
+    nft.approve_for_all(&seller, &market.address);
+    nft.approve_for_all(&partner, &market.address);
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    market.buy_nft(&partner, &nft.address, &1);
+    
+    // Devolver el token a la misma contraparte dentro de la ventana se rechaza
+    env.ledger().with_mut(|li| li.timestamp = 10_000 + 3599);
+    market.list_nft(&partner, &nft.address, &1, &1_200, &None);
+    assert!(market.try_buy_nft(&seller, &nft.address, &1).is_err());
+    assert_eq!(nft.owner_of(&1), partner);
+    
+    // Al cerrarse la ventana la operación vuelve a permitirse
+    env.ledger().with_mut(|li| li.timestamp = 10_000 + 3600);
+    market.buy_nft(&seller, &nft.address, &1);
+    assert_eq!(nft.owner_of(&1), seller);
+    
+    // Con un tercero no hay restricción
+    market.list_nft(&seller, &nft.address, &1, &1_300, &None);
+    market.buy_nft(&collector, &nft.address, &1);
+    assert_eq!(nft.owner_of(&1), collector);
+}
+
+#[test]
+fn test_auction_status_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 1);
+    let bidder = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    fund(&env, &market, &bidder, 700);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    assert_eq!(market.get_auction_status(&nft.address, &1), AuctionStatus::NotFound);
+    
+    nft.approve(&seller, &market.address, &1);
+    market.create_auction(&seller, &nft.address, &1, &500, &3600, &0);
+    assert_eq!(market.get_auction_status(&nft.address, &1), AuctionStatus::Active(0, 4_600));
+    
+    market.bid(&bidder, &nft.address, &1, &700);
+    assert_eq!(market.get_auction_status(&nft.address, &1), AuctionStatus::Active(700, 4_600));
+    
+    // Terminada pero sin liquidar hasta que alguien llama a end_auction
+    env.ledger().with_mut(|li| li.timestamp = 4_600);
+    assert_eq!(market.get_auction_status(&nft.address, &1), AuctionStatus::EndedUnsettled);
+    
+    market.end_auction(&keeper, &nft.address, &1);
+    assert_eq!(market.get_auction_status(&nft.address, &1), AuctionStatus::Settled);
+    assert_eq!(nft.owner_of(&1), bidder);
+}
+
+#[test]
+fn test_next_min_bid_and_reserve_met() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 2);
+    let bidder = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    StellarAssetClient::new(&env, &asset.address()).mint(&bidder, &10_000);
+    market.set_payment_token(&asset.address());
+    market.set_min_bid_increment(&50);
+    
+    nft.approve_for_all(&seller, &market.address);
+    market.create_auction(&seller, &nft.address, &1, &1_000, &3600, &0);
+    market.create_auction(&seller, &nft.address, &2, &1_000, &3600, &0);
+    
+    // Antes de la primera puja el mínimo es el precio inicial; después, puja + incremento
+    assert_eq!(market.next_min_bid(&nft.address, &1), 1_000);
+    assert!(market.try_bid(&bidder, &nft.address, &1, &999).is_err());
+    
+    // La reserva se fija antes de pujar y no puede quedar bajo el precio inicial
+    assert!(market.try_set_reserve_price(&seller, &nft.address, &1, &900).is_err());
+    assert!(market.try_set_reserve_price(&bidder, &nft.address, &1, &2_000).is_err());
+    market.set_reserve_price(&seller, &nft.address, &1, &2_000);
+    market.set_reserve_price(&seller, &nft.address, &2, &1_500);
+    assert!(!market.reserve_met(&nft.address, &1));
+    
+    market.bid(&bidder, &nft.address, &1, &1_500);
+    assert_eq!(market.next_min_bid(&nft.address, &1), 1_550);
+    assert!(market.try_bid(&bidder, &nft.address, &1, &1_549).is_err());
+    assert!(!market.reserve_met(&nft.address, &1));
+    assert!(market.try_set_reserve_price(&seller, &nft.address, &1, &1_000).is_err());
+    
+    market.bid(&bidder, &nft.address, &2, &1_500);
+    assert!(market.reserve_met(&nft.address, &2));
+    assert_eq!(token.balance(&bidder), 7_000);
+    
+    // Sin alcanzar la reserva no hay venta: se devuelve la puja y el NFT
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    market.end_auction(&keeper, &nft.address, &1);
+    assert_eq!(nft.owner_of(&1), seller);
+    assert_eq!(token.balance(&bidder), 8_500);
+    assert_eq!(market.get_sale_count(&nft.address, &1), 0);
+    
+    // Con la reserva alcanzada la subasta se liquida con normalidad
+    market.end_auction(&keeper, &nft.address, &2);
+    assert_eq!(nft.owner_of(&2), bidder);
+    assert_eq!(token.balance(&seller), 1_500 - 37);
+}
+
+#[test]
+fn test_bid_notifies_seller_endpoint() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 1);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    fund(&env, &market, &first, 2_500);
+    fund(&env, &market, &second, 1_200);
+    
+    let callback_id = env.register_contract(None, MockNotification);
+    let callback = MockNotificationClient::new(&env, &callback_id);
+    market.register_seller_notif_endpoint(&seller, &callback_id);
+    assert_eq!(market.get_notification_endpoint(&seller), Some(callback_id.clone()));
+    
+    nft.approve(&seller, &market.address, &1);
+    market.create_auction(&seller, &nft.address, &1, &1_000, &3600, &0);
+    
+    // El aviso llega en cada puja, con el pujador y el monto
+    market.bid(&first, &nft.address, &1, &1_000);
+    market.bid(&second, &nft.address, &1, &1_200);
+    let bids = callback.bids();
+    assert_eq!(bids.len(), 2);
+    assert_eq!(bids.get(0).unwrap(), (seller.clone(), nft.address.clone(), 1, first.clone(), 1_000));
+    assert_eq!(bids.get(1).unwrap(), (seller.clone(), nft.address.clone(), 1, second.clone(), 1_200));
+    
+    // Sin endpoint registrado no hay más avisos
+    market.remove_notification_endpoint(&seller);
+    market.bid(&first, &nft.address, &1, &1_500);
+    assert_eq!(callback.bids().len(), 2);
+}
+
+#[test]
+fn test_keeper_reward_paid_from_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 3);
+    let buyer = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &10_000);
+    StellarAssetClient::new(&env, &asset.address()).mint(&bidder, &10_000);
+    market.set_payment_token(&asset.address());
+    market.set_keeper_reward(&30);
+    assert_eq!(market.get_keeper_reward(), 30);
+    
+    // Una venta deja 100 de comisión en la tesorería
+    nft.approve_for_all(&seller, &market.address);
+    market.list_nft(&seller, &nft.address, &1, &4_000, &None);
+    market.buy_nft(&buyer, &nft.address, &1);
+    assert_eq!(market.get_treasury_balance(&asset.address()), 100);
+    
+    market.create_auction(&seller, &nft.address, &2, &1_000, &3600, &0);
+    market.create_auction(&seller, &nft.address, &3, &1_000, &3600, &0);
+    market.bid(&bidder, &nft.address, &2, &2_000);
+    market.bid(&bidder, &nft.address, &3, &2_000);
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    
+    // Un tercero que liquida cobra el incentivo de la tesorería; la nueva comisión se suma
+    market.end_auction(&keeper, &nft.address, &2);
+    assert_eq!(token.balance(&keeper), 30);
+    assert_eq!(market.get_treasury_balance(&asset.address()), 100 - 30 + 50);
+    
+    // El ganador ya tiene incentivo para liquidar y no cobra
+    market.end_auction(&bidder, &nft.address, &3);
+    assert_eq!(token.balance(&bidder), 10_000 - 4_000);
+    assert_eq!(market.get_treasury_balance(&asset.address()), 170);
+    assert_eq!(token.balance(&market.address), 170);
+}
+
+#[test]
+fn test_keeper_reward_skipped_when_token_treasury_empty() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 2);
+    let buyer = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let first_token = market.get_payment_token().unwrap();
+    fund(&env, &market, &buyer, 4_000);
+    market.set_keeper_reward(&30);
+    
+    // La tesorería solo tiene comisiones en el primer token
+    nft.approve_for_all(&seller, &market.address);
+    market.list_nft(&seller, &nft.address, &1, &4_000, &None);
+    market.buy_nft(&buyer, &nft.address, &1);
+    assert_eq!(market.get_treasury_balance(&first_token), 100);
+    
+    let second_token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    market.set_payment_token(&second_token);
+    fund(&env, &market, &bidder, 2_000);
+    market.create_auction(&seller, &nft.address, &2, &1_000, &3600, &0);
+    market.bid(&bidder, &nft.address, &2, &2_000);
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    
+    // La subasta en el segundo token se liquida sin incentivo y sin tocar el otro saldo
+    market.end_auction(&keeper, &nft.address, &2);
+    assert_eq!(nft.owner_of(&2), bidder);
+    assert_eq!(TokenClient::new(&env, &second_token).balance(&keeper), 0);
+    assert_eq!(TokenClient::new(&env, &first_token).balance(&keeper), 0);
+    assert_eq!(market.get_treasury_balance(&first_token), 100);
+    assert_eq!(market.get_treasury_balance(&second_token), 50);
+}
+
+#[test]
+fn test_token_market_history_timeline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 1);
+    let buyer = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    fund(&env, &market, &buyer, 1_200);
+    fund(&env, &market, &bidder, 1_600);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    
+    nft.approve_for_all(&seller, &market.address);
+    nft.approve_for_all(&buyer, &market.address);
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    market.cancel_listing(&seller, &nft.address, &1);
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    market.list_nft(&seller, &nft.address, &1, &1_200, &None);
+    market.buy_nft(&buyer, &nft.address, &1);
+    
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    market.create_auction(&buyer, &nft.address, &1, &1_500, &3600, &0);
+    market.bid(&bidder, &nft.address, &1, &1_600);
+    env.ledger().with_mut(|li| li.timestamp = 300 + 3600);
+    market.end_auction(&keeper, &nft.address, &1);
+    
+    // Todos los eventos del token, del más antiguo al más reciente; la subasta liquidada
+    // termina con su venta
+    let history = market.get_token_market_history(&nft.address, &1, &0, &50);
+    let mut kinds = std::vec::Vec::new();
+    for event in history.iter() {
+        kinds.push(event.kind);
+    }
+    assert_eq!(kinds, [
+        MarketEventKind::Listed,
+        MarketEventKind::Cancelled,
+        MarketEventKind::Listed,
+        MarketEventKind::Sold,
+        MarketEventKind::AuctionCreated,
+        MarketEventKind::BidPlaced,
+        MarketEventKind::AuctionEnded,
+        MarketEventKind::Sold,
+    ]);
+    
+    let sold = history.get(3).unwrap();
+    assert_eq!(sold.actor, buyer);
+    assert_eq!(sold.amount, 1_200);
+    assert_eq!(sold.timestamp, 200);
+    let ended = history.get(6).unwrap();
+    assert_eq!(ended.actor, bidder);
+    assert_eq!(ended.amount, 1_600);
+    
+    // Paginado
+    let page = market.get_token_market_history(&nft.address, &1, &5, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().kind, MarketEventKind::BidPlaced);
+    assert!(market.get_token_market_history(&nft.address, &1, &8, &10).is_empty());
+}
+
+#[test]
+fn test_listing_with_metadata_uses_cache() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 0);
+    let museum = Address::generate(&env);
+    
+    let metadata = sample_metadata(&env, &museum);
+    nft.mint_cultural_nft(&seller, &1, &metadata, &Vec::new(&env));
+    nft.approve(&seller, &market.address, &1);
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    
+    env.budget().reset_default();
+    let (listing, fetched) = market.get_listing_with_metadata(&nft.address, &1);
+    let miss_cost = env.budget().cpu_instruction_cost();
+    assert_eq!(listing.price, 1_000);
+    assert_eq!(fetched.title, metadata.title);
+    
+    // Un acierto de caché evita la llamada entre contratos y cuesta menos
+    env.budget().reset_default();
+    market.get_listing_with_metadata(&nft.address, &1);
+    assert!(env.budget().cpu_instruction_cost() < miss_cost);
+    
+    // Mientras la caché está vigente no se vuelve a consultar el contrato NFT
+    let mut restored = metadata.clone();
+    restored.condition = String::from_str(&env, "Restaurado");
+    nft.update_cultural_metadata(&museum, &1, &restored);
+    let (_, cached) = market.get_listing_with_metadata(&nft.address, &1);
+    assert_eq!(cached.condition, metadata.condition);
+    
+    // Al vencer la caché se leen los metadatos actuales
+    env.ledger().with_mut(|li| li.sequence_number += 721);
+    let (_, refreshed) = market.get_listing_with_metadata(&nft.address, &1);
+    assert_eq!(refreshed.condition, restored.condition);
+}
+
+#[test]
+fn test_bonding_curve_sequential_purchases() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 4);
+    let buyer = Address::generate(&env);
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &10_000);
+    market.set_payment_token(&asset.address());
+    
+    nft.approve_for_all(&seller, &market.address);
+    let token_ids = Vec::from_array(&env, [1u32, 2, 3]);
+    let curve_id = market.create_bonding_curve(&seller, &nft.address, &token_ids, &1_000, &500);
+    
+    // Cada compra entrega el siguiente token al precio siguiente de la curva
+    let mut spent = 0;
+    for (token_id, price) in [(1u32, 1_000i128), (2, 1_500), (3, 2_000)] {
+        assert_eq!(market.get_current_curve_price(&curve_id), price);
+        assert_eq!(market.buy_from_curve(&buyer, &curve_id), token_id);
+        assert_eq!(nft.owner_of(&token_id), buyer);
+        spent += price;
+        assert_eq!(token.balance(&buyer), 10_000 - spent);
+    }
+    
+    // Agotada la curva, la compra falla sin cobrar
+    let curve = market.get_bonding_curve(&curve_id);
+    assert_eq!(curve.sold_count, 3);
+    assert!(!curve.active);
+    assert!(market.try_buy_from_curve(&buyer, &curve_id).is_err());
+    assert_eq!(token.balance(&buyer), 10_000 - spent);
+    
+    // Una curva cancelada tampoco vende
+    let curve_id = market.create_bonding_curve(&seller, &nft.address, &Vec::from_array(&env, [4u32]), &1_000, &0);
+    market.cancel_bonding_curve(&seller, &curve_id);
+    assert!(market.try_buy_from_curve(&buyer, &curve_id).is_err());
+    assert_eq!(nft.owner_of(&4), seller);
+}
+
+#[test]
+fn test_normalized_volume_across_payment_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 2);
+    let buyer = Address::generate(&env);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let xlm = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000);
+    StellarAssetClient::new(&env, &xlm).mint(&buyer, &10_000);
+    nft.approve_for_all(&seller, &market.address);
+    
+    // Una venta en cada token de pago
+    market.set_payment_token(&usdc);
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    market.buy_nft(&buyer, &nft.address, &1);
+    market.set_payment_token(&xlm);
+    market.list_nft(&seller, &nft.address, &2, &4_000, &None);
+    market.buy_nft(&buyer, &nft.address, &2);
+    assert_eq!(market.get_token_volume(&usdc), 1_000);
+    assert_eq!(market.get_token_volume(&xlm), 4_000);
+    
+    // Sin tasa el token no se incluye
+    assert!(market.try_set_token_rate(&usdc, &-1).is_err());
+    market.set_token_rate(&usdc, &RATE_PRECISION);
+    assert_eq!(market.get_token_rate(&usdc), Some(RATE_PRECISION));
+    assert_eq!(market.get_normalized_volume(), 1_000);
+    
+    // 1 XLM = 0.25 de la unidad de referencia
+    market.set_token_rate(&xlm, &(RATE_PRECISION / 4));
+    assert_eq!(market.get_normalized_volume(), 1_000 + 1_000);
+    
+    market.set_token_rate(&usdc, &0);
+    assert_eq!(market.get_token_rate(&usdc), None);
+    assert_eq!(market.get_normalized_volume(), 1_000);
+}
+
+#[test]
+fn test_fee_tier_boundaries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, admin, seller) = setup(&env, 1);
+    let buyer = Address::generate(&env);
+    
+    // Los tramos deben empezar en 0 y tener umbrales crecientes
+    assert!(market.try_set_fee_tiers(&Vec::from_array(&env, [(1i128, 250u32)])).is_err());
+    assert!(market.try_set_fee_tiers(&Vec::from_array(&env, [(0i128, 250u32), (10_000_000, 150), (1_000_000, 75)])).is_err());
+    
+    let tiers = Vec::from_array(&env, [(0i128, 250u32), (1_000_000, 150), (10_000_000, 75)]);
+    market.set_fee_tiers(&tiers);
+    assert_eq!(market.get_fee_tiers(), tiers);
+    
+    // Justo debajo, en y justo encima de cada umbral
+    for (price, fee) in [
+        (1i128, 0i128),
+        (999_999, 24_999),
+        (1_000_000, 15_000),
+        (1_000_001, 15_000),
+        (9_999_999, 149_999),
+        (10_000_000, 75_000),
+        (10_000_001, 75_000),
+    ] {
+        assert_eq!(market.compute_fee(&price), fee);
+    }
+    
+    // buy_nft cobra la comisión del tramo del precio del listado
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &1_000_000);
+    market.set_payment_token(&asset.address());
+    nft.approve(&seller, &market.address, &1);
+    market.list_nft(&seller, &nft.address, &1, &1_000_000, &None);
+    market.buy_nft(&buyer, &nft.address, &1);
+    assert_eq!(token.balance(&seller), 985_000);
+    
+    // Sin tramos vuelve la comisión plana
+    market.set_fee_tiers(&Vec::new(&env));
+    assert_eq!(market.compute_fee(&10_000_000), 250_000);
+}
+
+#[test]
+fn test_sales_require_payment_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (nft, market, _admin, seller) = setup(&env, 3);
+    let buyer = Address::generate(&env);
+    nft.approve_for_all(&seller, &market.address);
+    
+    // Recién inicializado, el marketplace no tiene token de pago
+    let market_id = env.register_contract(None, Marketplace);
+    let unconfigured = MarketplaceClient::new(&env, &market_id);
+    unconfigured.initialize(&Address::generate(&env), &250, &7);
+    nft.approve_for_all(&seller, &market_id);
+    
+    // Ni la compra, ni la subasta, ni la curva entregan tokens sin cobrar
+    unconfigured.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    assert!(unconfigured.try_buy_nft(&buyer, &nft.address, &1).is_err());
+    assert!(unconfigured.try_create_auction(&seller, &nft.address, &2, &500, &3600, &0).is_err());
+    let curve_id = unconfigured.create_bonding_curve(&seller, &nft.address, &Vec::from_array(&env, [3u32]), &1_000, &0);
+    assert!(unconfigured.try_buy_from_curve(&buyer, &curve_id).is_err());
+    assert_eq!(nft.owner_of(&1), seller);
+    assert_eq!(nft.owner_of(&3), unconfigured.address);
+    assert_eq!(unconfigured.get_dashboard_stats().total_volume, 0);
+    
+    // Con el token configurado la compra cobra antes de entregar
+    unconfigured.cancel_bonding_curve(&seller, &curve_id);
+    market.list_nft(&seller, &nft.address, &1, &1_000, &None);
+    assert!(market.try_buy_nft(&buyer, &nft.address, &1).is_err());
+    fund(&env, &market, &buyer, 1_000);
+    market.buy_nft(&buyer, &nft.address, &1);
+    assert_eq!(nft.owner_of(&1), buyer);
+}
+
+#[test]
+fn test_is_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_nft, market, admin, seller) = setup(&env, 0);
+    
+    assert!(market.is_admin(&admin));
+    assert!(!market.is_admin(&seller));
+    assert!(!market.is_admin(&Address::generate(&env)));
+}
@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec, Symbol};
+use soroban_sdk::{contract, contractclient, contractimpl, contracttype, symbol_short, token, Address, Bytes, BytesN, Env, IntoVal, String, Vec, Symbol, Val};
 
 /// Contrato para el registro de museos verificados
 /// 
@@ -16,6 +16,89 @@ pub struct MuseumRegistry;
 const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
 const MUSEUM_COUNT_KEY: Symbol = symbol_short!("MUS_CNT");
 const MUSEUM_VERIFIED_KEY: Symbol = symbol_short!("MUS_VER");
+const MUSEUM_LIST_KEY: Symbol = symbol_short!("MUS_LIST");
+const VALIDATOR_KEY: Symbol = symbol_short!("VALIDATOR");
+const COSIGN_KEY: Symbol = symbol_short!("COSIGN");
+const COSIGNERS_KEY: Symbol = symbol_short!("COSIGNERS");
+const REGION_KEY: Symbol = symbol_short!("REGION");
+const REGION_INDEX_KEY: Symbol = symbol_short!("REG_IDX");
+const VERIFICATION_EXPIRY_KEY: Symbol = symbol_short!("VER_EXP");
+const REGISTRATION_REQUEST_KEY: Symbol = symbol_short!("REG_REQ");
+const PENDING_REGISTRATIONS_KEY: Symbol = symbol_short!("REG_PEND");
+const REGISTRATION_TTL_KEY: Symbol = symbol_short!("REG_TTL");
+const VERIFICATION_HISTORY_KEY: Symbol = symbol_short!("VER_HIST");
+const TIER_KEY: Symbol = symbol_short!("TIER");
+const CURATOR_KEY: Symbol = symbol_short!("CURATOR");
+const CURATORS_KEY: Symbol = symbol_short!("CURATORS");
+const VERIFIER_KEY: Symbol = symbol_short!("VERIFIER");
+const VERIFIERS_KEY: Symbol = symbol_short!("VERIFIERS");
+const SUSPENSION_KEY: Symbol = symbol_short!("SUSPEND");
+const PROFILE_KEY: Symbol = symbol_short!("PROFILE");
+const REGISTRATION_PROFILE_KEY: Symbol = symbol_short!("REG_PROF");
+const PEER_KEY: Symbol = symbol_short!("PEER");
+const MUSEUM_STATS_KEY: Symbol = symbol_short!("MUS_STATS");
+const REPORT_KEY: Symbol = symbol_short!("REPORT");
+const REPORT_COUNT_KEY: Symbol = symbol_short!("RPT_CNT");
+const OPEN_REPORTS_KEY: Symbol = symbol_short!("RPT_OPEN");
+const REPORTER_OPEN_KEY: Symbol = symbol_short!("RPT_USER");
+const PENDING_ADMIN_KEY: Symbol = symbol_short!("PEND_ADM");
+const MIGRATION_KEY: Symbol = symbol_short!("MIGRATE");
+const MIGRATION_SOURCE_KEY: Symbol = symbol_short!("MIGR_SRC");
+const MIGRATED_TO_KEY: Symbol = symbol_short!("MIGR_TO");
+const REGISTRATION_FEE_KEY: Symbol = symbol_short!("REG_FEE");
+const REGISTRATION_FEE_PAID_KEY: Symbol = symbol_short!("REG_PAID");
+const REGISTRATION_REFUND_KEY: Symbol = symbol_short!("REG_RFND");
+const COLLECTED_FEES_KEY: Symbol = symbol_short!("REG_FEES");
+const CULTURAL_NFT_KEY: Symbol = symbol_short!("NFT_CTR");
+const COLLECTION_STATS_KEY: Symbol = symbol_short!("COL_STAT");
+const COLLECTION_SCAN_KEY: Symbol = symbol_short!("COL_SCAN");
+const BOND_CONFIG_KEY: Symbol = symbol_short!("BOND_CFG");
+const BOND_KEY: Symbol = symbol_short!("BOND");
+const BOND_SLASHES_KEY: Symbol = symbol_short!("BOND_SLSH");
+const EDIT_HISTORY_KEY: Symbol = symbol_short!("EDIT_HIST");
+
+// Tamaño máximo de página para consultas paginadas
+const MAX_PAGE_SIZE: u32 = 50;
+
+// Máximo de curadores por museo
+const MAX_CURATORS: u32 = 20;
+
+// Máximo de verificadores regionales
+const MAX_VERIFIERS: u32 = 20;
+
+// Ediciones propias conservadas por museo (se descartan las más antiguas)
+const MAX_EDIT_HISTORY: u32 = 50;
+
+// Máximo de solicitudes de registro pendientes y vigentes
+const MAX_PENDING_REGISTRATIONS: u32 = 100;
+
+// Longitud máxima en bytes de un término de búsqueda
+const MAX_SEARCH_TERM_LENGTH: usize = 128;
+
+// Vigencia por defecto de las solicitudes de registro (30 días)
+const DEFAULT_REGISTRATION_TTL: u64 = 30 * 86400;
+
+// Plazo de revisión de las migraciones de dirección solicitadas por el admin (7 días)
+const ADMIN_MIGRATION_DELAY: u64 = 7 * 86400;
+
+// Espera para retirar la fianza tras perder la verificación (30 días)
+const BOND_COOLDOWN: u64 = 30 * 86400;
+
+// Denominador de los porcentajes en puntos básicos
+const BPS_DENOMINATOR: u32 = 10000;
+
+// Perfil del museo: año de fundación mínimo, segundos por año (para el año actual)
+// y máximo de categorías
+const MIN_FOUNDED_YEAR: u32 = 1000;
+const SECONDS_PER_YEAR: u64 = 31_556_952;
+const MAX_PROFILE_CATEGORIES: u32 = 10;
+
+// Códigos de región admitidos (continentes)
+const REGION_CODES: [&str; 6] = ["EU", "NA", "SA", "AF", "AS", "OC"];
+
+// Etiquetas de CulturalNFT usadas en las estadísticas de colección
+const UNESCO_TAG: &str = "unesco";
+const CATEGORY_TAGS: [&str; 3] = ["original", "reproduction", "digital"];
 
 // Estructura de datos para información del museo
 #[derive(Clone)]
@@ -26,6 +109,236 @@ pub struct MuseumInfo {
     pub metadata: Vec<String>,
     pub registration_date: u64,
     pub verified: bool,
+    pub status: MuseumStatus,
+}
+
+// Estructura para el perfil estructurado del museo (reemplaza a `MuseumInfo.metadata`)
+#[derive(Clone)]
+#[contracttype]
+pub struct MuseumProfile {
+    pub country: Symbol, // Código ISO 3166-1 alfa-2 (ej. "PE")
+    pub city: String,
+    pub website: String,
+    pub contact_email_hash: BytesN<32>, // Hash del email de contacto, nunca en texto plano
+    pub founded_year: u32,
+    pub categories: Vec<Symbol>, // ej. "archaeo", "fine_art", "history"
+}
+
+// Estado operativo del museo; fuera de Active no cuenta como verificado
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum MuseumStatus {
+    Active,
+    Suspended, // En investigación; la verificación se conserva para reinstaurarla
+    Removed,
+}
+
+// Nivel de acreditación del museo
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum MuseumTier {
+    Unverified,
+    Community, // Archivos comunitarios (no cuentan como verificados)
+    Accredited,
+    National,
+}
+
+// Rol de un curador dentro de su museo
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum CuratorRole {
+    MetadataEditor, // Edita metadatos, etiquetas e informes de conservación
+    Registrar, // Propone minteos en nombre del museo
+}
+
+// Estructura para cambios en la verificación de un museo
+#[derive(Clone)]
+#[contracttype]
+pub struct VerificationChange {
+    pub verified: bool, // true = verificado, false = revocado
+    pub reason: String, // Vacío al verificar
+    pub timestamp: u64,
+}
+
+// Estructura para las ediciones que un museo hace de su propia información
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MuseumEdit {
+    pub editor: Address,
+    pub fields: Vec<Symbol>, // "name", "description", "metadata" o "profile"
+    pub timestamp: u64,
+}
+
+// Estado de un reporte comunitario sobre un museo
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum ReportStatus {
+    Open,
+    Dismissed,
+    Upheld,
+}
+
+// Resolución de un reporte por un verificador
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum ReportOutcome {
+    Dismiss,
+    Uphold,
+    UpholdAndSuspend, // Además suspende el museo con el motivo del reporte
+}
+
+// Estructura para los reportes comunitarios sobre un museo
+#[derive(Clone)]
+#[contracttype]
+pub struct MuseumReport {
+    pub id: u32,
+    pub reporter: Address,
+    pub reason: String,
+    pub created_at: u64,
+    pub status: ReportStatus,
+}
+
+// Estructura para la actividad de un museo reportada por los contratos pares
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct MuseumStats {
+    pub nfts_minted: u32,
+    pub sales_completed: u32,
+    pub total_volume: i128,
+}
+
+// Estructura para el payload de los eventos del registro (topics: nombre del evento, museo)
+#[derive(Clone)]
+#[contracttype]
+pub struct MuseumEvent {
+    pub name: String, // Nombre del museo
+    pub actor: Address, // Quién realizó la acción
+    pub timestamp: u64,
+    pub details: String, // Motivo o nivel según el evento; vacío si no aplica
+}
+
+// Estructura con todos los datos de un museo para indexadores
+#[derive(Clone)]
+#[contracttype]
+pub struct FullMuseumData {
+    pub info: MuseumInfo,
+    pub is_verified: bool, // Verificación vigente (admin o co-firma de validador)
+    pub tier: MuseumTier,
+    pub verification_expiry: Option<u64>,
+    pub region: Option<String>,
+    pub co_signatures: Vec<(Address, String)>, // (validador, atestación)
+}
+
+// Estructura para migraciones de dirección pendientes de confirmar por la nueva dirección
+#[derive(Clone)]
+#[contracttype]
+pub struct AddressMigration {
+    pub old_address: Address,
+    pub new_address: Address,
+    pub requested_at: u64,
+    pub executable_at: u64, // Con solicitud del admin, tras el plazo de revisión
+    pub admin_assisted: bool,
+}
+
+// Estructura para la comisión de registro que pagan los museos al solicitarlo
+#[derive(Clone)]
+#[contracttype]
+pub struct RegistrationFee {
+    pub token: Address,
+    pub amount: i128,
+    pub treasury: Address,
+}
+
+// Estructura para solicitudes de registro enviadas por los propios museos
+#[derive(Clone)]
+#[contracttype]
+pub struct RegistrationRequest {
+    pub museum_address: Address,
+    pub name: String,
+    pub description: String,
+    pub metadata: Vec<String>,
+    pub requested_at: u64,
+    pub expires_at: u64,
+}
+
+// Estructura para la configuración de las fianzas de los museos
+#[derive(Clone)]
+#[contracttype]
+pub struct BondConfig {
+    pub token: Address,
+    pub min_amount: i128,
+    pub treasury: Address, // Destino de las fianzas ejecutadas
+    pub required: bool, // Si es true, `is_verified` exige una fianza de al menos `min_amount`
+}
+
+// Estructura para la fianza depositada por un museo
+#[derive(Clone)]
+#[contracttype]
+pub struct MuseumBond {
+    pub token: Address,
+    pub amount: i128,
+    pub posted_at: u64,
+    pub unlock_at: u64, // Se fija al perder la verificación; 0 = sin espera
+}
+
+// Estructura para las ejecuciones de la fianza de un museo
+#[derive(Clone)]
+#[contracttype]
+pub struct BondSlash {
+    pub amount: i128,
+    pub reason: String,
+    pub slashed_by: Address,
+    pub timestamp: u64,
+}
+
+// Estructura para la composición de la colección de un museo en CulturalNFT
+//
+// Las categorías (original, reproducción, digital) y la pertenencia a UNESCO salen de
+// las etiquetas del token; el estado de conservación, de `CulturalMetadata.condition`.
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct CollectionStats {
+    pub total_tokens: u32,
+    pub unesco_tokens: u32,
+    pub unesco_bps: u32, // Porcentaje UNESCO sobre el total, en puntos básicos
+    pub original_tokens: u32,
+    pub reproduction_tokens: u32,
+    pub digital_tokens: u32,
+    pub condition_counts: Vec<(String, u32)>,
+    pub updated_at: u64,
+}
+
+// Metadatos culturales tal como los expone el contrato CulturalNFT
+#[derive(Clone)]
+#[contracttype]
+pub struct CulturalMetadata {
+    pub title: String,
+    pub artist: String,
+    pub period: String,
+    pub culture: String,
+    pub material: String,
+    pub dimensions: String,
+    pub condition: String,
+    pub significance: String,
+    pub museum_address: Address,
+    pub location: ArtifactLocation,
+    pub is_soulbound: bool,
+}
+
+// Ubicación física del artefacto (latitud, longitud en millonésimas de grado)
+#[derive(Clone)]
+#[contracttype]
+pub enum ArtifactLocation {
+    Unknown,
+    Coordinates(i64, i64),
+}
+
+// Interfaz del contrato CulturalNFT usada para las estadísticas de colección
+#[contractclient(name = "CulturalNFTClient")]
+pub trait CulturalNFTInterface {
+    fn get_tokens_by_museum(env: Env, museum: Address, start: u32, limit: u32) -> Vec<u32>;
+    fn get_tags(env: Env, token_id: u32) -> Vec<Symbol>;
+    fn get_cultural_metadata(env: Env, token_id: u32) -> CulturalMetadata;
 }
 
 #[contractimpl]
@@ -51,11 +364,238 @@ impl MuseumRegistry {
         name: String,
         description: String,
         metadata: Vec<String>,
+        profile: Option<MuseumProfile>,
+    ) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        Self::create_museum(&env, museum_address.clone(), name, description, metadata, profile);
+        Self::publish_museum_event(&env, "museum_registered", &museum_address, &admin, String::from_str(&env, ""));
+    }
+
+    /// Solicita el registro del propio museo; queda pendiente de aprobación del admin
+    /// o de un verificador
+    pub fn request_registration(
+        env: Env,
+        museum_address: Address,
+        name: String,
+        description: String,
+        metadata: Vec<String>,
+        profile: Option<MuseumProfile>,
     ) {
+        // Verificar que el caller es el propio museo
+        museum_address.require_auth();
+        
+        if env.storage().persistent().has(&(symbol_short!("MUSEUM"), museum_address.clone())) {
+            panic!("Museum already registered");
+        }
+        if let Some(profile) = &profile {
+            Self::validate_profile(&env, profile);
+        }
+        
+        // Una solicitud vencida puede reemplazarse; una vigente no
+        let request_key = (REGISTRATION_REQUEST_KEY, museum_address.clone());
+        let existing: Option<RegistrationRequest> = env.storage().persistent().get(&request_key);
+        let now = env.ledger().timestamp();
+        if existing.map(|request| now < request.expires_at).unwrap_or(false) {
+            panic!("Registration already pending");
+        }
+        
+        // Depurar las solicitudes vencidas (incluida la propia) antes de encolar otra
+        let mut pending = Self::prune_expired_registrations(&env);
+        if pending.len() >= MAX_PENDING_REGISTRATIONS {
+            panic!("Too many pending registrations");
+        }
+        
+        // Cobrar la comisión de registro, que queda en custodia hasta la resolución
+        if let Some(fee) = Self::get_registration_fee(env.clone()) {
+            if fee.amount > 0 {
+                token::Client::new(&env, &fee.token).transfer(&museum_address, &env.current_contract_address(), &fee.amount);
+                env.storage().persistent().set(&(REGISTRATION_FEE_PAID_KEY, museum_address.clone()), &fee);
+            }
+        }
+        
+        let request = RegistrationRequest {
+            museum_address: museum_address.clone(),
+            name,
+            description,
+            metadata,
+            requested_at: now,
+            expires_at: now + Self::get_registration_ttl(env.clone()),
+        };
+        env.storage().persistent().set(&request_key, &request);
+        
+        // El perfil propuesto se guarda aparte hasta la aprobación
+        let profile_key = (REGISTRATION_PROFILE_KEY, museum_address.clone());
+        match profile {
+            Some(profile) => env.storage().persistent().set(&profile_key, &profile),
+            None => env.storage().persistent().remove(&profile_key),
+        }
+        
+        // Agregar a la cola de pendientes
+        pending.push_back(museum_address);
+        env.storage().persistent().set(&PENDING_REGISTRATIONS_KEY, &pending);
+    }
+
+    /// Aprueba una solicitud de registro y crea el museo (admin o verificador)
+    pub fn approve_registration(env: Env, caller: Address, museum_address: Address) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
+        
+        let profile: Option<MuseumProfile> = env.storage().persistent().get(&(REGISTRATION_PROFILE_KEY, museum_address.clone()));
+        let request = Self::take_registration_request(&env, &museum_address);
+        if env.ledger().timestamp() >= request.expires_at {
+            panic!("Registration request expired");
+        }
+        
+        Self::create_museum(&env, museum_address.clone(), request.name, request.description, request.metadata, profile);
+        Self::settle_registration_fee(&env, &museum_address, true);
+        Self::publish_museum_event(&env, "museum_registered", &museum_address, &caller, String::from_str(&env, ""));
+    }
+
+    /// Rechaza una solicitud de registro (admin o verificador)
+    pub fn reject_registration(env: Env, caller: Address, museum_address: Address, reason: String) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
+        
+        let request = Self::take_registration_request(&env, &museum_address);
+        Self::settle_registration_fee(&env, &museum_address, false);
+        
+        // El museo no existe todavía: el nombre sale de la solicitud
+        env.events().publish(
+            (Symbol::new(&env, "registration_rejected"), museum_address),
+            MuseumEvent {
+                name: request.name,
+                actor: caller,
+                timestamp: env.ledger().timestamp(),
+                details: reason,
+            },
+        );
+    }
+
+    /// Obtiene las solicitudes de registro pendientes y no vencidas (paginado)
+    pub fn get_pending_registrations(env: Env, offset: u32, limit: u32) -> Vec<RegistrationRequest> {
+        let pending: Vec<Address> = env.storage().persistent().get(&PENDING_REGISTRATIONS_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let now = env.ledger().timestamp();
+        
+        let mut page = Vec::new(&env);
+        let mut matched: u32 = 0;
+        for museum_address in pending.iter() {
+            if page.len() >= limit {
+                break;
+            }
+            
+            let request: RegistrationRequest = env.storage().persistent().get(&(REGISTRATION_REQUEST_KEY, museum_address)).unwrap();
+            if now >= request.expires_at {
+                continue;
+            }
+            
+            if matched >= offset {
+                page.push_back(request);
+            }
+            matched += 1;
+        }
+        
+        page
+    }
+
+    /// Configura la vigencia de las solicitudes de registro en segundos (solo admin)
+    pub fn set_registration_ttl(env: Env, ttl: u64) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if ttl == 0 {
+            panic!("TTL must be positive");
+        }
+        
+        env.storage().instance().set(&REGISTRATION_TTL_KEY, &ttl);
+    }
+
+    /// Obtiene la vigencia de las solicitudes de registro en segundos
+    pub fn get_registration_ttl(env: Env) -> u64 {
+        env.storage().instance().get(&REGISTRATION_TTL_KEY).unwrap_or(DEFAULT_REGISTRATION_TTL)
+    }
+
+    /// Configura la comisión que se cobra al solicitar el registro (solo admin)
+    ///
+    /// Los registros hechos directamente por el admin no pagan comisión. `amount = 0`
+    /// deja de cobrarla.
+    pub fn set_registration_fee(env: Env, token: Address, amount: i128, treasury: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if amount < 0 {
+            panic!("Fee cannot be negative");
+        }
+        
+        env.storage().instance().set(&REGISTRATION_FEE_KEY, &RegistrationFee { token, amount, treasury });
+    }
+
+    /// Obtiene la comisión de registro configurada
+    pub fn get_registration_fee(env: Env) -> Option<RegistrationFee> {
+        env.storage().instance().get(&REGISTRATION_FEE_KEY)
+    }
+
+    /// Configura qué parte de la comisión se devuelve al rechazar una solicitud, en puntos
+    /// básicos (solo admin); por defecto se devuelve completa
+    pub fn set_registration_refund_bps(env: Env, refund_bps: u32) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if refund_bps > BPS_DENOMINATOR {
+            panic!("Invalid refund percentage");
+        }
+        
+        env.storage().instance().set(&REGISTRATION_REFUND_KEY, &refund_bps);
+    }
+
+    /// Obtiene la parte de la comisión que se devuelve al rechazar una solicitud
+    pub fn get_registration_refund_bps(env: Env) -> u32 {
+        env.storage().instance().get(&REGISTRATION_REFUND_KEY).unwrap_or(BPS_DENOMINATOR)
+    }
+
+    /// Obtiene las comisiones de registro retenidas en un token, pendientes de retirar
+    pub fn get_collected_registration_fees(env: Env, token: Address) -> i128 {
+        env.storage().persistent().get(&(COLLECTED_FEES_KEY, token)).unwrap_or(0)
+    }
+
+    /// Envía a la tesorería las comisiones de registro retenidas en un token (solo admin);
+    /// devuelve el monto retirado
+    pub fn withdraw_registration_fees(env: Env, token: Address) -> i128 {
         // Verificar que el caller es admin
         let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
         admin.require_auth();
         
+        let fee = Self::get_registration_fee(env.clone()).unwrap_or_else(|| {
+            panic!("Registration fee not set");
+        });
+        let amount = Self::get_collected_registration_fees(env.clone(), token.clone());
+        if amount == 0 {
+            panic!("No fees to withdraw");
+        }
+        
+        env.storage().persistent().remove(&(COLLECTED_FEES_KEY, token.clone()));
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &fee.treasury, &amount);
+        
+        amount
+    }
+
+    /// Crea el registro de un museo y lo agrega al índice
+    fn create_museum(
+        env: &Env,
+        museum_address: Address,
+        name: String,
+        description: String,
+        metadata: Vec<String>,
+        profile: Option<MuseumProfile>,
+    ) {
         // Verificar que el museo no esté ya registrado
         let museum_key = symbol_short!("MUSEUM");
         let museum_storage_key = (museum_key, museum_address.clone());
@@ -63,6 +603,12 @@ impl MuseumRegistry {
         if env.storage().persistent().has(&museum_storage_key) {
             panic!("Museum already registered");
         }
+        if env.storage().persistent().has(&(MIGRATED_TO_KEY, museum_address.clone())) {
+            panic!("Museum address migrated");
+        }
+        if let Some(profile) = &profile {
+            Self::validate_profile(env, profile);
+        }
         
         // Crear información del museo
         let museum_info = MuseumInfo {
@@ -71,26 +617,38 @@ impl MuseumRegistry {
             metadata: metadata.clone(),
             registration_date: env.ledger().timestamp(),
             verified: false, // Requiere verificación manual
+            status: MuseumStatus::Active,
         };
         
         // Guardar información del museo
         env.storage().persistent().set(&museum_storage_key, &museum_info);
+        if let Some(profile) = profile {
+            env.storage().persistent().set(&(PROFILE_KEY, museum_address.clone()), &profile);
+        }
         
         // Marcar como no verificado inicialmente
-        let verified_key = (MUSEUM_VERIFIED_KEY, museum_address);
+        let verified_key = (MUSEUM_VERIFIED_KEY, museum_address.clone());
         env.storage().persistent().set(&verified_key, &false);
         
         // Incrementar contador
         let mut count: u32 = env.storage().instance().get(&MUSEUM_COUNT_KEY).unwrap_or(0);
         count += 1;
         env.storage().instance().set(&MUSEUM_COUNT_KEY, &count);
+        
+        // Agregar al índice de museos
+        let mut museums: Vec<Address> = env.storage().persistent().get(&MUSEUM_LIST_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        museums.push_back(museum_address);
+        env.storage().persistent().set(&MUSEUM_LIST_KEY, &museums);
     }
 
-    /// Verifica un museo (solo admin)
-    pub fn verify_museum(env: Env, museum_address: Address) {
-        // Verificar que el caller es admin
-        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
-        admin.require_auth();
+    /// Verifica un museo (admin o verificador)
+    ///
+    /// `validity_period` es la vigencia de la acreditación en segundos; 0 = no expira.
+    pub fn verify_museum(env: Env, caller: Address, museum_address: Address, validity_period: u64) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
         
         // Verificar que el museo existe
         let museum_key = symbol_short!("MUSEUM");
@@ -101,63 +659,1253 @@ impl MuseumRegistry {
         }
         
         // Marcar como verificado
-        let verified_key = (MUSEUM_VERIFIED_KEY, museum_address);
+        let verified_key = (MUSEUM_VERIFIED_KEY, museum_address.clone());
         env.storage().persistent().set(&verified_key, &true);
         
+        Self::record_verification_change(&env, &museum_address, true, String::from_str(&env, ""));
+        
+        // Un museo verificado es como mínimo Accredited
+        let tier_key = (TIER_KEY, museum_address.clone());
+        let tier: Option<MuseumTier> = env.storage().persistent().get(&tier_key);
+        if tier != Some(MuseumTier::National) {
+            env.storage().persistent().set(&tier_key, &MuseumTier::Accredited);
+        }
+        
+        // Guardar la vigencia: (expira en, período de renovación)
+        let expiry_key = (VERIFICATION_EXPIRY_KEY, museum_address.clone());
+        if validity_period > 0 {
+            let expires_at = env.ledger().timestamp() + validity_period;
+            env.storage().persistent().set(&expiry_key, &(expires_at, validity_period));
+        } else {
+            env.storage().persistent().remove(&expiry_key);
+        }
+        
         // Actualizar información del museo
         let mut museum_info: MuseumInfo = env.storage().persistent().get(&museum_storage_key).unwrap();
         museum_info.verified = true;
         env.storage().persistent().set(&museum_storage_key, &museum_info);
+        
+        Self::publish_museum_event(&env, "museum_verified", &museum_address, &caller, String::from_str(&env, ""));
     }
 
-    /// Verifica si un museo está registrado y verificado
-    pub fn is_verified(env: Env, museum_address: Address) -> bool {
-        let verified_key = (MUSEUM_VERIFIED_KEY, museum_address);
-        env.storage().persistent().get(&verified_key).unwrap_or(false)
-    }
-
-    /// Obtiene información completa del museo
-    pub fn get_museum_info(env: Env, museum_address: Address) -> MuseumInfo {
-        let museum_key = symbol_short!("MUSEUM");
-        let museum_storage_key = (museum_key, museum_address);
+    /// Revoca la verificación de un museo que perdió su acreditación (admin o verificador)
+    ///
+    /// También descarta las co-firmas de validadores, para que `is_verified` devuelva false
+    /// de inmediato; el museo puede volver a verificarse con `verify_museum`.
+    pub fn revoke_verification(env: Env, caller: Address, museum_address: Address, reason: String) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
         
-        env.storage().persistent().get(&museum_storage_key).unwrap_or_else(|| {
-            panic!("Museum not found");
-        })
+        if reason.is_empty() {
+            panic!("Reason required");
+        }
+        
+        Self::clear_verification(&env, &museum_address, reason.clone());
+        Self::publish_museum_event(&env, "verification_revoked", &museum_address, &caller, reason);
     }
-    
 
-    /// Obtiene solo el nombre del museo
-    pub fn get_museum_name(env: Env, museum_address: Address) -> String {
-        let museum_info = Self::get_museum_info(env, museum_address);
-        museum_info.name
+    /// Renuncia voluntariamente a la verificación (solo el propio museo)
+    ///
+    /// La fianza queda bloqueada durante `BOND_COOLDOWN` por si hay que ejecutarla.
+    pub fn renounce_verification(env: Env, museum_address: Address) {
+        // Verificar que el caller es el propio museo
+        museum_address.require_auth();
+        
+        let verified: bool = env.storage().persistent().get(&(MUSEUM_VERIFIED_KEY, museum_address.clone())).unwrap_or(false);
+        if !verified {
+            panic!("Museum not verified");
+        }
+        
+        let reason = String::from_str(&env, "Voluntary de-verification");
+        Self::clear_verification(&env, &museum_address, reason.clone());
+        Self::publish_museum_event(&env, "verification_revoked", &museum_address, &museum_address, reason);
     }
 
-    /// Obtiene la descripción del museo
-    pub fn get_museum_description(env: Env, museum_address: Address) -> String {
-        let museum_info = Self::get_museum_info(env, museum_address);
-        museum_info.description
+    /// Configura la fianza de los museos (solo admin)
+    pub fn set_bond_config(env: Env, token: Address, min_amount: i128, treasury: Address, required: bool) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if min_amount <= 0 {
+            panic!("Minimum bond must be positive");
+        }
+        
+        env.storage().instance().set(&BOND_CONFIG_KEY, &BondConfig { token, min_amount, treasury, required });
     }
 
-    /// Obtiene los metadatos del museo
-    pub fn get_museum_metadata(env: Env, museum_address: Address) -> Vec<String> {
-        let museum_info = Self::get_museum_info(env, museum_address);
-        museum_info.metadata
+    /// Obtiene la configuración de las fianzas
+    pub fn get_bond_config(env: Env) -> Option<BondConfig> {
+        env.storage().instance().get(&BOND_CONFIG_KEY)
     }
 
-    /// Obtiene el total de museos registrados
-    pub fn get_total_museums(env: Env) -> u32 {
-        env.storage().instance().get(&MUSEUM_COUNT_KEY).unwrap_or(0)
+    /// Deposita o aumenta la fianza del museo; el total debe alcanzar el mínimo configurado
+    pub fn post_bond(env: Env, museum: Address, token: Address, amount: i128) {
+        // Verificar que el caller es el propio museo
+        museum.require_auth();
+        
+        if !env.storage().persistent().has(&(symbol_short!("MUSEUM"), museum.clone())) {
+            panic!("Museum not found");
+        }
+        let config = Self::get_bond_config(env.clone()).unwrap_or_else(|| {
+            panic!("Bond not configured");
+        });
+        if token != config.token {
+            panic!("Invalid bond token");
+        }
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        
+        let mut bond = Self::get_bond(env.clone(), museum.clone()).unwrap_or(MuseumBond {
+            token: token.clone(),
+            amount: 0,
+            posted_at: env.ledger().timestamp(),
+            unlock_at: 0,
+        });
+        if bond.token != token {
+            panic!("Invalid bond token");
+        }
+        bond.amount += amount;
+        if bond.amount < config.min_amount {
+            panic!("Bond below minimum");
+        }
+        
+        token::Client::new(&env, &token).transfer(&museum, &env.current_contract_address(), &amount);
+        env.storage().persistent().set(&(BOND_KEY, museum.clone()), &bond);
+        Self::publish_museum_event(&env, "bond_posted", &museum, &museum, String::from_str(&env, ""));
     }
 
-    /// Obtiene el admin del contrato
-    pub fn get_admin(env: Env) -> Address {
-        env.storage().instance().get(&ADMIN_KEY).unwrap()
+    /// Retira la fianza completa (solo el propio museo)
+    ///
+    /// Solo si el museo no está verificado y ya pasó la espera desde que perdió la verificación.
+    pub fn withdraw_bond(env: Env, museum: Address) -> i128 {
+        // Verificar que el caller es el propio museo
+        museum.require_auth();
+        
+        let bond = Self::get_bond(env.clone(), museum.clone()).unwrap_or_else(|| {
+            panic!("No bond posted");
+        });
+        let verified: bool = env.storage().persistent().get(&(MUSEUM_VERIFIED_KEY, museum.clone())).unwrap_or(false);
+        if verified {
+            panic!("Museum is verified");
+        }
+        if env.ledger().timestamp() < bond.unlock_at {
+            panic!("Bond locked");
+        }
+        
+        env.storage().persistent().remove(&(BOND_KEY, museum.clone()));
+        token::Client::new(&env, &bond.token).transfer(&env.current_contract_address(), &museum, &bond.amount);
+        
+        bond.amount
     }
 
-    /// Actualiza información del museo (solo admin)
-    pub fn update_museum_info(
-        env: Env,
+    /// Ejecuta parte de la fianza de un museo (p. ej. por artefactos fraudulentos) y la envía
+    /// a la tesorería (admin o verificador)
+    pub fn slash_bond(env: Env, caller: Address, museum: Address, amount: i128, reason: String) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
+        
+        let mut bond = Self::get_bond(env.clone(), museum.clone()).unwrap_or_else(|| {
+            panic!("No bond posted");
+        });
+        if amount <= 0 || amount > bond.amount {
+            panic!("Invalid slash amount");
+        }
+        if reason.is_empty() {
+            panic!("Reason required");
+        }
+        let config = Self::get_bond_config(env.clone()).unwrap();
+        
+        bond.amount -= amount;
+        if bond.amount == 0 {
+            env.storage().persistent().remove(&(BOND_KEY, museum.clone()));
+        } else {
+            env.storage().persistent().set(&(BOND_KEY, museum.clone()), &bond);
+        }
+        token::Client::new(&env, &bond.token).transfer(&env.current_contract_address(), &config.treasury, &amount);
+        
+        let mut slashes = Self::get_bond_slashes(env.clone(), museum.clone());
+        slashes.push_back(BondSlash {
+            amount,
+            reason: reason.clone(),
+            slashed_by: caller.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&(BOND_SLASHES_KEY, museum.clone()), &slashes);
+        
+        Self::publish_museum_event(&env, "bond_slashed", &museum, &caller, reason);
+    }
+
+    /// Obtiene la fianza vigente de un museo
+    pub fn get_bond(env: Env, museum: Address) -> Option<MuseumBond> {
+        env.storage().persistent().get(&(BOND_KEY, museum))
+    }
+
+    /// Obtiene el historial de ejecuciones de la fianza de un museo
+    pub fn get_bond_slashes(env: Env, museum: Address) -> Vec<BondSlash> {
+        env.storage().persistent().get(&(BOND_SLASHES_KEY, museum)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Quita la verificación de un museo (nivel, vigencia y co-firmas incluidos) y bloquea
+    /// su fianza durante `BOND_COOLDOWN`
+    fn clear_verification(env: &Env, museum_address: &Address, reason: String) {
+        let museum_storage_key = (symbol_short!("MUSEUM"), museum_address.clone());
+        let mut museum_info: MuseumInfo = env.storage().persistent().get(&museum_storage_key).unwrap_or_else(|| {
+            panic!("Museum not found");
+        });
+        
+        // Marcar como no verificado
+        env.storage().persistent().set(&(MUSEUM_VERIFIED_KEY, museum_address.clone()), &false);
+        env.storage().persistent().remove(&(VERIFICATION_EXPIRY_KEY, museum_address.clone()));
+        museum_info.verified = false;
+        env.storage().persistent().set(&museum_storage_key, &museum_info);
+        
+        env.storage().persistent().remove(&(TIER_KEY, museum_address.clone()));
+        Self::clear_co_signatures(env, museum_address);
+        
+        Self::record_verification_change(env, museum_address, false, reason);
+        
+        if let Some(mut bond) = Self::get_bond(env.clone(), museum_address.clone()) {
+            bond.unlock_at = env.ledger().timestamp() + BOND_COOLDOWN;
+            env.storage().persistent().set(&(BOND_KEY, museum_address.clone()), &bond);
+        }
+    }
+
+    /// Descarta las co-firmas de validadores de un museo
+    fn clear_co_signatures(env: &Env, museum_address: &Address) {
+        let cosigners: Vec<Address> = env.storage().persistent().get(&(COSIGNERS_KEY, museum_address.clone())).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        for validator in cosigners.iter() {
+            env.storage().persistent().remove(&(COSIGN_KEY, museum_address.clone(), validator));
+        }
+        env.storage().persistent().remove(&(COSIGNERS_KEY, museum_address.clone()));
+    }
+
+    /// Suspende temporalmente un museo en investigación (admin o verificador)
+    ///
+    /// Mientras está suspendido `is_verified` devuelve false, pero la verificación, el
+    /// nivel y las co-firmas se conservan: `reinstate_museum` lo restablece todo.
+    pub fn suspend_museum(env: Env, caller: Address, museum_address: Address, reason: String) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
+        
+        if reason.is_empty() {
+            panic!("Reason required");
+        }
+        
+        if Self::get_museum_status(env.clone(), museum_address.clone()) != MuseumStatus::Active {
+            panic!("Museum not active");
+        }
+        
+        Self::apply_suspension(&env, &caller, &museum_address, reason);
+    }
+
+    /// Levanta la suspensión de un museo (admin o verificador)
+    pub fn reinstate_museum(env: Env, caller: Address, museum_address: Address) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
+        
+        let museum_storage_key = (symbol_short!("MUSEUM"), museum_address.clone());
+        let mut museum_info = Self::get_museum_info(env.clone(), museum_address.clone());
+        if museum_info.status != MuseumStatus::Suspended {
+            panic!("Museum not suspended");
+        }
+        
+        museum_info.status = MuseumStatus::Active;
+        env.storage().persistent().set(&museum_storage_key, &museum_info);
+        env.storage().persistent().remove(&(SUSPENSION_KEY, museum_address.clone()));
+        
+        Self::publish_museum_event(&env, "museum_reinstated", &museum_address, &caller, String::from_str(&env, ""));
+    }
+
+    /// Reporta un museo sospechoso (p. ej. que suplanta a otra institución)
+    ///
+    /// Cada usuario puede tener un solo reporte abierto por museo. Devuelve el ID del
+    /// reporte, correlativo por museo.
+    pub fn report_museum(env: Env, reporter: Address, museum_address: Address, reason: String) -> u32 {
+        // Verificar que el caller es el propio usuario
+        reporter.require_auth();
+        
+        if !env.storage().persistent().has(&(symbol_short!("MUSEUM"), museum_address.clone())) {
+            panic!("Museum not found");
+        }
+        if reason.is_empty() {
+            panic!("Reason required");
+        }
+        
+        let reporter_key = (REPORTER_OPEN_KEY, museum_address.clone(), reporter.clone());
+        if env.storage().persistent().has(&reporter_key) {
+            panic!("Report already open");
+        }
+        
+        let count_key = (REPORT_COUNT_KEY, museum_address.clone());
+        let report_id: u32 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+        env.storage().persistent().set(&count_key, &report_id);
+        
+        let report = MuseumReport {
+            id: report_id,
+            reporter: reporter.clone(),
+            reason: reason.clone(),
+            created_at: env.ledger().timestamp(),
+            status: ReportStatus::Open,
+        };
+        env.storage().persistent().set(&(REPORT_KEY, museum_address.clone(), report_id), &report);
+        env.storage().persistent().set(&reporter_key, &report_id);
+        
+        let open_key = (OPEN_REPORTS_KEY, museum_address.clone());
+        let open: u32 = env.storage().persistent().get(&open_key).unwrap_or(0);
+        env.storage().persistent().set(&open_key, &(open + 1));
+        
+        Self::publish_museum_event(&env, "museum_reported", &museum_address, &reporter, reason);
+        
+        report_id
+    }
+
+    /// Resuelve un reporte abierto (admin o verificador)
+    ///
+    /// Con `UpholdAndSuspend` el museo, si está activo, queda suspendido con el motivo
+    /// del reporte.
+    pub fn resolve_report(env: Env, caller: Address, museum_address: Address, report_id: u32, outcome: ReportOutcome) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
+        
+        let report_key = (REPORT_KEY, museum_address.clone(), report_id);
+        let mut report: MuseumReport = env.storage().persistent().get(&report_key).unwrap_or_else(|| {
+            panic!("Report not found");
+        });
+        if report.status != ReportStatus::Open {
+            panic!("Report already resolved");
+        }
+        
+        report.status = match outcome {
+            ReportOutcome::Dismiss => ReportStatus::Dismissed,
+            ReportOutcome::Uphold | ReportOutcome::UpholdAndSuspend => ReportStatus::Upheld,
+        };
+        env.storage().persistent().set(&report_key, &report);
+        env.storage().persistent().remove(&(REPORTER_OPEN_KEY, museum_address.clone(), report.reporter.clone()));
+        
+        let open_key = (OPEN_REPORTS_KEY, museum_address.clone());
+        let open: u32 = env.storage().persistent().get(&open_key).unwrap_or(0);
+        env.storage().persistent().set(&open_key, &open.saturating_sub(1));
+        
+        let details = String::from_str(&env, if report.status == ReportStatus::Upheld { "upheld" } else { "dismissed" });
+        Self::publish_museum_event(&env, "report_resolved", &museum_address, &caller, details);
+        
+        if outcome == ReportOutcome::UpholdAndSuspend && Self::get_museum_status(env.clone(), museum_address.clone()) == MuseumStatus::Active {
+            Self::apply_suspension(&env, &caller, &museum_address, report.reason);
+        }
+    }
+
+    /// Obtiene el número de reportes abiertos de un museo
+    pub fn get_report_count(env: Env, museum_address: Address) -> u32 {
+        env.storage().persistent().get(&(OPEN_REPORTS_KEY, museum_address)).unwrap_or(0)
+    }
+
+    /// Obtiene los reportes de un museo, abiertos y resueltos, en orden de creación (paginado)
+    pub fn get_museum_reports(env: Env, museum_address: Address, offset: u32, limit: u32) -> Vec<MuseumReport> {
+        let count: u32 = env.storage().persistent().get(&(REPORT_COUNT_KEY, museum_address.clone())).unwrap_or(0);
+        
+        let mut page = Vec::new(&env);
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        for i in offset..end {
+            let report: MuseumReport = env.storage().persistent().get(&(REPORT_KEY, museum_address.clone(), i + 1)).unwrap();
+            page.push_back(report);
+        }
+        
+        page
+    }
+
+    /// Obtiene el estado operativo de un museo
+    pub fn get_museum_status(env: Env, museum_address: Address) -> MuseumStatus {
+        Self::get_museum_info(env, museum_address).status
+    }
+
+    /// Obtiene el motivo de la suspensión vigente de un museo (None si no está suspendido)
+    pub fn get_suspension_reason(env: Env, museum_address: Address) -> Option<String> {
+        env.storage().persistent().get(&(SUSPENSION_KEY, museum_address))
+    }
+
+    /// Asigna el nivel de acreditación de un museo (solo admin)
+    ///
+    /// Accredited y National cuentan como verificados para `is_verified`; Community y
+    /// Unverified no, y bajar a uno de ellos descarta las co-firmas de validadores.
+    pub fn set_museum_tier(env: Env, museum_address: Address, tier: MuseumTier) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let museum_storage_key = (symbol_short!("MUSEUM"), museum_address.clone());
+        let mut museum_info: MuseumInfo = env.storage().persistent().get(&museum_storage_key).unwrap_or_else(|| {
+            panic!("Museum not found");
+        });
+        
+        env.storage().persistent().set(&(TIER_KEY, museum_address.clone()), &tier);
+        
+        // Mantener sincronizada la bandera de verificación
+        let verified = matches!(tier, MuseumTier::Accredited | MuseumTier::National);
+        if museum_info.verified != verified {
+            let reason = if verified { "" } else { "Tier downgraded" };
+            Self::record_verification_change(&env, &museum_address, verified, String::from_str(&env, reason));
+        }
+        env.storage().persistent().set(&(MUSEUM_VERIFIED_KEY, museum_address.clone()), &verified);
+        museum_info.verified = verified;
+        env.storage().persistent().set(&museum_storage_key, &museum_info);
+        if !verified {
+            Self::clear_co_signatures(&env, &museum_address);
+        }
+        
+        let tier_name = match tier {
+            MuseumTier::Unverified => "Unverified",
+            MuseumTier::Community => "Community",
+            MuseumTier::Accredited => "Accredited",
+            MuseumTier::National => "National",
+        };
+        Self::publish_museum_event(&env, "tier_changed", &museum_address, &admin, String::from_str(&env, tier_name));
+    }
+
+    /// Obtiene el nivel de acreditación de un museo
+    ///
+    /// Los museos verificados antes de existir los niveles se consideran Accredited.
+    pub fn get_museum_tier(env: Env, museum_address: Address) -> MuseumTier {
+        if let Some(tier) = env.storage().persistent().get(&(TIER_KEY, museum_address.clone())) {
+            return tier;
+        }
+        
+        let verified: bool = env.storage().persistent().get(&(MUSEUM_VERIFIED_KEY, museum_address)).unwrap_or(false);
+        if verified {
+            MuseumTier::Accredited
+        } else {
+            MuseumTier::Unverified
+        }
+    }
+
+    /// Obtiene el historial de verificaciones y revocaciones de un museo
+    pub fn get_verification_history(env: Env, museum_address: Address) -> Vec<VerificationChange> {
+        env.storage().persistent().get(&(VERIFICATION_HISTORY_KEY, museum_address)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Verifica si un museo está registrado y verificado
+    ///
+    /// Un museo también se considera verificado si algún validador externo registrado
+    /// (ej. UNESCO, ICOM) co-firmó su verificación, salvo que su verificación haya expirado:
+    /// en ese caso solo `renew_verification` o `verify_museum` lo vuelven a verificar.
+    pub fn is_verified(env: Env, museum_address: Address) -> bool {
+        // Un museo suspendido o dado de baja no cuenta como verificado
+        let museum_info: Option<MuseumInfo> = env.storage().persistent().get(&(symbol_short!("MUSEUM"), museum_address.clone()));
+        if museum_info.map(|info| info.status != MuseumStatus::Active).unwrap_or(false) {
+            return false;
+        }
+        
+        // Con fianza obligatoria, un museo sin la fianza mínima no cuenta como verificado
+        if let Some(config) = Self::get_bond_config(env.clone()) {
+            let bonded = Self::get_bond(env.clone(), museum_address.clone()).map(|bond| bond.amount).unwrap_or(0);
+            if config.required && bonded < config.min_amount {
+                return false;
+            }
+        }
+        
+        let verified_key = (MUSEUM_VERIFIED_KEY, museum_address.clone());
+        let verified: bool = env.storage().persistent().get(&verified_key).unwrap_or(false);
+        let expired = Self::get_verification_expiry(env.clone(), museum_address.clone())
+            .map(|expires_at| env.ledger().timestamp() >= expires_at)
+            .unwrap_or(false);
+        if expired {
+            return false;
+        }
+        if verified {
+            return true;
+        }
+        
+        // Un museo que el admin dejó en Community o Unverified no se verifica por co-firmas
+        let tier: Option<MuseumTier> = env.storage().persistent().get(&(TIER_KEY, museum_address.clone()));
+        if matches!(tier, Some(MuseumTier::Community | MuseumTier::Unverified)) {
+            return false;
+        }
+        
+        let cosigners: Vec<Address> = env.storage().persistent().get(&(COSIGNERS_KEY, museum_address)).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        for validator in cosigners.iter() {
+            if env.storage().persistent().has(&(VALIDATOR_KEY, validator)) {
+                return true;
+            }
+        }
+        
+        false
+    }
+
+    /// Renueva la verificación de un museo por otro período de vigencia (admin o verificador)
+    pub fn renew_verification(env: Env, caller: Address, museum_address: Address) {
+        // Verificar que el caller es admin o verificador
+        Self::ensure_verifier(&env, &caller);
+        
+        let expiry_key = (VERIFICATION_EXPIRY_KEY, museum_address.clone());
+        let (_, validity_period): (u64, u64) = env.storage().persistent().get(&expiry_key).unwrap_or_else(|| {
+            panic!("Verification does not expire");
+        });
+        
+        let expires_at = env.ledger().timestamp() + validity_period;
+        env.storage().persistent().set(&expiry_key, &(expires_at, validity_period));
+        
+        Self::publish_museum_event(&env, "verification_renewed", &museum_address, &caller, String::from_str(&env, ""));
+    }
+
+    /// Obtiene cuándo expira la verificación de un museo (None si no expira)
+    pub fn get_verification_expiry(env: Env, museum_address: Address) -> Option<u64> {
+        let expiry: Option<(u64, u64)> = env.storage().persistent().get(&(VERIFICATION_EXPIRY_KEY, museum_address));
+        expiry.map(|(expires_at, _)| expires_at)
+    }
+
+    /// Registra un validador externo de acreditación (solo admin)
+    pub fn register_validator(env: Env, validator_address: Address, organization_name: String) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().persistent().set(&(VALIDATOR_KEY, validator_address), &organization_name);
+    }
+
+    /// Elimina un validador externo (solo admin); sus co-firmas dejan de contar
+    pub fn remove_validator(env: Env, validator_address: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().persistent().remove(&(VALIDATOR_KEY, validator_address));
+    }
+
+    /// Obtiene la organización de un validador registrado
+    pub fn get_validator(env: Env, validator_address: Address) -> Option<String> {
+        env.storage().persistent().get(&(VALIDATOR_KEY, validator_address))
+    }
+
+    /// Co-firma la verificación de un museo como validador externo
+    pub fn co_sign_verification(env: Env, validator: Address, museum_address: Address, attestation: String) {
+        // Verificar que el caller es un validador registrado
+        validator.require_auth();
+        if !env.storage().persistent().has(&(VALIDATOR_KEY, validator.clone())) {
+            panic!("Not a registered validator");
+        }
+        
+        // Verificar que el museo existe
+        let museum_storage_key = (symbol_short!("MUSEUM"), museum_address.clone());
+        if !env.storage().persistent().has(&museum_storage_key) {
+            panic!("Museum not found");
+        }
+        
+        // Guardar co-firma
+        let cosign_key = (COSIGN_KEY, museum_address.clone(), validator.clone());
+        env.storage().persistent().set(&cosign_key, &(attestation, env.ledger().timestamp()));
+        
+        // Agregar al índice de co-firmantes del museo
+        let cosigners_key = (COSIGNERS_KEY, museum_address);
+        let mut cosigners: Vec<Address> = env.storage().persistent().get(&cosigners_key).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        if !cosigners.contains(&validator) {
+            cosigners.push_back(validator);
+            env.storage().persistent().set(&cosigners_key, &cosigners);
+        }
+    }
+
+    /// Obtiene las co-firmas (validador, atestación) de un museo
+    pub fn get_co_signatures(env: Env, museum_address: Address) -> Vec<(Address, String)> {
+        let cosigners: Vec<Address> = env.storage().persistent().get(&(COSIGNERS_KEY, museum_address.clone())).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        let mut result = Vec::new(&env);
+        for validator in cosigners.iter() {
+            let cosign_key = (COSIGN_KEY, museum_address.clone(), validator.clone());
+            let (attestation, _timestamp): (String, u64) = env.storage().persistent().get(&cosign_key).unwrap();
+            result.push_back((validator, attestation));
+        }
+        
+        result
+    }
+
+    /// Solicita migrar el registro de un museo a una nueva dirección (firmada por la
+    /// dirección actual); la nueva dirección debe confirmarla
+    ///
+    /// Una nueva solicitud reemplaza a la pendiente.
+    pub fn request_address_migration(env: Env, old: Address, new: Address) {
+        // Verificar que el caller es el propio museo
+        old.require_auth();
+        
+        Self::open_migration(&env, &old, &new, &old, 0);
+    }
+
+    /// Solicita la migración en nombre de un museo que perdió su clave (solo admin); solo
+    /// puede confirmarse tras `ADMIN_MIGRATION_DELAY`, y el museo puede cancelarla mientras
+    pub fn admin_request_address_migration(env: Env, old: Address, new: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        Self::open_migration(&env, &old, &new, &admin, ADMIN_MIGRATION_DELAY);
+    }
+
+    /// Confirma la migración pendiente hacia `new` (firmada por la nueva dirección)
+    ///
+    /// Información, verificación, nivel, curadores, co-firmas, región, reportes y
+    /// estadísticas pasan a la nueva dirección; la anterior queda apuntando a ella.
+    pub fn confirm_address_migration(env: Env, new: Address) {
+        // Verificar que el caller es la nueva dirección
+        new.require_auth();
+        
+        let migration: AddressMigration = env.storage().persistent().get(&(MIGRATION_KEY, new.clone())).unwrap_or_else(|| {
+            panic!("Migration not found");
+        });
+        if env.ledger().timestamp() < migration.executable_at {
+            panic!("Migration under review");
+        }
+        Self::ensure_migration_target(&env, &new);
+        
+        let old = migration.old_address;
+        env.storage().persistent().remove(&(MIGRATION_KEY, new.clone()));
+        env.storage().persistent().remove(&(MIGRATION_SOURCE_KEY, old.clone()));
+        
+        // Mover el estado del museo
+        for key in [
+            symbol_short!("MUSEUM"),
+            MUSEUM_VERIFIED_KEY,
+            VERIFICATION_EXPIRY_KEY,
+            VERIFICATION_HISTORY_KEY,
+            TIER_KEY,
+            SUSPENSION_KEY,
+            PROFILE_KEY,
+            MUSEUM_STATS_KEY,
+            COLLECTION_STATS_KEY,
+            BOND_KEY,
+            BOND_SLASHES_KEY,
+            EDIT_HISTORY_KEY,
+            REGION_KEY,
+            COSIGNERS_KEY,
+            REPORT_COUNT_KEY,
+            OPEN_REPORTS_KEY,
+        ] {
+            Self::move_entry(&env, &(key.clone(), old.clone()), &(key, new.clone()));
+        }
+        let cosigners: Vec<Address> = env.storage().persistent().get(&(COSIGNERS_KEY, new.clone())).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        for validator in cosigners.iter() {
+            Self::move_entry(&env, &(COSIGN_KEY, old.clone(), validator.clone()), &(COSIGN_KEY, new.clone(), validator));
+        }
+        let report_count: u32 = env.storage().persistent().get(&(REPORT_COUNT_KEY, new.clone())).unwrap_or(0);
+        for report_id in 1..=report_count {
+            Self::move_entry(&env, &(REPORT_KEY, old.clone(), report_id), &(REPORT_KEY, new.clone(), report_id));
+            let report: MuseumReport = env.storage().persistent().get(&(REPORT_KEY, new.clone(), report_id)).unwrap();
+            Self::move_entry(&env, &(REPORTER_OPEN_KEY, old.clone(), report.reporter.clone()), &(REPORTER_OPEN_KEY, new.clone(), report.reporter));
+        }
+        
+        // Mover los curadores (la nueva dirección deja de ser curadora de su propio museo)
+        let mut curators = Self::get_curators(env.clone(), old.clone());
+        env.storage().persistent().remove(&(CURATORS_KEY, old.clone()));
+        if let Some(position) = curators.first_index_of(&new) {
+            curators.remove(position);
+            env.storage().persistent().remove(&(CURATOR_KEY, old.clone(), new.clone()));
+        }
+        for curator in curators.iter() {
+            Self::move_entry(&env, &(CURATOR_KEY, old.clone(), curator.clone()), &(CURATOR_KEY, new.clone(), curator));
+        }
+        if !curators.is_empty() {
+            env.storage().persistent().set(&(CURATORS_KEY, new.clone()), &curators);
+        }
+        
+        // Reemplazar la dirección en los índices
+        Self::replace_in_index(&env, &MUSEUM_LIST_KEY, &old, &new);
+        if let Some(region) = Self::get_museum_region(env.clone(), new.clone()) {
+            Self::replace_in_index(&env, &(REGION_INDEX_KEY, region), &old, &new);
+        }
+        
+        env.storage().persistent().set(&(MIGRATED_TO_KEY, old.clone()), &new);
+        Self::publish_museum_event(&env, "museum_migrated", &new, &new, old.to_string());
+    }
+
+    /// Cancela una migración pendiente (el propio museo o el admin)
+    pub fn cancel_address_migration(env: Env, caller: Address, old: Address) {
+        caller.require_auth();
+        
+        if caller != old && !Self::is_admin(env.clone(), caller.clone()) {
+            panic!("Not authorized to cancel migration");
+        }
+        
+        let new: Address = env.storage().persistent().get(&(MIGRATION_SOURCE_KEY, old.clone())).unwrap_or_else(|| {
+            panic!("Migration not found");
+        });
+        env.storage().persistent().remove(&(MIGRATION_SOURCE_KEY, old));
+        env.storage().persistent().remove(&(MIGRATION_KEY, new));
+    }
+
+    /// Obtiene la migración pendiente de un museo
+    pub fn get_pending_migration(env: Env, old: Address) -> Option<AddressMigration> {
+        let new: Address = env.storage().persistent().get(&(MIGRATION_SOURCE_KEY, old))?;
+        env.storage().persistent().get(&(MIGRATION_KEY, new))
+    }
+
+    /// Obtiene la dirección a la que se migró un museo (None si no se migró)
+    pub fn get_migrated_to(env: Env, old: Address) -> Option<Address> {
+        env.storage().persistent().get(&(MIGRATED_TO_KEY, old))
+    }
+
+    /// Obtiene información completa del museo
+    pub fn get_museum_info(env: Env, museum_address: Address) -> MuseumInfo {
+        let museum_key = symbol_short!("MUSEUM");
+        let museum_storage_key = (museum_key, museum_address);
+        
+        env.storage().persistent().get(&museum_storage_key).unwrap_or_else(|| {
+            panic!("Museum not found");
+        })
+    }
+    
+    /// Obtiene en una sola llamada toda la información de un museo
+    pub fn get_all_museum_data(env: Env, museum_address: Address) -> FullMuseumData {
+        FullMuseumData {
+            info: Self::get_museum_info(env.clone(), museum_address.clone()),
+            is_verified: Self::is_verified(env.clone(), museum_address.clone()),
+            tier: Self::get_museum_tier(env.clone(), museum_address.clone()),
+            verification_expiry: Self::get_verification_expiry(env.clone(), museum_address.clone()),
+            region: Self::get_museum_region(env.clone(), museum_address.clone()),
+            co_signatures: Self::get_co_signatures(env, museum_address),
+        }
+    }
+
+    /// Obtiene solo el nombre del museo
+    pub fn get_museum_name(env: Env, museum_address: Address) -> String {
+        let museum_info = Self::get_museum_info(env, museum_address);
+        museum_info.name
+    }
+
+    /// Obtiene la descripción del museo
+    pub fn get_museum_description(env: Env, museum_address: Address) -> String {
+        let museum_info = Self::get_museum_info(env, museum_address);
+        museum_info.description
+    }
+
+    /// Obtiene los metadatos del museo
+    pub fn get_museum_metadata(env: Env, museum_address: Address) -> Vec<String> {
+        let museum_info = Self::get_museum_info(env, museum_address);
+        museum_info.metadata
+    }
+
+    /// Obtiene el total de museos registrados
+    pub fn get_total_museums(env: Env) -> u32 {
+        env.storage().instance().get(&MUSEUM_COUNT_KEY).unwrap_or(0)
+    }
+
+    /// Obtiene los museos registrados en orden de registro (paginado)
+    pub fn get_museums(env: Env, start: u32, limit: u32) -> Vec<Address> {
+        let museums: Vec<Address> = env.storage().persistent().get(&MUSEUM_LIST_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        let mut page = Vec::new(&env);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(museums.len());
+        for i in start..end {
+            page.push_back(museums.get(i).unwrap());
+        }
+        
+        page
+    }
+
+    /// Asigna la región geográfica de un museo (solo admin)
+    ///
+    /// `region` debe ser uno de los códigos de `REGION_CODES`; si el museo ya tenía
+    /// región, se mueve al índice de la nueva.
+    pub fn set_museum_region(env: Env, museum_address: Address, region: String) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        // Verificar que el museo existe
+        let museum_storage_key = (symbol_short!("MUSEUM"), museum_address.clone());
+        if !env.storage().persistent().has(&museum_storage_key) {
+            panic!("Museum not found");
+        }
+        
+        // Verificar que el código de región es válido
+        if !REGION_CODES.iter().any(|code| region == String::from_str(&env, code)) {
+            panic!("Invalid region code");
+        }
+        
+        // Quitar el museo del índice de su región anterior
+        let region_key = (REGION_KEY, museum_address.clone());
+        if let Some(old_region) = env.storage().persistent().get::<_, String>(&region_key) {
+            if old_region == region {
+                return;
+            }
+            
+            let old_index_key = (REGION_INDEX_KEY, old_region);
+            let mut old_index: Vec<Address> = env.storage().persistent().get(&old_index_key).unwrap();
+            if let Some(position) = old_index.first_index_of(&museum_address) {
+                old_index.remove(position);
+            }
+            env.storage().persistent().set(&old_index_key, &old_index);
+        }
+        
+        // Agregar al índice de la nueva región
+        let index_key = (REGION_INDEX_KEY, region.clone());
+        let mut index: Vec<Address> = env.storage().persistent().get(&index_key).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        index.push_back(museum_address);
+        env.storage().persistent().set(&index_key, &index);
+        
+        env.storage().persistent().set(&region_key, &region);
+    }
+
+    /// Obtiene la región de un museo
+    pub fn get_museum_region(env: Env, museum_address: Address) -> Option<String> {
+        env.storage().persistent().get(&(REGION_KEY, museum_address))
+    }
+
+    /// Obtiene los museos de una región (paginado)
+    pub fn get_museums_by_region(env: Env, region: String, offset: u32, limit: u32) -> Vec<Address> {
+        let index: Vec<Address> = env.storage().persistent().get(&(REGION_INDEX_KEY, region)).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        let mut page = Vec::new(&env);
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(index.len());
+        for i in offset..end {
+            page.push_back(index.get(i).unwrap());
+        }
+        
+        page
+    }
+
+    /// Obtiene el número de museos de una región
+    pub fn get_region_museum_count(env: Env, region: String) -> u32 {
+        let index: Vec<Address> = env.storage().persistent().get(&(REGION_INDEX_KEY, region)).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        index.len()
+    }
+
+    /// Obtiene los museos registrados entre dos fechas, inclusive (paginado sobre los resultados)
+    pub fn get_museums_registered_between(
+        env: Env,
+        from_ts: u64,
+        to_ts: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        let museums: Vec<Address> = env.storage().persistent().get(&MUSEUM_LIST_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        let limit = limit.min(MAX_PAGE_SIZE);
+        
+        let mut page = Vec::new(&env);
+        let mut matched: u32 = 0;
+        for museum_address in museums.iter() {
+            if page.len() >= limit {
+                break;
+            }
+            
+            let info = Self::get_museum_info(env.clone(), museum_address.clone());
+            if info.registration_date < from_ts || info.registration_date > to_ts {
+                continue;
+            }
+            
+            if matched >= start {
+                page.push_back(museum_address);
+            }
+            matched += 1;
+        }
+        
+        page
+    }
+
+    /// Autoriza o retira un contrato par (CulturalNFT, Marketplace) que reporta actividad
+    /// de los museos (solo admin)
+    pub fn set_peer_contract(env: Env, peer: Address, allowed: bool) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let peer_key = (PEER_KEY, peer);
+        if allowed {
+            env.storage().persistent().set(&peer_key, &true);
+        } else {
+            env.storage().persistent().remove(&peer_key);
+        }
+    }
+
+    /// Verifica si un contrato puede reportar actividad de los museos
+    pub fn is_peer_contract(env: Env, peer: Address) -> bool {
+        env.storage().persistent().get(&(PEER_KEY, peer)).unwrap_or(false)
+    }
+
+    /// Registra actividad de un museo (solo contratos pares)
+    ///
+    /// `kind` es "mint" (un NFT minteado; `amount` se ignora) o "sale" (una venta
+    /// completada por `amount`).
+    pub fn record_museum_activity(env: Env, peer: Address, museum_address: Address, kind: Symbol, amount: i128) {
+        // Verificar que el caller es un contrato par
+        peer.require_auth();
+        if !Self::is_peer_contract(env.clone(), peer) {
+            panic!("Not a peer contract");
+        }
+        
+        if !env.storage().persistent().has(&(symbol_short!("MUSEUM"), museum_address.clone())) {
+            panic!("Museum not found");
+        }
+        if amount < 0 {
+            panic!("Invalid amount");
+        }
+        
+        let mut stats = Self::get_museum_stats(env.clone(), museum_address.clone());
+        if kind == symbol_short!("mint") {
+            stats.nfts_minted += 1;
+        } else if kind == symbol_short!("sale") {
+            stats.sales_completed += 1;
+            stats.total_volume += amount;
+        } else {
+            panic!("Unknown activity kind");
+        }
+        
+        env.storage().persistent().set(&(MUSEUM_STATS_KEY, museum_address), &stats);
+    }
+
+    /// Obtiene la actividad acumulada de un museo
+    pub fn get_museum_stats(env: Env, museum_address: Address) -> MuseumStats {
+        env.storage().persistent().get(&(MUSEUM_STATS_KEY, museum_address)).unwrap_or(MuseumStats {
+            nfts_minted: 0,
+            sales_completed: 0,
+            total_volume: 0,
+        })
+    }
+
+    /// Configura el contrato CulturalNFT del que salen las estadísticas de colección (solo admin)
+    pub fn set_cultural_nft(env: Env, cultural_nft: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        env.storage().instance().set(&CULTURAL_NFT_KEY, &cultural_nft);
+    }
+
+    /// Obtiene el contrato CulturalNFT configurado
+    pub fn get_cultural_nft(env: Env) -> Option<Address> {
+        env.storage().instance().get(&CULTURAL_NFT_KEY)
+    }
+
+    /// Recalcula la composición de la colección de un museo recorriendo sus tokens en
+    /// CulturalNFT por páginas; cualquiera puede invocarlo
+    ///
+    /// Cada llamada procesa hasta `limit` tokens (máximo `MAX_PAGE_SIZE`) a partir de `start`
+    /// y acumula el resultado parcial. Devuelve el `start` de la siguiente página, o `None`
+    /// cuando el recorrido termina y las estadísticas quedan publicadas en `get_collection_stats`.
+    /// Con `start = 0` se descarta cualquier recorrido en curso y se empieza de nuevo.
+    ///
+    /// Los tokens sensibles exponen el estado de conservación vacío y no cuentan en
+    /// `condition_counts`.
+    pub fn update_museum_collection_stats(env: Env, museum_address: Address, start: u32, limit: u32) -> Option<u32> {
+        if !env.storage().persistent().has(&(symbol_short!("MUSEUM"), museum_address.clone())) {
+            panic!("Museum not found");
+        }
+        let cultural_nft = Self::get_cultural_nft(env.clone()).unwrap_or_else(|| {
+            panic!("CulturalNFT not set");
+        });
+        let nft = CulturalNFTClient::new(&env, &cultural_nft);
+        
+        // Retomar el recorrido parcial; solo se acepta la página que le sigue
+        let scan_key = (COLLECTION_SCAN_KEY, museum_address.clone());
+        let mut stats = if start == 0 {
+            CollectionStats {
+                total_tokens: 0,
+                unesco_tokens: 0,
+                unesco_bps: 0,
+                original_tokens: 0,
+                reproduction_tokens: 0,
+                digital_tokens: 0,
+                condition_counts: Vec::new(&env),
+                updated_at: 0,
+            }
+        } else {
+            let (stats, next_start): (CollectionStats, u32) = env.storage().persistent().get(&scan_key).unwrap_or_else(|| {
+                panic!("No collection scan in progress");
+            });
+            if start != next_start {
+                panic!("Invalid cursor");
+            }
+            stats
+        };
+        
+        let unesco_tag = Symbol::new(&env, UNESCO_TAG);
+        let category_tags = CATEGORY_TAGS.map(|tag| Symbol::new(&env, tag));
+        let limit = limit.clamp(1, MAX_PAGE_SIZE);
+        let page = nft.get_tokens_by_museum(&museum_address, &start, &limit);
+        for token_id in page.iter() {
+            stats.total_tokens += 1;
+            
+            let tags = nft.get_tags(&token_id);
+            if tags.contains(&unesco_tag) {
+                stats.unesco_tokens += 1;
+            }
+            if tags.contains(&category_tags[0]) {
+                stats.original_tokens += 1;
+            }
+            if tags.contains(&category_tags[1]) {
+                stats.reproduction_tokens += 1;
+            }
+            if tags.contains(&category_tags[2]) {
+                stats.digital_tokens += 1;
+            }
+            
+            let condition = nft.get_cultural_metadata(&token_id).condition;
+            if condition.is_empty() {
+                continue;
+            }
+            match stats.condition_counts.iter().position(|(existing, _)| existing == condition) {
+                Some(i) => {
+                    let (existing, count) = stats.condition_counts.get(i as u32).unwrap();
+                    stats.condition_counts.set(i as u32, (existing, count + 1));
+                }
+                None => stats.condition_counts.push_back((condition, 1)),
+            }
+        }
+        
+        // Página completa: puede haber más tokens, así que se guarda el parcial
+        if page.len() == limit {
+            let next_start = start + page.len();
+            env.storage().persistent().set(&scan_key, &(stats, next_start));
+            return Some(next_start);
+        }
+        
+        stats.unesco_bps = if stats.total_tokens == 0 {
+            0
+        } else {
+            (stats.unesco_tokens as u64 * BPS_DENOMINATOR as u64 / stats.total_tokens as u64) as u32
+        };
+        stats.updated_at = env.ledger().timestamp();
+        env.storage().persistent().set(&(COLLECTION_STATS_KEY, museum_address), &stats);
+        env.storage().persistent().remove(&scan_key);
+        
+        None
+    }
+
+    /// Obtiene la última composición calculada de la colección de un museo
+    pub fn get_collection_stats(env: Env, museum_address: Address) -> CollectionStats {
+        env.storage().persistent().get(&(COLLECTION_STATS_KEY, museum_address)).unwrap_or(CollectionStats {
+            total_tokens: 0,
+            unesco_tokens: 0,
+            unesco_bps: 0,
+            original_tokens: 0,
+            reproduction_tokens: 0,
+            digital_tokens: 0,
+            condition_counts: Vec::new(&env),
+            updated_at: 0,
+        })
+    }
+
+    /// Obtiene los museos cuyo nombre o alguna entrada de metadatos coincide con la búsqueda
+    ///
+    /// `query_hash` se calcula con `hash_search_term`. Forma parte de la interfaz de búsqueda
+    /// que usa `RegistryFederation` para consultar varios registros.
+    pub fn get_museums_matching(env: Env, query_hash: u64, limit: u32) -> Vec<Address> {
+        let museums: Vec<Address> = env.storage().persistent().get(&MUSEUM_LIST_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        let limit = limit.min(MAX_PAGE_SIZE);
+        
+        let mut page = Vec::new(&env);
+        for museum_address in museums.iter() {
+            if page.len() >= limit {
+                break;
+            }
+            
+            let info = Self::get_museum_info(env.clone(), museum_address.clone());
+            let matches = Self::term_hash(&env, &info.name) == Some(query_hash)
+                || info.metadata.iter().any(|entry| Self::term_hash(&env, &entry) == Some(query_hash));
+            if matches {
+                page.push_back(museum_address);
+            }
+        }
+        
+        page
+    }
+
+    /// Calcula el hash de un término de búsqueda: los primeros 8 bytes (big-endian) del
+    /// SHA-256 de su texto UTF-8
+    pub fn hash_search_term(env: Env, term: String) -> u64 {
+        Self::term_hash(&env, &term).unwrap_or_else(|| {
+            panic!("Search term too long");
+        })
+    }
+
+    /// Hash de búsqueda de un texto (None si supera `MAX_SEARCH_TERM_LENGTH`)
+    fn term_hash(env: &Env, term: &String) -> Option<u64> {
+        let len = term.len() as usize;
+        if len > MAX_SEARCH_TERM_LENGTH {
+            return None;
+        }
+        
+        let mut buffer = [0u8; MAX_SEARCH_TERM_LENGTH];
+        term.copy_into_slice(&mut buffer[..len]);
+        let digest = env.crypto().sha256(&Bytes::from_slice(env, &buffer[..len])).to_array();
+        
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest[..8]);
+        Some(u64::from_be_bytes(prefix))
+    }
+
+    /// Agrega (o cambia el rol de) un curador del museo (solo el propio museo)
+    pub fn add_curator(env: Env, museum: Address, curator: Address, role: CuratorRole) {
+        // Verificar que el caller es el propio museo
+        museum.require_auth();
+        
+        if !env.storage().persistent().has(&(symbol_short!("MUSEUM"), museum.clone())) {
+            panic!("Museum not found");
+        }
+        if curator == museum {
+            panic!("Museum cannot be its own curator");
+        }
+        
+        let mut curators = Self::get_curators(env.clone(), museum.clone());
+        if !curators.contains(&curator) {
+            if curators.len() >= MAX_CURATORS {
+                panic!("Too many curators");
+            }
+            curators.push_back(curator.clone());
+            env.storage().persistent().set(&(CURATORS_KEY, museum.clone()), &curators);
+        }
+        
+        env.storage().persistent().set(&(CURATOR_KEY, museum, curator), &role);
+    }
+
+    /// Elimina un curador del museo (solo el propio museo)
+    pub fn remove_curator(env: Env, museum: Address, curator: Address) {
+        // Verificar que el caller es el propio museo
+        museum.require_auth();
+        
+        let mut curators = Self::get_curators(env.clone(), museum.clone());
+        let position = curators.first_index_of(&curator).unwrap_or_else(|| {
+            panic!("Curator not found");
+        });
+        curators.remove(position);
+        env.storage().persistent().set(&(CURATORS_KEY, museum.clone()), &curators);
+        env.storage().persistent().remove(&(CURATOR_KEY, museum, curator));
+    }
+
+    /// Obtiene el rol de un curador en un museo (None si no es curador)
+    pub fn is_curator(env: Env, museum: Address, curator: Address) -> Option<CuratorRole> {
+        env.storage().persistent().get(&(CURATOR_KEY, museum, curator))
+    }
+
+    /// Obtiene los curadores de un museo
+    pub fn get_curators(env: Env, museum: Address) -> Vec<Address> {
+        env.storage().persistent().get(&(CURATORS_KEY, museum)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Otorga el rol de verificador a una dirección (solo admin)
+    ///
+    /// Los verificadores pueden verificar, renovar y revocar museos y resolver solicitudes
+    /// de registro, pero no gestionar roles ni la configuración del registro.
+    pub fn grant_verifier(env: Env, who: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut verifiers = Self::get_verifiers(env.clone());
+        if verifiers.contains(&who) {
+            return;
+        }
+        if verifiers.len() >= MAX_VERIFIERS {
+            panic!("Too many verifiers");
+        }
+        verifiers.push_back(who.clone());
+        env.storage().instance().set(&VERIFIERS_KEY, &verifiers);
+        env.storage().persistent().set(&(VERIFIER_KEY, who), &true);
+    }
+
+    /// Retira el rol de verificador (solo admin)
+    pub fn revoke_verifier(env: Env, who: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut verifiers = Self::get_verifiers(env.clone());
+        let position = verifiers.first_index_of(&who).unwrap_or_else(|| {
+            panic!("Verifier not found");
+        });
+        verifiers.remove(position);
+        env.storage().instance().set(&VERIFIERS_KEY, &verifiers);
+        env.storage().persistent().remove(&(VERIFIER_KEY, who));
+    }
+
+    /// Verifica si una dirección tiene el rol de verificador
+    pub fn is_verifier(env: Env, who: Address) -> bool {
+        env.storage().persistent().get(&(VERIFIER_KEY, who)).unwrap_or(false)
+    }
+
+    /// Obtiene los verificadores registrados
+    pub fn get_verifiers(env: Env) -> Vec<Address> {
+        env.storage().instance().get(&VERIFIERS_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Obtiene el admin del contrato
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&ADMIN_KEY).unwrap()
+    }
+
+    /// Verifica si una dirección es el admin del contrato
+    pub fn is_admin(env: Env, who: Address) -> bool {
+        let admin: Option<Address> = env.storage().instance().get(&ADMIN_KEY);
+        admin == Some(who)
+    }
+
+    /// Propone un nuevo admin (solo admin); el cambio se hace efectivo cuando lo acepta
+    ///
+    /// Una nueva propuesta reemplaza a la anterior.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        if new_admin == admin {
+            panic!("Already admin");
+        }
+        
+        env.storage().instance().set(&PENDING_ADMIN_KEY, &new_admin);
+        env.events().publish((Symbol::new(&env, "admin_proposed"), admin), new_admin);
+    }
+
+    /// Acepta la propuesta y pasa a ser admin; el admin anterior pierde el acceso de inmediato
+    pub fn accept_admin(env: Env, new_admin: Address) {
+        // Verificar que el caller es el admin propuesto
+        new_admin.require_auth();
+        
+        let pending: Option<Address> = env.storage().instance().get(&PENDING_ADMIN_KEY);
+        if pending != Some(new_admin.clone()) {
+            panic!("Not the pending admin");
+        }
+        
+        let previous: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        env.storage().instance().set(&ADMIN_KEY, &new_admin);
+        env.storage().instance().remove(&PENDING_ADMIN_KEY);
+        env.events().publish((Symbol::new(&env, "admin_transferred"), previous), new_admin);
+    }
+
+    /// Obtiene el admin propuesto pendiente de aceptar
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&PENDING_ADMIN_KEY)
+    }
+
+    /// Actualiza información del museo (solo admin)
+    pub fn update_museum_info(
+        env: Env,
         museum_address: Address,
         name: Option<String>,
         description: Option<String>,
@@ -167,6 +1915,312 @@ impl MuseumRegistry {
         let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
         admin.require_auth();
         
+        Self::apply_museum_update(&env, museum_address.clone(), name, description, metadata);
+        Self::publish_museum_event(&env, "museum_updated", &museum_address, &admin, String::from_str(&env, ""));
+    }
+
+    /// Permite al museo actualizar su propia información
+    ///
+    /// Solo cambia nombre, descripción y metadatos; la verificación sigue siendo
+    /// exclusiva del admin.
+    pub fn update_own_museum_info(
+        env: Env,
+        museum_address: Address,
+        name: Option<String>,
+        description: Option<String>,
+        metadata: Option<Vec<String>>,
+    ) {
+        // Verificar que el caller es el propio museo
+        museum_address.require_auth();
+        
+        let mut fields = Vec::new(&env);
+        if name.is_some() {
+            fields.push_back(Symbol::new(&env, "name"));
+        }
+        if description.is_some() {
+            fields.push_back(Symbol::new(&env, "description"));
+        }
+        if metadata.is_some() {
+            fields.push_back(Symbol::new(&env, "metadata"));
+        }
+        
+        Self::apply_museum_update(&env, museum_address.clone(), name, description, metadata);
+        Self::record_museum_edit(&env, &museum_address, fields);
+        Self::publish_museum_event(&env, "museum_updated", &museum_address, &museum_address, String::from_str(&env, ""));
+    }
+
+    /// Actualiza el perfil estructurado del propio museo
+    pub fn update_museum_profile(env: Env, museum_address: Address, profile: MuseumProfile) {
+        // Verificar que el caller es el propio museo
+        museum_address.require_auth();
+        
+        Self::store_profile(&env, &museum_address, &profile);
+        Self::record_museum_edit(&env, &museum_address, Vec::from_array(&env, [Symbol::new(&env, "profile")]));
+    }
+
+    /// Obtiene las ediciones que el museo hizo de su propia información (las del admin
+    /// no se registran aquí)
+    pub fn get_museum_edit_history(env: Env, museum_address: Address) -> Vec<MuseumEdit> {
+        env.storage().persistent().get(&(EDIT_HISTORY_KEY, museum_address)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Asigna el perfil estructurado de un museo registrado (solo admin), p. ej. para
+    /// migrar museos que solo tienen los metadatos heredados
+    pub fn set_museum_profile(env: Env, museum_address: Address, profile: MuseumProfile) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        Self::store_profile(&env, &museum_address, &profile);
+    }
+
+    /// Obtiene el perfil estructurado de un museo (None en museos registrados antes del
+    /// perfil: sus datos siguen en `get_museum_metadata`)
+    pub fn get_museum_profile(env: Env, museum_address: Address) -> Option<MuseumProfile> {
+        env.storage().persistent().get(&(PROFILE_KEY, museum_address))
+    }
+
+    /// Valida y guarda el perfil de un museo registrado
+    fn store_profile(env: &Env, museum_address: &Address, profile: &MuseumProfile) {
+        if !env.storage().persistent().has(&(symbol_short!("MUSEUM"), museum_address.clone())) {
+            panic!("Museum not found");
+        }
+        Self::validate_profile(env, profile);
+        
+        env.storage().persistent().set(&(PROFILE_KEY, museum_address.clone()), profile);
+    }
+
+    /// Verifica que el perfil tiene país, un año de fundación plausible y pocas categorías
+    fn validate_profile(env: &Env, profile: &MuseumProfile) {
+        if profile.country == Symbol::new(env, "") {
+            panic!("Country required");
+        }
+        
+        let current_year = 1970 + (env.ledger().timestamp() / SECONDS_PER_YEAR) as u32;
+        if profile.founded_year < MIN_FOUNDED_YEAR || profile.founded_year > current_year {
+            panic!("Invalid founded year");
+        }
+        
+        if profile.categories.len() > MAX_PROFILE_CATEGORIES {
+            panic!("Too many categories");
+        }
+    }
+
+    /// Marca un museo activo como suspendido y guarda el motivo
+    fn apply_suspension(env: &Env, caller: &Address, museum_address: &Address, reason: String) {
+        let museum_storage_key = (symbol_short!("MUSEUM"), museum_address.clone());
+        let mut museum_info = Self::get_museum_info(env.clone(), museum_address.clone());
+        
+        museum_info.status = MuseumStatus::Suspended;
+        env.storage().persistent().set(&museum_storage_key, &museum_info);
+        env.storage().persistent().set(&(SUSPENSION_KEY, museum_address.clone()), &reason);
+        
+        Self::publish_museum_event(env, "museum_suspended", museum_address, caller, reason);
+    }
+
+    /// Publica un evento del registro con el museo en los topics y el nombre vigente en el payload
+    fn publish_museum_event(env: &Env, event: &str, museum_address: &Address, actor: &Address, details: String) {
+        let museum_info: MuseumInfo = env.storage().persistent().get(&(symbol_short!("MUSEUM"), museum_address.clone())).unwrap();
+        
+        env.events().publish(
+            (Symbol::new(env, event), museum_address.clone()),
+            MuseumEvent {
+                name: museum_info.name,
+                actor: actor.clone(),
+                timestamp: env.ledger().timestamp(),
+                details,
+            },
+        );
+    }
+
+    /// Agrega un cambio de verificación al historial del museo
+    fn record_verification_change(env: &Env, museum_address: &Address, verified: bool, reason: String) {
+        let history_key = (VERIFICATION_HISTORY_KEY, museum_address.clone());
+        let mut history: Vec<VerificationChange> = env.storage().persistent().get(&history_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        history.push_back(VerificationChange {
+            verified,
+            reason,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&history_key, &history);
+    }
+
+    /// Agrega una edición propia al historial del museo, descartando la más antigua al
+    /// superar `MAX_EDIT_HISTORY`
+    fn record_museum_edit(env: &Env, museum_address: &Address, fields: Vec<Symbol>) {
+        let history_key = (EDIT_HISTORY_KEY, museum_address.clone());
+        let mut history: Vec<MuseumEdit> = env.storage().persistent().get(&history_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        history.push_back(MuseumEdit {
+            editor: museum_address.clone(),
+            fields,
+            timestamp: env.ledger().timestamp(),
+        });
+        if history.len() > MAX_EDIT_HISTORY {
+            history.pop_front();
+        }
+        env.storage().persistent().set(&history_key, &history);
+    }
+
+    /// Registra una migración pendiente de `old` a `new`, reemplazando la anterior de `old`
+    fn open_migration(env: &Env, old: &Address, new: &Address, actor: &Address, delay: u64) {
+        if !env.storage().persistent().has(&(symbol_short!("MUSEUM"), old.clone())) {
+            panic!("Museum not found");
+        }
+        if old == new {
+            panic!("Same address");
+        }
+        Self::ensure_migration_target(env, new);
+        if env.storage().persistent().has(&(MIGRATION_KEY, new.clone())) {
+            panic!("Migration already pending");
+        }
+        
+        // Descartar la solicitud anterior del museo
+        if let Some(previous) = env.storage().persistent().get::<_, Address>(&(MIGRATION_SOURCE_KEY, old.clone())) {
+            env.storage().persistent().remove(&(MIGRATION_KEY, previous));
+        }
+        
+        let now = env.ledger().timestamp();
+        let migration = AddressMigration {
+            old_address: old.clone(),
+            new_address: new.clone(),
+            requested_at: now,
+            executable_at: now + delay,
+            admin_assisted: delay > 0,
+        };
+        env.storage().persistent().set(&(MIGRATION_KEY, new.clone()), &migration);
+        env.storage().persistent().set(&(MIGRATION_SOURCE_KEY, old.clone()), new);
+        
+        Self::publish_museum_event(env, "migration_requested", old, actor, new.to_string());
+    }
+
+    /// Verifica que una dirección puede recibir un museo migrado
+    fn ensure_migration_target(env: &Env, new: &Address) {
+        if env.storage().persistent().has(&(symbol_short!("MUSEUM"), new.clone()))
+            || env.storage().persistent().has(&(REGISTRATION_REQUEST_KEY, new.clone()))
+            || env.storage().persistent().has(&(MIGRATED_TO_KEY, new.clone()))
+        {
+            panic!("Address already in use");
+        }
+    }
+
+    /// Mueve una entrada de almacenamiento persistente a otra clave, si existe
+    fn move_entry<K: IntoVal<Env, Val>, L: IntoVal<Env, Val>>(env: &Env, from: &K, to: &L) {
+        let value: Option<Val> = env.storage().persistent().get(from);
+        if let Some(value) = value {
+            env.storage().persistent().set(to, &value);
+            env.storage().persistent().remove(from);
+        }
+    }
+
+    /// Reemplaza una dirección por otra en un índice de museos
+    fn replace_in_index<K: IntoVal<Env, Val>>(env: &Env, index_key: &K, old: &Address, new: &Address) {
+        let mut index: Vec<Address> = env.storage().persistent().get(index_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if let Some(position) = index.first_index_of(old) {
+            index.set(position, new.clone());
+            env.storage().persistent().set(index_key, &index);
+        }
+    }
+
+    /// Verifica que el caller es el admin o un verificador
+    fn ensure_verifier(env: &Env, caller: &Address) {
+        caller.require_auth();
+        
+        if !Self::is_admin(env.clone(), caller.clone()) && !Self::is_verifier(env.clone(), caller.clone()) {
+            panic!("Not admin or verifier");
+        }
+    }
+
+    /// Liquida la comisión en custodia de una solicitud, si la hubo: al aprobarla se retiene
+    /// completa; al rechazarla se devuelve la parte configurada y se retiene el resto
+    fn settle_registration_fee(env: &Env, museum_address: &Address, approved: bool) {
+        let paid_key = (REGISTRATION_FEE_PAID_KEY, museum_address.clone());
+        let fee: RegistrationFee = match env.storage().persistent().get(&paid_key) {
+            Some(fee) => fee,
+            None => return,
+        };
+        env.storage().persistent().remove(&paid_key);
+        
+        let refund = if approved {
+            0
+        } else {
+            fee.amount * Self::get_registration_refund_bps(env.clone()) as i128 / BPS_DENOMINATOR as i128
+        };
+        if refund > 0 {
+            token::Client::new(env, &fee.token).transfer(&env.current_contract_address(), museum_address, &refund);
+        }
+        
+        let retained = fee.amount - refund;
+        if retained > 0 {
+            let collected_key = (COLLECTED_FEES_KEY, fee.token);
+            let collected: i128 = env.storage().persistent().get(&collected_key).unwrap_or(0);
+            env.storage().persistent().set(&collected_key, &(collected + retained));
+        }
+    }
+
+    /// Saca de la cola las solicitudes de registro vencidas y liquida su comisión como un
+    /// rechazo; retorna la cola depurada
+    fn prune_expired_registrations(env: &Env) -> Vec<Address> {
+        let pending: Vec<Address> = env.storage().persistent().get(&PENDING_REGISTRATIONS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        let now = env.ledger().timestamp();
+        
+        let mut remaining = Vec::new(env);
+        for museum_address in pending.iter() {
+            let request_key = (REGISTRATION_REQUEST_KEY, museum_address.clone());
+            let request: RegistrationRequest = env.storage().persistent().get(&request_key).unwrap();
+            if now < request.expires_at {
+                remaining.push_back(museum_address);
+                continue;
+            }
+            
+            env.storage().persistent().remove(&request_key);
+            env.storage().persistent().remove(&(REGISTRATION_PROFILE_KEY, museum_address.clone()));
+            Self::settle_registration_fee(env, &museum_address, false);
+        }
+        
+        if remaining.len() != pending.len() {
+            env.storage().persistent().set(&PENDING_REGISTRATIONS_KEY, &remaining);
+        }
+        remaining
+    }
+
+    /// Retira una solicitud de registro de la cola de pendientes
+    fn take_registration_request(env: &Env, museum_address: &Address) -> RegistrationRequest {
+        let request_key = (REGISTRATION_REQUEST_KEY, museum_address.clone());
+        let request: RegistrationRequest = env.storage().persistent().get(&request_key).unwrap_or_else(|| {
+            panic!("Registration request not found");
+        });
+        env.storage().persistent().remove(&request_key);
+        env.storage().persistent().remove(&(REGISTRATION_PROFILE_KEY, museum_address.clone()));
+        
+        let mut pending: Vec<Address> = env.storage().persistent().get(&PENDING_REGISTRATIONS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if let Some(position) = pending.first_index_of(museum_address) {
+            pending.remove(position);
+        }
+        env.storage().persistent().set(&PENDING_REGISTRATIONS_KEY, &pending);
+        
+        request
+    }
+
+    /// Aplica los campos proporcionados a la información del museo
+    fn apply_museum_update(
+        env: &Env,
+        museum_address: Address,
+        name: Option<String>,
+        description: Option<String>,
+        metadata: Option<Vec<String>>,
+    ) {
         let museum_key = symbol_short!("MUSEUM");
         let museum_storage_key = (museum_key, museum_address);
         
@@ -189,3 +2243,6 @@ impl MuseumRegistry {
         env.storage().persistent().set(&museum_storage_key, &museum_info);
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,1021 @@
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::IntoVal;
+
+#[test]
+fn test_verifier_role_permissions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let museum = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    
+    // Solo el admin gestiona el rol
+    registry.grant_verifier(&verifier);
+    assert!(registry.is_verifier(&verifier));
+    assert!(!registry.is_verifier(&outsider));
+    assert_eq!(registry.get_verifiers().len(), 1);
+    
+    // El verificador resuelve solicitudes de registro
+    registry.request_registration(&museum, &String::from_str(&env, "Museo Larco"), &String::from_str(&env, "Arte precolombino"), &Vec::new(&env), &None);
+    assert!(registry.try_approve_registration(&outsider, &museum).is_err());
+    registry.approve_registration(&verifier, &museum);
+    assert_eq!(registry.get_total_museums(), 1);
+    
+    // El verificador verifica, renueva y revoca museos; un tercero no
+    assert!(registry.try_verify_museum(&outsider, &museum, &0).is_err());
+    registry.verify_museum(&verifier, &museum, &86400);
+    assert!(registry.is_verified(&museum));
+    registry.renew_verification(&verifier, &museum);
+    assert!(registry.try_revoke_verification(&outsider, &museum, &String::from_str(&env, "Sin motivo")).is_err());
+    registry.revoke_verification(&verifier, &museum, &String::from_str(&env, "Acreditación vencida"));
+    assert!(!registry.is_verified(&museum));
+    
+    // El admin conserva todas las acciones de verificación
+    registry.verify_museum(&admin, &museum, &0);
+    assert!(registry.is_verified(&museum));
+    
+    // La firma del verificador no basta para gestionar roles
+    let result = registry
+        .mock_auths(&[MockAuth {
+            address: &verifier,
+            invoke: &MockAuthInvoke {
+                contract: &registry_id,
+                fn_name: "grant_verifier",
+                args: (outsider.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_grant_verifier(&outsider);
+    assert!(result.is_err());
+    
+    // Al retirar el rol se pierden los permisos
+    registry.revoke_verifier(&verifier);
+    assert!(!registry.is_verifier(&verifier));
+    assert!(registry.try_verify_museum(&verifier, &museum, &0).is_err());
+}
+
+#[test]
+fn test_suspend_and_reinstate_museum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(&museum, &String::from_str(&env, "Museo de Sitio Pachacamac"), &String::from_str(&env, "Santuario prehispánico"), &Vec::new(&env), &None);
+    
+    // Activo sin verificar
+    assert_eq!(registry.get_museum_status(&museum), MuseumStatus::Active);
+    assert!(!registry.is_verified(&museum));
+    
+    // Activo y verificado
+    registry.verify_museum(&admin, &museum, &0);
+    assert!(registry.is_verified(&museum));
+    
+    // Suspendido: deja de contar como verificado sin perder la verificación
+    registry.suspend_museum(&admin, &museum, &String::from_str(&env, "Investigación de procedencia"));
+    assert_eq!(registry.get_museum_status(&museum), MuseumStatus::Suspended);
+    assert!(!registry.is_verified(&museum));
+    assert!(registry.get_museum_info(&museum).verified);
+    assert_eq!(registry.get_suspension_reason(&museum), Some(String::from_str(&env, "Investigación de procedencia")));
+    assert!(registry.try_suspend_museum(&admin, &museum, &String::from_str(&env, "Otra vez")).is_err());
+    
+    // Reinstaurado en una sola llamada
+    registry.reinstate_museum(&admin, &museum);
+    assert_eq!(registry.get_museum_status(&museum), MuseumStatus::Active);
+    assert!(registry.is_verified(&museum));
+    assert_eq!(registry.get_suspension_reason(&museum), None);
+    assert!(registry.try_reinstate_museum(&admin, &museum).is_err());
+}
+
+#[test]
+fn test_community_reports_rate_limit_and_suspension() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let other_reporter = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.grant_verifier(&verifier);
+    registry.register_museum(&museum, &String::from_str(&env, "Museo Nacional"), &String::from_str(&env, "Colección"), &Vec::new(&env), &None);
+    registry.verify_museum(&admin, &museum, &0);
+    
+    // Un reporte abierto por usuario y museo
+    let reason = String::from_str(&env, "No es el Museo Nacional");
+    let first_id = registry.report_museum(&reporter, &museum, &reason);
+    assert!(registry.try_report_museum(&reporter, &museum, &String::from_str(&env, "Otra vez")).is_err());
+    let second_id = registry.report_museum(&other_reporter, &museum, &String::from_str(&env, "Dirección falsa"));
+    assert_eq!(registry.get_report_count(&museum), 2);
+    
+    // Descartar libera al usuario para volver a reportar
+    registry.resolve_report(&verifier, &museum, &second_id, &ReportOutcome::Dismiss);
+    assert!(registry.try_resolve_report(&verifier, &museum, &second_id, &ReportOutcome::Uphold).is_err());
+    assert_eq!(registry.get_report_count(&museum), 1);
+    
+    // Confirmar con suspensión
+    registry.resolve_report(&verifier, &museum, &first_id, &ReportOutcome::UpholdAndSuspend);
+    assert_eq!(registry.get_report_count(&museum), 0);
+    assert_eq!(registry.get_museum_status(&museum), MuseumStatus::Suspended);
+    assert_eq!(registry.get_suspension_reason(&museum), Some(reason));
+    assert!(!registry.is_verified(&museum));
+    
+    let third_id = registry.report_museum(&reporter, &museum, &String::from_str(&env, "Sigue operando"));
+    let reports = registry.get_museum_reports(&museum, &0, &10);
+    assert_eq!(reports.len(), 3);
+    assert_eq!(reports.get(0).unwrap().status, ReportStatus::Upheld);
+    assert_eq!(reports.get(1).unwrap().status, ReportStatus::Dismissed);
+    assert_eq!(reports.get(2).unwrap().id, third_id);
+    assert_eq!(registry.get_museum_reports(&museum, &2, &10).len(), 1);
+}
+
+#[test]
+fn test_registry_events_for_register_then_verify() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let name = String::from_str(&env, "Museo Nacional de Arqueología");
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    registry.register_museum(&museum, &name, &String::from_str(&env, "Colección nacional"), &Vec::new(&env), &None);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    registry.verify_museum(&admin, &museum, &0);
+    
+    let empty = String::from_str(&env, "");
+    assert_eq!(
+        env.events().all(),
+        soroban_sdk::vec![
+            &env,
+            (
+                registry_id.clone(),
+                (Symbol::new(&env, "museum_registered"), museum.clone()).into_val(&env),
+                MuseumEvent { name: name.clone(), actor: admin.clone(), timestamp: 1_000, details: empty.clone() }.into_val(&env),
+            ),
+            (
+                registry_id.clone(),
+                (Symbol::new(&env, "museum_verified"), museum.clone()).into_val(&env),
+                MuseumEvent { name, actor: admin, timestamp: 2_000, details: empty }.into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_museum_profile_validation_and_legacy_fallback() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|ledger| ledger.timestamp = 1_700_000_000); // Noviembre de 2023
+    let admin = Address::generate(&env);
+    let legacy = Address::generate(&env);
+    let museum = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    
+    let profile = MuseumProfile {
+        country: Symbol::new(&env, "PE"),
+        city: String::from_str(&env, "Lima"),
+        website: String::from_str(&env, "https://www.museolarco.org"),
+        contact_email_hash: BytesN::from_array(&env, &[3u8; 32]),
+        founded_year: 1926,
+        categories: Vec::from_array(&env, [Symbol::new(&env, "archaeo")]),
+    };
+    
+    // Un museo heredado solo tiene el Vec de metadatos
+    let legacy_metadata = Vec::from_array(&env, [String::from_str(&env, "https://museo.pe"), String::from_str(&env, "Perú")]);
+    registry.register_museum(&legacy, &String::from_str(&env, "Museo Heredado"), &String::from_str(&env, "Registrado sin perfil"), &legacy_metadata, &None);
+    assert!(registry.get_museum_profile(&legacy).is_none());
+    assert_eq!(registry.get_museum_metadata(&legacy), legacy_metadata);
+    
+    // Migración por el admin
+    registry.set_museum_profile(&legacy, &profile);
+    assert_eq!(registry.get_museum_profile(&legacy).unwrap().city, profile.city);
+    assert_eq!(registry.get_museum_metadata(&legacy), legacy_metadata);
+    
+    // Perfiles inválidos
+    let mut no_country = profile.clone();
+    no_country.country = Symbol::new(&env, "");
+    let mut too_old = profile.clone();
+    too_old.founded_year = 999;
+    let mut future = profile.clone();
+    future.founded_year = 2030;
+    for invalid in [no_country, too_old, future] {
+        assert!(registry.try_register_museum(&museum, &String::from_str(&env, "Museo Larco"), &String::from_str(&env, "Arte precolombino"), &Vec::new(&env), &Some(invalid.clone())).is_err());
+        assert!(registry.try_update_museum_profile(&legacy, &invalid).is_err());
+    }
+    
+    // Perfil al registrarse y actualizado por el propio museo
+    registry.register_museum(&museum, &String::from_str(&env, "Museo Larco"), &String::from_str(&env, "Arte precolombino"), &Vec::new(&env), &Some(profile.clone()));
+    let mut updated = profile.clone();
+    updated.founded_year = 1927;
+    registry.update_museum_profile(&museum, &updated);
+    assert_eq!(registry.get_museum_profile(&museum).unwrap().founded_year, 1927);
+}
+
+#[test]
+fn test_registry_admin_two_step_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let other_museum = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    
+    // La propuesta no cambia el admin hasta que se acepta
+    registry.propose_admin(&new_admin);
+    assert_eq!(registry.get_pending_admin(), Some(new_admin.clone()));
+    assert!(registry.is_admin(&admin));
+    assert!(registry.try_accept_admin(&outsider).is_err());
+    
+    registry.accept_admin(&new_admin);
+    assert!(registry.is_admin(&new_admin));
+    assert!(!registry.is_admin(&admin));
+    assert_eq!(registry.get_pending_admin(), None);
+    
+    // El admin anterior ya no puede registrar museos
+    let result = registry
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &registry_id,
+                fn_name: "register_museum",
+                args: (
+                    museum.clone(),
+                    String::from_str(&env, "Museo Larco"),
+                    String::from_str(&env, "Arte precolombino"),
+                    Vec::<String>::new(&env),
+                    Option::<MuseumProfile>::None,
+                )
+                    .into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_register_museum(
+            &museum,
+            &String::from_str(&env, "Museo Larco"),
+            &String::from_str(&env, "Arte precolombino"),
+            &Vec::new(&env),
+            &None,
+        );
+    assert!(result.is_err());
+    
+    // ...ni gestionar verificadores
+    let result = registry
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &registry_id,
+                fn_name: "grant_verifier",
+                args: (outsider.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_grant_verifier(&outsider);
+    assert!(result.is_err());
+    
+    // El nuevo admin asume todas las funciones
+    env.mock_all_auths();
+    registry.register_museum(
+        &other_museum,
+        &String::from_str(&env, "Museo de Arte de Lima"),
+        &String::from_str(&env, "Arte peruano"),
+        &Vec::new(&env),
+        &None,
+    );
+    assert!(registry.try_verify_museum(&admin, &other_museum, &0).is_err());
+    registry.verify_museum(&new_admin, &other_museum, &0);
+    assert!(registry.is_verified(&other_museum));
+    registry.grant_verifier(&outsider);
+    assert!(registry.is_verifier(&outsider));
+}
+
+#[test]
+fn test_museum_address_migration_self_service() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let old = Address::generate(&env);
+    let new = Address::generate(&env);
+    let curator = Address::generate(&env);
+    let peer = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(&old, &String::from_str(&env, "Museo Larco"), &String::from_str(&env, "Arte precolombino"), &Vec::new(&env), &None);
+    registry.set_museum_tier(&old, &MuseumTier::National);
+    registry.set_museum_region(&old, &String::from_str(&env, "SA"));
+    registry.add_curator(&old, &curator, &CuratorRole::Registrar);
+    registry.set_peer_contract(&peer, &true);
+    registry.record_museum_activity(&peer, &old, &symbol_short!("sale"), &1_500);
+    
+    // La migración no tiene efecto hasta que la nueva dirección la confirma
+    registry.request_address_migration(&old, &new);
+    assert_eq!(registry.get_pending_migration(&old).unwrap().new_address, new);
+    assert!(registry.is_verified(&old));
+    assert!(registry.try_confirm_address_migration(&Address::generate(&env)).is_err());
+    
+    registry.confirm_address_migration(&new);
+    assert_eq!(registry.get_migrated_to(&old), Some(new.clone()));
+    assert!(registry.get_pending_migration(&old).is_none());
+    
+    // Todo el estado del museo es accesible en la nueva dirección
+    assert_eq!(registry.get_museum_name(&new), String::from_str(&env, "Museo Larco"));
+    assert!(registry.is_verified(&new));
+    assert_eq!(registry.get_museum_tier(&new), MuseumTier::National);
+    assert_eq!(registry.is_curator(&new, &curator), Some(CuratorRole::Registrar));
+    assert_eq!(registry.get_museum_stats(&new).total_volume, 1_500);
+    assert_eq!(registry.get_museums(&0, &10), Vec::from_array(&env, [new.clone()]));
+    assert_eq!(registry.get_museums_by_region(&String::from_str(&env, "SA"), &0, &10), Vec::from_array(&env, [new.clone()]));
+    assert_eq!(registry.get_total_museums(), 1);
+    
+    // La dirección anterior deja de estar verificada y no puede reutilizarse
+    assert!(!registry.is_verified(&old));
+    assert!(registry.try_get_museum_info(&old).is_err());
+    assert_eq!(registry.is_curator(&old, &curator), None);
+    assert!(registry
+        .try_register_museum(&old, &String::from_str(&env, "Impostor"), &String::from_str(&env, ""), &Vec::new(&env), &None)
+        .is_err());
+}
+
+#[test]
+fn test_museum_address_migration_admin_assisted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let old = Address::generate(&env);
+    let new = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(&old, &String::from_str(&env, "Museo de Arte de Lima"), &String::from_str(&env, "Arte peruano"), &Vec::new(&env), &None);
+    registry.verify_museum(&admin, &old, &0);
+    
+    // Solo el admin puede solicitar la migración sin la clave del museo
+    let result = registry
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &registry_id,
+                fn_name: "admin_request_address_migration",
+                args: (old.clone(), attacker.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_admin_request_address_migration(&old, &attacker);
+    assert!(result.is_err());
+    
+    // La solicitud del admin queda en revisión durante el plazo obligatorio
+    env.mock_all_auths();
+    env.ledger().with_mut(|ledger| ledger.timestamp = 1_000);
+    registry.admin_request_address_migration(&old, &new);
+    let migration = registry.get_pending_migration(&old).unwrap();
+    assert!(migration.admin_assisted);
+    assert_eq!(migration.executable_at, 1_000 + 7 * 86400);
+    assert!(registry.try_confirm_address_migration(&new).is_err());
+    
+    env.ledger().with_mut(|ledger| ledger.timestamp = 1_000 + 7 * 86400);
+    registry.confirm_address_migration(&new);
+    assert!(registry.is_verified(&new));
+    assert!(!registry.is_verified(&old));
+    assert_eq!(registry.get_migrated_to(&old), Some(new));
+}
+
+#[test]
+fn test_registration_fee_charge_refund_and_retain() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let approved = Address::generate(&env);
+    let rejected = Address::generate(&env);
+    let direct = Address::generate(&env);
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    let asset_admin = StellarAssetClient::new(&env, &asset.address());
+    asset_admin.mint(&approved, &1_000);
+    asset_admin.mint(&rejected, &1_000);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.set_registration_fee(&asset.address(), &400, &treasury);
+    registry.set_registration_refund_bps(&7_500);
+    assert_eq!(registry.get_registration_fee().unwrap().amount, 400);
+    
+    // La comisión se cobra al solicitar y queda en custodia
+    let name = String::from_str(&env, "Museo Nacional de Arqueología");
+    let description = String::from_str(&env, "Colecciones prehispánicas");
+    registry.request_registration(&approved, &name, &description, &Vec::new(&env), &None);
+    registry.request_registration(&rejected, &name, &description, &Vec::new(&env), &None);
+    assert_eq!(token.balance(&approved), 600);
+    assert_eq!(token.balance(&registry_id), 800);
+    
+    // Al aprobar se retiene completa
+    registry.approve_registration(&admin, &approved);
+    assert_eq!(token.balance(&approved), 600);
+    assert_eq!(registry.get_collected_registration_fees(&asset.address()), 400);
+    
+    // Al rechazar se devuelve la parte configurada
+    registry.reject_registration(&admin, &rejected, &String::from_str(&env, "Documentación incompleta"));
+    assert_eq!(token.balance(&rejected), 900);
+    assert_eq!(registry.get_collected_registration_fees(&asset.address()), 500);
+    
+    // El registro directo del admin no cobra comisión
+    registry.register_museum(&direct, &name, &description, &Vec::new(&env), &None);
+    assert_eq!(token.balance(&registry_id), 500);
+    
+    // Lo retenido se retira a la tesorería
+    assert_eq!(registry.withdraw_registration_fees(&asset.address()), 500);
+    assert_eq!(token.balance(&treasury), 500);
+    assert_eq!(token.balance(&registry_id), 0);
+    assert!(registry.try_withdraw_registration_fees(&asset.address()).is_err());
+}
+
+#[test]
+fn test_museum_bond_slash_and_withdrawal_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let museum = Address::generate(&env);
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    StellarAssetClient::new(&env, &asset.address()).mint(&museum, &1_000);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(
+        &museum,
+        &String::from_str(&env, "Museo del Oro"),
+        &String::from_str(&env, "Orfebrería prehispánica"),
+        &Vec::new(&env),
+        &None,
+    );
+    registry.set_bond_config(&asset.address(), &500, &treasury, &true);
+    
+    // Con fianza obligatoria, la verificación no cuenta hasta depositar el mínimo
+    registry.verify_museum(&admin, &museum, &0);
+    assert!(!registry.is_verified(&museum));
+    assert!(registry.try_post_bond(&museum, &asset.address(), &400).is_err());
+    registry.post_bond(&museum, &asset.address(), &600);
+    assert!(registry.is_verified(&museum));
+    assert_eq!(token.balance(&registry_id), 600);
+    
+    // Un museo verificado no puede retirar la fianza
+    assert!(registry.try_withdraw_bond(&museum).is_err());
+    
+    // La ejecución va a la tesorería y queda en el historial
+    let reason = String::from_str(&env, "Artefacto fraudulento");
+    assert!(registry.try_slash_bond(&admin, &museum, &700, &reason).is_err());
+    registry.slash_bond(&admin, &museum, &250, &reason);
+    assert_eq!(token.balance(&treasury), 250);
+    assert_eq!(registry.get_bond(&museum).unwrap().amount, 350);
+    let slashes = registry.get_bond_slashes(&museum);
+    assert_eq!(slashes.len(), 1);
+    assert_eq!(slashes.get(0).unwrap().amount, 250);
+    assert_eq!(slashes.get(0).unwrap().slashed_by, admin);
+    assert!(!registry.is_verified(&museum));
+    
+    // Tras renunciar a la verificación, la fianza queda bloqueada durante la espera
+    registry.renounce_verification(&museum);
+    assert!(registry.try_withdraw_bond(&museum).is_err());
+    env.ledger().with_mut(|li| li.timestamp += 30 * 86400);
+    assert_eq!(registry.withdraw_bond(&museum), 350);
+    assert_eq!(token.balance(&museum), 750);
+    assert!(registry.get_bond(&museum).is_none());
+}
+
+#[test]
+fn test_museum_self_edits_and_audit_trail() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(
+        &museum,
+        &String::from_str(&env, "Museo de Arte de Lmia"),
+        &String::from_str(&env, "Arte peruano"),
+        &Vec::new(&env),
+        &None,
+    );
+    
+    // El museo corrige su propio nombre sin pasar por el admin
+    registry.update_own_museum_info(&museum, &Some(String::from_str(&env, "Museo de Arte de Lima")), &None, &None);
+    assert_eq!(registry.get_museum_info(&museum).name, String::from_str(&env, "Museo de Arte de Lima"));
+    
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    registry.update_museum_profile(&museum, &MuseumProfile {
+        country: Symbol::new(&env, "PE"),
+        city: String::from_str(&env, "Lima"),
+        website: String::from_str(&env, "https://mali.pe"),
+        contact_email_hash: BytesN::from_array(&env, &[5u8; 32]),
+        founded_year: 1959,
+        categories: Vec::from_array(&env, [Symbol::new(&env, "fine_art")]),
+    });
+    
+    // La verificación sigue siendo exclusiva del admin y los verificadores
+    assert!(registry.try_verify_museum(&museum, &museum, &0).is_err());
+    assert!(!registry.is_verified(&museum));
+    
+    // El admin conserva la edición, sin entrar al historial de ediciones propias
+    registry.update_museum_info(&museum, &None, &Some(String::from_str(&env, "Arte peruano de tres milenios")), &None);
+    
+    let history = registry.get_museum_edit_history(&museum);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap(), MuseumEdit {
+        editor: museum.clone(),
+        fields: Vec::from_array(&env, [Symbol::new(&env, "name")]),
+        timestamp: 1_000,
+    });
+    assert_eq!(history.get(1).unwrap().fields, Vec::from_array(&env, [Symbol::new(&env, "profile")]));
+    assert_eq!(history.get(1).unwrap().timestamp, 2_000);
+}
+
+#[test]
+fn test_museums_registered_between() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    
+    // Un museo por día: 1_000, 87_400, 173_800 y 260_200
+    let mut museums = std::vec::Vec::new();
+    for day in 0..4u64 {
+        env.ledger().with_mut(|li| li.timestamp = 1_000 + day * 86400);
+        let museum = Address::generate(&env);
+        registry.register_museum(
+            &museum,
+            &String::from_str(&env, "Museo regional"),
+            &String::from_str(&env, "Historia local"),
+            &Vec::new(&env),
+            &None,
+        );
+        museums.push(museum);
+    }
+    
+    // Los extremos del rango son inclusivos
+    let page = registry.get_museums_registered_between(&87_400, &173_800, &0, &10);
+    assert_eq!(page, Vec::from_array(&env, [museums[1].clone(), museums[2].clone()]));
+    
+    // La paginación se aplica sobre los museos dentro del rango
+    let page = registry.get_museums_registered_between(&0, &u64::MAX, &1, &2);
+    assert_eq!(page, Vec::from_array(&env, [museums[1].clone(), museums[2].clone()]));
+    
+    assert!(registry.get_museums_registered_between(&1_001, &87_399, &0, &10).is_empty());
+}
+
+#[test]
+fn test_validator_co_signature_verifies_museum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let icom = Address::generate(&env);
+    let museum = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(
+        &museum,
+        &String::from_str(&env, "Museo Comunitario de Pisac"),
+        &String::from_str(&env, "Textiles andinos"),
+        &Vec::new(&env),
+        &None,
+    );
+    assert!(!registry.is_verified(&museum));
+    
+    // Solo el admin registra validadores, y solo un validador registrado co-firma
+    let attestation = String::from_str(&env, "ICOM-PE-2024-031");
+    let organization = String::from_str(&env, "ICOM");
+    let result = registry
+        .mock_auths(&[MockAuth {
+            address: &icom,
+            invoke: &MockAuthInvoke {
+                contract: &registry_id,
+                fn_name: "register_validator",
+                args: (icom.clone(), organization.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_register_validator(&icom, &organization);
+    assert!(result.is_err());
+    assert!(registry.try_co_sign_verification(&icom, &museum, &attestation).is_err());
+    registry.register_validator(&icom, &organization);
+    assert_eq!(registry.get_validator(&icom), Some(String::from_str(&env, "ICOM")));
+    
+    // La co-firma basta para que un museo no verificado pase is_verified
+    registry.co_sign_verification(&icom, &museum, &attestation);
+    assert!(registry.is_verified(&museum));
+    assert_eq!(registry.get_co_signatures(&museum), Vec::from_array(&env, [(icom.clone(), attestation)]));
+    
+    // Al retirar al validador su co-firma deja de contar
+    registry.remove_validator(&icom);
+    assert!(!registry.is_verified(&museum));
+}
+
+#[test]
+fn test_update_own_museum_info_cannot_self_verify() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(
+        &museum,
+        &String::from_str(&env, "Museo Nacional de Arqueología"),
+        &String::from_str(&env, "Colección"),
+        &Vec::new(&env),
+        &None,
+    );
+    
+    // Con su propia firma el museo cambia su descripción
+    let description = Some(String::from_str(&env, "Arqueología, antropología e historia del Perú"));
+    registry
+        .mock_auths(&[MockAuth {
+            address: &museum,
+            invoke: &MockAuthInvoke {
+                contract: &registry_id,
+                fn_name: "update_own_museum_info",
+                args: (museum.clone(), None::<String>, description.clone(), None::<Vec<String>>).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .update_own_museum_info(&museum, &None, &description, &None);
+    assert_eq!(registry.get_museum_info(&museum).description, description.clone().unwrap());
+    
+    // La firma de un tercero no alcanza para editar el museo
+    let result = registry
+        .mock_auths(&[MockAuth {
+            address: &outsider,
+            invoke: &MockAuthInvoke {
+                contract: &registry_id,
+                fn_name: "update_own_museum_info",
+                args: (museum.clone(), None::<String>, description.clone(), None::<Vec<String>>).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_update_own_museum_info(&museum, &None, &description, &None);
+    assert!(result.is_err());
+    
+    // Ni siquiera con su propia firma el museo puede verificarse
+    let result = registry
+        .mock_auths(&[MockAuth {
+            address: &museum,
+            invoke: &MockAuthInvoke {
+                contract: &registry_id,
+                fn_name: "verify_museum",
+                args: (museum.clone(), museum.clone(), 0u64).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_verify_museum(&museum, &museum, &0);
+    assert!(result.is_err());
+    assert!(!registry.is_verified(&museum));
+    assert!(!registry.get_museum_info(&museum).verified);
+}
+
+#[test]
+fn test_museum_regions_index_and_counts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    
+    let mut museums = std::vec::Vec::new();
+    for _ in 0..3 {
+        let museum = Address::generate(&env);
+        registry.register_museum(&museum, &String::from_str(&env, "Museo"), &String::from_str(&env, "Colección"), &Vec::new(&env), &None);
+        museums.push(museum);
+    }
+    let sa = String::from_str(&env, "SA");
+    let eu = String::from_str(&env, "EU");
+    
+    // Solo códigos estándar y museos registrados
+    assert!(registry.try_set_museum_region(&museums[0], &String::from_str(&env, "LATAM")).is_err());
+    assert!(registry.try_set_museum_region(&Address::generate(&env), &sa).is_err());
+    
+    registry.set_museum_region(&museums[0], &sa);
+    registry.set_museum_region(&museums[1], &sa);
+    registry.set_museum_region(&museums[2], &eu);
+    assert_eq!(registry.get_museum_region(&museums[0]), Some(sa.clone()));
+    assert_eq!(registry.get_region_museum_count(&sa), 2);
+    assert_eq!(registry.get_region_museum_count(&eu), 1);
+    assert_eq!(registry.get_region_museum_count(&String::from_str(&env, "OC")), 0);
+    assert_eq!(registry.get_museums_by_region(&sa, &1, &10), Vec::from_array(&env, [museums[1].clone()]));
+    
+    // Cambiar de región mueve al museo entre índices; repetir la misma no lo duplica
+    registry.set_museum_region(&museums[1], &eu);
+    registry.set_museum_region(&museums[1], &eu);
+    assert_eq!(registry.get_museums_by_region(&sa, &0, &10), Vec::from_array(&env, [museums[0].clone()]));
+    assert_eq!(registry.get_museums_by_region(&eu, &0, &10), Vec::from_array(&env, [museums[2].clone(), museums[1].clone()]));
+    assert_eq!(registry.get_region_museum_count(&eu), 2);
+}
+
+#[test]
+fn test_verification_expiry_and_renewal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let icom = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let permanent = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    for (address, name) in [(&museum, "Museo de Arte de Lima"), (&permanent, "Museo Larco")] {
+        registry.register_museum(address, &String::from_str(&env, name), &String::from_str(&env, "Arte"), &Vec::new(&env), &None);
+    }
+    
+    // Sin período de vigencia la verificación no expira ni se puede renovar
+    registry.verify_museum(&admin, &permanent, &0);
+    assert_eq!(registry.get_verification_expiry(&permanent), None);
+    assert!(registry.try_renew_verification(&admin, &permanent).is_err());
+    
+    registry.verify_museum(&admin, &museum, &86_400);
+    assert_eq!(registry.get_verification_expiry(&museum), Some(87_400));
+    
+    // Una co-firma no mantiene verificado a un museo cuya verificación expiró
+    registry.register_validator(&icom, &String::from_str(&env, "ICOM"));
+    registry.co_sign_verification(&icom, &museum, &String::from_str(&env, "ICOM-PE-2024-031"));
+    env.ledger().with_mut(|li| li.timestamp = 87_399);
+    assert!(registry.is_verified(&museum));
+    env.ledger().with_mut(|li| li.timestamp = 87_400);
+    assert!(!registry.is_verified(&museum));
+    assert!(registry.is_verified(&permanent));
+    
+    // Solo admin o verificador renuevan, por otro período completo desde ahora
+    let stranger = Address::generate(&env);
+    assert!(registry.try_renew_verification(&stranger, &museum).is_err());
+    registry.renew_verification(&admin, &museum);
+    assert_eq!(registry.get_verification_expiry(&museum), Some(87_400 + 86_400));
+    assert!(registry.is_verified(&museum));
+}
+
+#[test]
+fn test_registration_requests_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let approved = Address::generate(&env);
+    let rejected = Address::generate(&env);
+    let stale = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.set_registration_ttl(&86_400);
+    let name = String::from_str(&env, "Museo de Arte de Lima");
+    let description = String::from_str(&env, "Arte peruano");
+    
+    registry.request_registration(&stale, &name, &description, &Vec::new(&env), &None);
+    env.ledger().with_mut(|li| li.timestamp = 50_000);
+    registry.request_registration(&approved, &name, &description, &Vec::new(&env), &None);
+    registry.request_registration(&rejected, &name, &description, &Vec::new(&env), &None);
+    assert_eq!(registry.get_pending_registrations(&0, &10).len(), 3);
+    
+    // Una solicitud vigente no se duplica
+    assert!(registry.try_request_registration(&approved, &name, &description, &Vec::new(&env), &None).is_err());
+    
+    // Aprobar crea el museo y suma al contador; rechazar lo descarta
+    registry.approve_registration(&admin, &approved);
+    assert_eq!(registry.get_total_museums(), 1);
+    assert_eq!(registry.get_museum_name(&approved), name);
+    registry.reject_registration(&admin, &rejected, &String::from_str(&env, "Documentación incompleta"));
+    assert!(registry.try_get_museum_info(&rejected).is_err());
+    assert!(registry.try_approve_registration(&admin, &rejected).is_err());
+    
+    // Un museo ya registrado no puede volver a solicitar
+    assert!(registry.try_request_registration(&approved, &name, &description, &Vec::new(&env), &None).is_err());
+    
+    // La solicitud vencida deja de listarse, no se aprueba y sale de la cola con la próxima solicitud
+    env.ledger().with_mut(|li| li.timestamp = 87_400);
+    assert!(registry.get_pending_registrations(&0, &10).is_empty());
+    assert!(registry.try_approve_registration(&admin, &stale).is_err());
+    registry.request_registration(&rejected, &name, &description, &Vec::new(&env), &None);
+    let pending = registry.get_pending_registrations(&0, &10);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get(0).unwrap().museum_address, rejected);
+    
+    // La cola de pendientes vigentes tiene un tope
+    env.budget().reset_unlimited();
+    for _ in 1..MAX_PENDING_REGISTRATIONS {
+        registry.request_registration(&Address::generate(&env), &name, &description, &Vec::new(&env), &None);
+    }
+    assert!(registry.try_request_registration(&Address::generate(&env), &name, &description, &Vec::new(&env), &None).is_err());
+}
+
+#[test]
+fn test_get_all_museum_data_matches_getters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let icom = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let archive = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    for (address, name) in [(&museum, "Museo de Arte de Lima"), (&archive, "Archivo Comunitario de Sarhua")] {
+        registry.register_museum(address, &String::from_str(&env, name), &String::from_str(&env, "Arte"), &Vec::new(&env), &None);
+    }
+    
+    registry.verify_museum(&admin, &museum, &86_400);
+    registry.set_museum_tier(&museum, &MuseumTier::National);
+    registry.set_museum_region(&museum, &String::from_str(&env, "SA"));
+    registry.register_validator(&icom, &String::from_str(&env, "ICOM"));
+    registry.co_sign_verification(&icom, &museum, &String::from_str(&env, "ICOM-PE-2024-031"));
+    
+    // Cada campo coincide con su getter individual
+    let data = registry.get_all_museum_data(&museum);
+    let info = registry.get_museum_info(&museum);
+    assert_eq!(data.info.name, info.name);
+    assert_eq!(data.info.registration_date, info.registration_date);
+    assert!(data.info.verified && info.verified);
+    assert!(data.info.status == info.status);
+    assert!(data.is_verified);
+    assert_eq!(data.tier, MuseumTier::National);
+    assert_eq!(data.verification_expiry, Some(87_400));
+    assert_eq!(data.region, Some(String::from_str(&env, "SA")));
+    assert_eq!(data.co_signatures, registry.get_co_signatures(&museum));
+    assert_eq!(data.co_signatures.len(), 1);
+    
+    // Un museo sin datos opcionales los devuelve vacíos
+    let data = registry.get_all_museum_data(&archive);
+    assert_eq!(data.info.name, String::from_str(&env, "Archivo Comunitario de Sarhua"));
+    assert!(!data.is_verified);
+    assert_eq!(data.tier, registry.get_museum_tier(&archive));
+    assert_eq!(data.verification_expiry, None);
+    assert_eq!(data.region, None);
+    assert!(data.co_signatures.is_empty());
+    
+    assert!(registry.try_get_all_museum_data(&Address::generate(&env)).is_err());
+}
+
+#[test]
+fn test_revoke_and_reverify_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let icom = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let name = String::from_str(&env, "Museo de Arte de Lima");
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(&museum, &name, &String::from_str(&env, "Arte peruano"), &Vec::new(&env), &None);
+    registry.register_validator(&icom, &String::from_str(&env, "ICOM"));
+    
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    registry.verify_museum(&admin, &museum, &0);
+    registry.co_sign_verification(&icom, &museum, &String::from_str(&env, "ICOM-PE-2024-031"));
+    
+    // Revocar exige un motivo y un admin o verificador
+    let reason = String::from_str(&env, "Licencia cancelada");
+    assert!(registry.try_revoke_verification(&admin, &museum, &String::from_str(&env, "")).is_err());
+    assert!(registry.try_revoke_verification(&museum, &museum, &reason).is_err());
+    
+    // La revocación es inmediata y también descarta las co-firmas
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    registry.revoke_verification(&admin, &museum, &reason);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        soroban_sdk::vec![
+            &env,
+            (
+                registry_id.clone(),
+                (Symbol::new(&env, "verification_revoked"), museum.clone()).into_val(&env),
+                MuseumEvent { name, actor: admin.clone(), timestamp: 2_000, details: reason.clone() }.into_val(&env),
+            ),
+        ]
+    );
+    assert!(!registry.is_verified(&museum));
+    assert!(!registry.get_museum_info(&museum).verified);
+    assert!(registry.get_co_signatures(&museum).is_empty());
+    assert_eq!(registry.get_museum_tier(&museum), MuseumTier::Unverified);
+    
+    // Volver a verificar agrega una tercera entrada al historial
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    registry.verify_museum(&admin, &museum, &0);
+    assert!(registry.is_verified(&museum));
+    
+    let history = registry.get_verification_history(&museum);
+    assert_eq!(history.len(), 3);
+    for (index, (verified, timestamp)) in [(true, 1_000u64), (false, 2_000), (true, 3_000)].into_iter().enumerate() {
+        let change = history.get(index as u32).unwrap();
+        assert_eq!(change.verified, verified);
+        assert_eq!(change.timestamp, timestamp);
+    }
+    assert_eq!(history.get(1).unwrap().reason, reason);
+}
+
+#[test]
+fn test_museum_tiers_and_is_verified_shim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let icom = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let legacy = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    for (address, name) in [(&museum, "Museo de Arte de Lima"), (&legacy, "Museo Larco")] {
+        registry.register_museum(address, &String::from_str(&env, name), &String::from_str(&env, "Arte"), &Vec::new(&env), &None);
+    }
+    assert_eq!(registry.get_museum_tier(&museum), MuseumTier::Unverified);
+    assert!(!registry.is_verified(&museum));
+    assert!(registry.try_set_museum_tier(&Address::generate(&env), &MuseumTier::National).is_err());
+    
+    // Solo Accredited y National cuentan como verificados, y el flag de MuseumInfo los sigue
+    for (tier, verified) in [
+        (MuseumTier::Community, false),
+        (MuseumTier::Accredited, true),
+        (MuseumTier::National, true),
+        (MuseumTier::Unverified, false),
+    ] {
+        registry.set_museum_tier(&museum, &tier);
+        assert_eq!(registry.get_museum_tier(&museum), tier);
+        assert_eq!(registry.is_verified(&museum), verified);
+        assert_eq!(registry.get_museum_info(&museum).verified, verified);
+    }
+    
+    // Bajar a Community descarta las co-firmas y las nuevas no lo verifican
+    registry.register_validator(&icom, &String::from_str(&env, "ICOM"));
+    registry.set_museum_tier(&museum, &MuseumTier::National);
+    registry.co_sign_verification(&icom, &museum, &String::from_str(&env, "ICOM-PE-2024-031"));
+    registry.set_museum_tier(&museum, &MuseumTier::Community);
+    assert!(registry.get_co_signatures(&museum).is_empty());
+    assert!(!registry.is_verified(&museum));
+    registry.co_sign_verification(&icom, &museum, &String::from_str(&env, "ICOM-PE-2024-032"));
+    assert!(!registry.is_verified(&museum));
+    
+    // verify_museum asigna Accredited sin rebajar a un museo National
+    registry.verify_museum(&admin, &legacy, &0);
+    assert_eq!(registry.get_museum_tier(&legacy), MuseumTier::Accredited);
+    registry.set_museum_tier(&legacy, &MuseumTier::National);
+    registry.verify_museum(&admin, &legacy, &0);
+    assert_eq!(registry.get_museum_tier(&legacy), MuseumTier::National);
+    assert!(registry.is_verified(&legacy));
+    
+    // Un museo verificado antes de existir los niveles se lee como Accredited
+    env.as_contract(&registry_id, || {
+        env.storage().persistent().remove(&(TIER_KEY, legacy.clone()));
+    });
+    assert_eq!(registry.get_museum_tier(&legacy), MuseumTier::Accredited);
+    assert!(registry.is_verified(&legacy));
+}
@@ -0,0 +1,143 @@
+#![no_std]
+
+use soroban_sdk::{contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, String, Vec, Symbol};
+
+/// Contrato de federación de registros de museos
+/// 
+/// Este contrato permite:
+/// - Agrupar varios despliegues de MuseumRegistry
+/// - Buscar museos en todos los registros con una sola consulta
+#[contract]
+pub struct RegistryFederation;
+
+// Claves de storage
+const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+const REGISTRIES_KEY: Symbol = symbol_short!("REGISTRS");
+
+// Máximo de registros federados
+const MAX_REGISTRIES: u32 = 10;
+
+// Tamaño máximo de página para consultas paginadas
+const MAX_PAGE_SIZE: u32 = 50;
+
+// Estructuras espejo de MuseumRegistry (deben coincidir en nombres y orden de campos)
+#[derive(Clone)]
+#[contracttype]
+pub struct MuseumInfo {
+    pub name: String,
+    pub description: String,
+    pub metadata: Vec<String>,
+    pub registration_date: u64,
+    pub verified: bool,
+    pub status: MuseumStatus,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[contracttype]
+pub enum MuseumStatus {
+    Active,
+    Suspended,
+    Removed,
+}
+
+// Interfaz de búsqueda que implementa cada registro federado
+#[contractclient(name = "MuseumRegistryClient")]
+pub trait SearchInterface {
+    fn get_museums_matching(env: Env, query_hash: u64, limit: u32) -> Vec<Address>;
+    fn get_museum_info(env: Env, museum_address: Address) -> MuseumInfo;
+}
+
+#[contractimpl]
+impl RegistryFederation {
+    /// Inicializa el contrato
+    pub fn initialize(env: Env, admin: Address) {
+        // Verificar que no esté ya inicializado
+        if env.storage().instance().has(&ADMIN_KEY) {
+            panic!("Contract already initialized");
+        }
+        
+        // Guardar admin
+        env.storage().instance().set(&ADMIN_KEY, &admin);
+    }
+
+    /// Agrega un registro a la federación (solo admin)
+    pub fn add_registry(env: Env, registry: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut registries = Self::get_registries(env.clone());
+        if registries.contains(&registry) {
+            panic!("Registry already added");
+        }
+        if registries.len() >= MAX_REGISTRIES {
+            panic!("Too many registries");
+        }
+        
+        registries.push_back(registry);
+        env.storage().instance().set(&REGISTRIES_KEY, &registries);
+    }
+
+    /// Quita un registro de la federación (solo admin)
+    pub fn remove_registry(env: Env, registry: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut registries = Self::get_registries(env.clone());
+        let index = registries.first_index_of(&registry).unwrap_or_else(|| {
+            panic!("Registry not found");
+        });
+        
+        registries.remove(index);
+        env.storage().instance().set(&REGISTRIES_KEY, &registries);
+    }
+
+    /// Obtiene los registros federados
+    pub fn get_registries(env: Env) -> Vec<Address> {
+        env.storage().instance().get(&REGISTRIES_KEY).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Busca museos en todos los registros federados
+    ///
+    /// Cada registro aporta hasta `limit / N` resultados (N = número de registros, mínimo 1
+    /// por registro). Un museo presente en varios registros aparece una sola vez, con la
+    /// información del primero que lo devuelve; los registros que fallan se omiten.
+    pub fn search_museums(env: Env, query_hash: u64, limit: u32) -> Vec<(Address, MuseumInfo)> {
+        let registries = Self::get_registries(env.clone());
+        let limit = limit.min(MAX_PAGE_SIZE);
+        
+        let mut results: Vec<(Address, MuseumInfo)> = Vec::new(&env);
+        if registries.is_empty() || limit == 0 {
+            return results;
+        }
+        let per_registry = (limit / registries.len()).max(1);
+        
+        let mut seen: Vec<Address> = Vec::new(&env);
+        for registry in registries.iter() {
+            let client = MuseumRegistryClient::new(&env, &registry);
+            let matches = match client.try_get_museums_matching(&query_hash, &per_registry) {
+                Ok(Ok(matches)) => matches,
+                _ => continue,
+            };
+            
+            for museum_address in matches.iter() {
+                if results.len() >= limit {
+                    return results;
+                }
+                if seen.contains(&museum_address) {
+                    continue;
+                }
+                
+                if let Ok(Ok(info)) = client.try_get_museum_info(&museum_address) {
+                    seen.push_back(museum_address.clone());
+                    results.push_back((museum_address, info));
+                }
+            }
+        }
+        
+        results
+    }
+}
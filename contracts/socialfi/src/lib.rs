@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec, Symbol};
+use soroban_sdk::{contract, contractclient, contractimpl, contracttype, symbol_short, Address, Bytes, Env, String, Vec, Symbol};
 
 /// Contrato para el sistema SocialFi de PermaMuseum
 /// 
@@ -19,6 +19,80 @@ const BADGES_KEY: Symbol = symbol_short!("BADGES");
 const REWARDS_KEY: Symbol = symbol_short!("REWARDS");
 const LEADERBOARD_KEY: Symbol = symbol_short!("LEADER");
 const ACTIVITY_KEY: Symbol = symbol_short!("ACTIVITY");
+const SPENDER_KEY: Symbol = symbol_short!("SPENDER");
+const FIRST_ACTIVITY_KEY: Symbol = symbol_short!("FIRST_ACT");
+const ANNIVERSARY_BADGE_KEY: Symbol = symbol_short!("ANNIV_BDG");
+const ANNIVERSARY_CLAIM_KEY: Symbol = symbol_short!("ANNIV_CLM");
+const CLAIM_DELEGATE_KEY: Symbol = symbol_short!("CLAIM_DL");
+const NOMINATION_KEY: Symbol = symbol_short!("NOMINATE");
+const NOMINATION_COUNT_KEY: Symbol = symbol_short!("NOM_CNT");
+const ACTIVE_NOMINATIONS_KEY: Symbol = symbol_short!("NOM_ACT");
+const NOMINATED_ARTWORK_KEY: Symbol = symbol_short!("NOM_ART");
+const NOMINATION_VOTES_KEY: Symbol = symbol_short!("NOM_VTN");
+const NOMINATION_VOTER_KEY: Symbol = symbol_short!("NOM_VTR");
+const ACTIVITY_TYPES_KEY: Symbol = symbol_short!("ACT_TYPES");
+const STREAK_KEY: Symbol = symbol_short!("STREAK");
+const REFERRER_KEY: Symbol = symbol_short!("REFERRER");
+const REFERRAL_COUNT_KEY: Symbol = symbol_short!("REF_CNT");
+const REPUTATION_WEIGHTS_KEY: Symbol = symbol_short!("REP_WGHT");
+const REPUTATION_SCORE_KEY: Symbol = symbol_short!("REP_SCR");
+const MAX_AWARD_KEY: Symbol = symbol_short!("MAX_AWARD");
+const ISSUER_KEY: Symbol = symbol_short!("ISSUER");
+const USER_BADGE_COUNT_KEY: Symbol = symbol_short!("USR_BDGS");
+const REDEMPTION_KEY: Symbol = symbol_short!("REDEEM");
+const REDEMPTION_COUNT_KEY: Symbol = symbol_short!("REDEEM_CN");
+const LIFETIME_POINTS_KEY: Symbol = symbol_short!("LIFE_PTS");
+const ACHIEVEMENT_NFT_CONTRACT_KEY: Symbol = symbol_short!("ACH_NFT");
+const ACHIEVEMENT_MINTED_KEY: Symbol = symbol_short!("ACH_MNT");
+const ACHIEVEMENT_COUNT_KEY: Symbol = symbol_short!("ACH_CNT");
+const BADGE_ISSUED_KEY: Symbol = symbol_short!("BDG_ISSUD");
+const ACTIVITY_LOG_KEY: Symbol = symbol_short!("ACT_LOG");
+const USER_REDEMPTIONS_KEY: Symbol = symbol_short!("USR_RDMS");
+const SENTIMENT_KEY: Symbol = symbol_short!("SENTMT");
+const SENTIMENT_SUM_KEY: Symbol = symbol_short!("SENT_SUM");
+const SENTIMENT_CATEGORIES_KEY: Symbol = symbol_short!("SENT_CATS");
+
+// Aniversarios: segundos por año y bonos de puntos por hito
+const SECONDS_PER_YEAR: u64 = 365 * 86400;
+const ONE_YEAR_BONUS: i128 = 100;
+const FIVE_YEAR_BONUS: i128 = 500;
+
+// Curaduría de obras destacadas: costo de nominar, vigencia y tamaño máximo del ranking
+const NOMINATION_COST: i128 = 10;
+const NOMINATION_DURATION: u64 = 30 * 86400;
+const MAX_FEATURED: u32 = 20;
+
+// Índice de sentimiento: costo por opinión, rango de puntuación (i32, Soroban no admite i8),
+// épocas semanales y escala del promedio (millonésimas)
+const SENTIMENT_COST: i128 = 5;
+const MIN_SENTIMENT_SCORE: i32 = -2;
+const MAX_SENTIMENT_SCORE: i32 = 2;
+const SECONDS_PER_WEEK: u64 = 7 * 86400;
+const SENTIMENT_SCALE: i64 = 1_000_000;
+const MAX_SENTIMENT_CATEGORIES: u32 = 100; // Categorías distintas por época
+const MAX_SENTIMENT_CATEGORY_LENGTH: u32 = 64;
+
+// Niveles: cada nivel requiere 100 puntos ganados en total (los canjes no restan nivel)
+const POINTS_PER_LEVEL: i128 = 100;
+
+// Niveles que otorgan un NFT conmemorativo de logro
+const ACHIEVEMENT_LEVELS: [u32; 3] = [10, 25, 50];
+
+// Actividades recientes conservadas por usuario para consulta y exportación
+const MAX_ACTIVITY_HISTORY: u32 = 50;
+
+// Longitud máxima de la referencia de entrega (hash o dirección cifrada)
+const MAX_DELIVERY_REF_LENGTH: u32 = 256;
+
+// Reputación: cada factor se normaliza a 0-100 y se pondera en basis points
+const BPS_DENOMINATOR: u32 = 10_000;
+const REPUTATION_CACHE_DURATION: u64 = 86400;
+const SECONDS_PER_DAY: u64 = 86400;
+const REPUTATION_POINTS_CAP: i128 = 1_000; // Puntos para el máximo del factor
+const REPUTATION_BADGES_CAP: u32 = 10;
+const REPUTATION_STREAK_CAP: u32 = 30; // Días consecutivos
+const REPUTATION_REFERRALS_CAP: u32 = 10;
+const REPUTATION_DIVERSITY_CAP: u32 = 5; // Tipos de actividad distintos
 
 // Estructura para insignias
 #[derive(Clone)]
@@ -30,6 +104,7 @@ pub struct Badge {
     pub points_required: i128,
     pub rarity: String, // "common", "rare", "epic", "legendary"
     pub category: String, // "collector", "curator", "explorer", "creator"
+    pub max_supply: u32, // Máximo de usuarios con la insignia; 0 = ilimitada
 }
 
 // Estructura para recompensas
@@ -43,6 +118,71 @@ pub struct Reward {
     pub available: bool,
     pub max_redemptions: u32,
     pub current_redemptions: u32,
+    pub required_badge: Option<u32>, // Solo canjeable por quienes tienen esta insignia
+}
+
+// Metadatos culturales tal como los recibe el contrato CulturalNFT
+#[derive(Clone)]
+#[contracttype]
+pub struct CulturalMetadata {
+    pub title: String,
+    pub artist: String,
+    pub period: String,
+    pub culture: String,
+    pub material: String,
+    pub dimensions: String,
+    pub condition: String,
+    pub significance: String,
+    pub museum_address: Address,
+    pub location: ArtifactLocation,
+    pub is_soulbound: bool,
+}
+
+// Ubicación física del artefacto (definida en CulturalNFT)
+#[derive(Clone)]
+#[contracttype]
+pub enum ArtifactLocation {
+    Unknown,
+    Coordinates(i64, i64),
+}
+
+// Registro de procedencia (definido en CulturalNFT)
+#[derive(Clone)]
+#[contracttype]
+pub struct PROVENANCERecord {
+    pub date: u64,
+    pub from: Address,
+    pub to: Address,
+    pub transaction_type: String,
+    pub notes: String,
+    pub declared_value: Option<i128>,
+}
+
+// Interfaz del contrato CulturalNFT usada para mintear NFTs de logro
+//
+// SocialFi debe ser el admin de la colección de logros para poder mintear.
+#[contractclient(name = "CulturalNFTClient")]
+pub trait CulturalNFTInterface {
+    fn mint_cultural_nft(
+        env: Env,
+        to: Address,
+        token_id: u32,
+        cultural_metadata: CulturalMetadata,
+        provenance: Vec<PROVENANCERecord>,
+    );
+}
+
+// Estructura para canjes de recompensas
+#[derive(Clone)]
+#[contracttype]
+pub struct Redemption {
+    pub id: u32,
+    pub user: Address,
+    pub reward_id: u32,
+    pub points_cost: i128,
+    pub delivery_ref: Option<Bytes>, // Hash/cifrado de la dirección de entrega, nunca texto plano
+    pub redeemed_at: u64,
+    pub fulfilled: bool,
 }
 
 // Estructura para actividad del usuario
@@ -67,6 +207,46 @@ pub struct ActivityRecord {
     pub description: String,
 }
 
+// Estructura con todos los datos guardados de un usuario (portabilidad de datos)
+#[derive(Clone)]
+#[contracttype]
+pub struct UserDataExport {
+    pub user: Address,
+    pub points_balance: i128,
+    pub lifetime_points: i128,
+    pub level: u32,
+    pub reputation_score: u32,
+    pub badges: Vec<u32>,
+    pub activity_history: Vec<ActivityRecord>, // Últimas `MAX_ACTIVITY_HISTORY`, de la más antigua a la más reciente
+    pub rewards_redeemed: Vec<u32>, // IDs de las recompensas canjeadas, en orden de canje
+    pub referral_count: u32,
+    pub streak: u32,
+    pub join_timestamp: Option<u64>, // Primera actividad registrada
+}
+
+// Estructura para nominaciones de obras destacadas
+#[derive(Clone)]
+#[contracttype]
+pub struct Nomination {
+    pub id: u32,
+    pub nominator: Address,
+    pub nft_contract: Address,
+    pub token_id: u32,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+// Pesos de los factores de reputación en basis points (deben sumar 10000)
+#[derive(Clone)]
+#[contracttype]
+pub struct ReputationWeights {
+    pub points: u32,
+    pub badges: u32,
+    pub streak: u32,
+    pub referrals: u32,
+    pub diversity: u32,
+}
+
 #[contractimpl]
 impl SocialFi {
     /// Inicializa el contrato
@@ -100,28 +280,68 @@ impl SocialFi {
             panic!("Points must be positive");
         }
         
-        // Obtener balance actual
-        let points_key = (POINTS_KEY, user.clone());
-        let current_balance: i128 = env.storage().persistent().get(&points_key).unwrap_or(0);
+        // Verificar que no se supera el máximo por llamada
+        if let Some(max_award) = Self::get_max_award_per_call(env.clone()) {
+            if points > max_award {
+                panic!("Award exceeds max points per call");
+            }
+        }
         
-        // Actualizar balance
-        let new_balance = current_balance + points;
-        env.storage().persistent().set(&points_key, &new_balance);
+        Self::credit_points(&env, &user, points, reason);
+    }
+
+    /// Configura el máximo de puntos que puede otorgar una sola llamada a award_points
+    /// (solo admin; 0 elimina el límite)
+    pub fn set_max_award_per_call(env: Env, max_award: i128) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
         
-        // Registrar actividad
-        let activity_record = ActivityRecord {
-            user: user.clone(),
-            activity_type: String::from_str(&env, "points_awarded"),
-            points_awarded: points,
-            timestamp: env.ledger().timestamp(),
-            description: reason,
-        };
+        if max_award < 0 {
+            panic!("Max award cannot be negative");
+        }
         
-        let activity_key = (ACTIVITY_KEY, user.clone(), env.ledger().timestamp());
-        env.storage().persistent().set(&activity_key, &activity_record);
+        if max_award == 0 {
+            env.storage().instance().remove(&MAX_AWARD_KEY);
+        } else {
+            env.storage().instance().set(&MAX_AWARD_KEY, &max_award);
+        }
+    }
+
+    /// Obtiene el máximo de puntos por llamada (None si no hay límite)
+    pub fn get_max_award_per_call(env: Env) -> Option<i128> {
+        env.storage().instance().get(&MAX_AWARD_KEY)
+    }
+
+    /// Configura la colección CulturalNFT donde se mintean los NFTs de logro (solo admin)
+    ///
+    /// Este contrato debe ser el admin de esa colección.
+    pub fn set_achievement_nft_contract(env: Env, nft_contract: Address) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
         
-        // Actualizar leaderboard
-        Self::update_leaderboard(env, user);
+        env.storage().instance().set(&ACHIEVEMENT_NFT_CONTRACT_KEY, &nft_contract);
+    }
+
+    /// Obtiene la colección de NFTs de logro configurada
+    pub fn get_achievement_nft_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&ACHIEVEMENT_NFT_CONTRACT_KEY)
+    }
+
+    /// Obtiene los puntos ganados en total por un usuario (sin descontar gastos)
+    pub fn get_lifetime_points(env: Env, user: Address) -> i128 {
+        env.storage().persistent().get(&(LIFETIME_POINTS_KEY, user)).unwrap_or(0)
+    }
+
+    /// Obtiene el nivel de un usuario según sus puntos ganados en total
+    pub fn get_user_level(env: Env, user: Address) -> u32 {
+        (Self::get_lifetime_points(env, user) / POINTS_PER_LEVEL) as u32
+    }
+
+    /// Verifica si el usuario ya recibió el NFT de logro de un nivel
+    pub fn has_achievement_nft(env: Env, user: Address, level: u32) -> bool {
+        env.storage().persistent().get(&(ACHIEVEMENT_MINTED_KEY, user, level)).unwrap_or(false)
     }
 
     /// Obtiene el balance de puntos de un usuario
@@ -141,34 +361,28 @@ impl SocialFi {
         let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
         admin.require_auth();
         
-        // Obtener insignias actuales del usuario
+        Self::grant_badge(&env, &user, badge_id);
+    }
+
+    /// Retira una insignia a un usuario (solo admin)
+    pub fn revoke_badge(env: Env, user: Address, badge_id: u32) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
         let badges_key = (BADGES_KEY, user.clone());
-        let mut user_badges: Vec<u32> = env.storage().persistent().get(&badges_key).unwrap_or_else(|| {
-            Vec::new(&env)
+        let mut user_badges = Self::get_user_badges(env.clone(), user.clone());
+        let index = user_badges.first_index_of(badge_id).unwrap_or_else(|| {
+            panic!("User does not have this badge");
         });
         
-        // Verificar que no tiene ya la insignia
-        for badge in user_badges.iter() {
-            if badge == badge_id {
-                panic!("User already has this badge");
-            }
-        }
-        
-        // Agregar insignia
-        user_badges.push_back(badge_id);
+        user_badges.remove(index);
         env.storage().persistent().set(&badges_key, &user_badges);
+        env.storage().persistent().set(&(USER_BADGE_COUNT_KEY, user), &user_badges.len());
         
-        // Registrar actividad
-        let activity_record = ActivityRecord {
-            user: user.clone(),
-            activity_type: String::from_str(&env, "badge_awarded"),
-            points_awarded: 0,
-            timestamp: env.ledger().timestamp(),
-            description: String::from_str(&env, "Badge awarded"),
-        };
-        
-        let activity_key = (ACTIVITY_KEY, user.clone(), env.ledger().timestamp());
-        env.storage().persistent().set(&activity_key, &activity_record);
+        // La insignia retirada vuelve a estar disponible
+        let issued = Self::get_badge_issued(env.clone(), badge_id);
+        env.storage().persistent().set(&(BADGE_ISSUED_KEY, badge_id), &issued.saturating_sub(1));
     }
 
     /// Obtiene las insignias de un usuario
@@ -179,7 +393,18 @@ impl SocialFi {
         })
     }
 
+    /// Obtiene el número de insignias de un usuario sin leer la lista completa
+    pub fn get_user_badge_count(env: Env, user: Address) -> u32 {
+        let count_key = (USER_BADGE_COUNT_KEY, user.clone());
+        env.storage().persistent().get(&count_key).unwrap_or_else(|| {
+            // Usuarios con insignias previas al contador
+            Self::get_user_badges(env.clone(), user).len()
+        })
+    }
+
     /// Crea una nueva insignia (solo admin)
+    ///
+    /// `max_supply` limita cuántos usuarios pueden tenerla a la vez (0 = ilimitada).
     pub fn create_badge(
         env: Env,
         name: String,
@@ -187,6 +412,7 @@ impl SocialFi {
         points_required: i128,
         rarity: String,
         category: String,
+        max_supply: u32,
     ) -> u32 {
         // Verificar que el caller es admin
         let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
@@ -206,6 +432,7 @@ impl SocialFi {
             points_required,
             rarity: rarity.clone(),
             category: category.clone(),
+            max_supply,
         };
         
         // Guardar insignia
@@ -223,6 +450,43 @@ impl SocialFi {
         })
     }
 
+    /// Obtiene cuántos usuarios tienen actualmente una insignia
+    pub fn get_badge_issued(env: Env, badge_id: u32) -> u32 {
+        env.storage().persistent().get(&(BADGE_ISSUED_KEY, badge_id)).unwrap_or(0)
+    }
+
+    /// Obtiene cuántas unidades de una insignia quedan por otorgar (None si es ilimitada)
+    pub fn get_badge_remaining_supply(env: Env, badge_id: u32) -> Option<u32> {
+        let badge = Self::get_badge_info(env.clone(), badge_id);
+        if badge.max_supply == 0 {
+            return None;
+        }
+        Some(badge.max_supply.saturating_sub(Self::get_badge_issued(env, badge_id)))
+    }
+
+    /// Obtiene el progreso hacia las insignias por puntos aún no obtenidas:
+    /// (insignia, puntos requeridos, puntos del usuario)
+    pub fn get_badge_progress(env: Env, user: Address) -> Vec<(u32, i128, i128)> {
+        let user_points = Self::get_points_balance(env.clone(), user.clone());
+        let user_badges = Self::get_user_badges(env.clone(), user);
+        
+        let mut progress = Vec::new(&env);
+        for badge_id in 1..=Self::get_total_badges(env.clone()) {
+            if user_badges.contains(badge_id) {
+                continue;
+            }
+            
+            let badge: Option<Badge> = env.storage().persistent().get(&(symbol_short!("BADGE_INF"), badge_id));
+            if let Some(badge) = badge {
+                if badge.points_required > 0 {
+                    progress.push_back((badge_id, badge.points_required, user_points));
+                }
+            }
+        }
+        
+        progress
+    }
+
     
     /// Crea una nueva recompensa (solo admin)
     pub fn create_reward(
@@ -231,6 +495,7 @@ impl SocialFi {
         description: String,
         points_cost: i128,
         max_redemptions: u32,
+        required_badge: Option<u32>,
     ) -> u32 {
         // Verificar que el caller es admin
         let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
@@ -251,6 +516,7 @@ impl SocialFi {
             available: true,
             max_redemptions,
             current_redemptions: 0,
+            required_badge,
         };
         
         // Guardar recompensa
@@ -268,15 +534,93 @@ impl SocialFi {
         })
     }
 
-    /// Canjea puntos por recompensas
+    /// Canjea puntos por recompensas; devuelve el ID del canje
+    ///
+    /// Para recompensas físicas, `delivery_ref` es una referencia a la dirección de entrega
+    /// (hash o cifrado generado fuera de la cadena) que el admin usa para el envío.
     pub fn redeem_points(
         env: Env,
         user: Address,
         reward_id: u32,
-    ) {
+        delivery_ref: Option<Bytes>,
+    ) -> u32 {
         // Verificar que el caller es el usuario
         user.require_auth();
         
+        if let Some(delivery_ref) = &delivery_ref {
+            if delivery_ref.is_empty() || delivery_ref.len() > MAX_DELIVERY_REF_LENGTH {
+                panic!("Invalid delivery reference");
+            }
+        }
+        
+        Self::redeem_reward(&env, &user, reward_id, delivery_ref)
+    }
+
+    /// Obtiene un canje de recompensa
+    pub fn get_redemption(env: Env, redemption_id: u32) -> Redemption {
+        env.storage().persistent().get(&(REDEMPTION_KEY, redemption_id)).unwrap_or_else(|| {
+            panic!("Redemption not found");
+        })
+    }
+
+    /// Marca un canje como entregado (solo admin)
+    pub fn fulfill_redemption(env: Env, redemption_id: u32) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let mut redemption = Self::get_redemption(env.clone(), redemption_id);
+        if redemption.fulfilled {
+            panic!("Redemption already fulfilled");
+        }
+        
+        redemption.fulfilled = true;
+        env.storage().persistent().set(&(REDEMPTION_KEY, redemption_id), &redemption);
+    }
+
+    /// Delega el canje de recompensas del usuario a otra cuenta (ej. un museo)
+    pub fn delegate_reward_claiming(env: Env, user: Address, delegate: Address) {
+        // Verificar que el caller es el usuario
+        user.require_auth();
+        
+        if delegate == user {
+            panic!("Cannot delegate to self");
+        }
+        
+        env.storage().persistent().set(&(CLAIM_DELEGATE_KEY, user), &delegate);
+    }
+
+    /// Revoca la delegación de canje de recompensas del usuario
+    pub fn revoke_reward_claiming(env: Env, user: Address) {
+        // Verificar que el caller es el usuario
+        user.require_auth();
+        
+        let delegate_key = (CLAIM_DELEGATE_KEY, user);
+        if !env.storage().persistent().has(&delegate_key) {
+            panic!("No reward claiming delegate");
+        }
+        env.storage().persistent().remove(&delegate_key);
+    }
+
+    /// Obtiene el delegado de canje de recompensas de un usuario
+    pub fn get_reward_claim_delegate(env: Env, user: Address) -> Option<Address> {
+        env.storage().persistent().get(&(CLAIM_DELEGATE_KEY, user))
+    }
+
+    /// Canjea una recompensa en nombre de un usuario que delegó el canje; devuelve el ID
+    /// del canje
+    pub fn claim_reward_for_user(env: Env, delegate: Address, user: Address, reward_id: u32) -> u32 {
+        // Verificar que el caller es el delegado del usuario
+        delegate.require_auth();
+        if Self::get_reward_claim_delegate(env.clone(), user.clone()) != Some(delegate) {
+            panic!("Not the reward claiming delegate");
+        }
+        
+        Self::redeem_reward(&env, &user, reward_id, None)
+    }
+
+    /// Descuenta los puntos del usuario y registra el canje de la recompensa
+    fn redeem_reward(env: &Env, user: &Address, reward_id: u32, delivery_ref: Option<Bytes>) -> u32 {
         // Obtener información de la recompensa
         let reward = Self::get_reward_info(env.clone(), reward_id);
         
@@ -290,6 +634,13 @@ impl SocialFi {
             panic!("Reward sold out");
         }
         
+        // Verificar que el usuario tiene la insignia exigida
+        if let Some(badge_id) = reward.required_badge {
+            if !Self::get_user_badges(env.clone(), user.clone()).contains(badge_id) {
+                panic!("Required badge not held");
+            }
+        }
+        
         // Verificar que el usuario tiene suficientes puntos
         let user_balance = Self::get_points_balance(env.clone(), user.clone());
         if user_balance < reward.points_cost {
@@ -307,17 +658,507 @@ impl SocialFi {
         let reward_key = (REWARDS_KEY, reward_id);
         env.storage().persistent().set(&reward_key, &updated_reward);
         
-        // Registrar actividad
-        let activity_record = ActivityRecord {
+        // Registrar el canje para su entrega
+        let redemption_id: u32 = env.storage().instance().get(&REDEMPTION_COUNT_KEY).unwrap_or(0) + 1;
+        env.storage().instance().set(&REDEMPTION_COUNT_KEY, &redemption_id);
+        let redemption = Redemption {
+            id: redemption_id,
             user: user.clone(),
-            activity_type: String::from_str(&env, "reward_redeemed"),
-            points_awarded: -reward.points_cost,
-            timestamp: env.ledger().timestamp(),
-            description: String::from_str(&env, "Redeemed reward"),
+            reward_id,
+            points_cost: reward.points_cost,
+            delivery_ref,
+            redeemed_at: env.ledger().timestamp(),
+            fulfilled: false,
         };
+        env.storage().persistent().set(&(REDEMPTION_KEY, redemption_id), &redemption);
         
-        let activity_key = (ACTIVITY_KEY, user.clone(), env.ledger().timestamp());
-        env.storage().persistent().set(&activity_key, &activity_record);
+        let user_redemptions_key = (USER_REDEMPTIONS_KEY, user.clone());
+        let mut user_redemptions: Vec<u32> = env.storage().persistent().get(&user_redemptions_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        user_redemptions.push_back(redemption_id);
+        env.storage().persistent().set(&user_redemptions_key, &user_redemptions);
+        
+        // Registrar actividad
+        Self::record_activity(env, user, "reward_redeemed", -reward.points_cost, String::from_str(env, "Redeemed reward"));
+        
+        redemption_id
+    }
+
+    /// Autoriza o desautoriza a un contrato para gastar puntos de usuarios (solo admin)
+    pub fn set_points_spender(env: Env, spender: Address, allowed: bool) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let spender_key = (SPENDER_KEY, spender);
+        if allowed {
+            env.storage().persistent().set(&spender_key, &true);
+        } else {
+            env.storage().persistent().remove(&spender_key);
+        }
+    }
+
+    /// Verifica si un contrato está autorizado para gastar puntos
+    pub fn is_points_spender(env: Env, spender: Address) -> bool {
+        let spender_key = (SPENDER_KEY, spender);
+        env.storage().persistent().get(&spender_key).unwrap_or(false)
+    }
+
+    /// Gasta puntos de un usuario desde un contrato autorizado (ej. descuentos del marketplace)
+    pub fn spend_points(
+        env: Env,
+        spender: Address,
+        user: Address,
+        points: i128,
+        reason: String,
+    ) {
+        // Verificar que el spender es un contrato autorizado
+        spender.require_auth();
+        if !Self::is_points_spender(env.clone(), spender) {
+            panic!("Spender not authorized");
+        }
+        
+        // Verificar que el usuario autoriza el gasto
+        user.require_auth();
+        
+        if points <= 0 {
+            panic!("Points must be positive");
+        }
+        
+        // Verificar que el usuario tiene suficientes puntos
+        let user_balance = Self::get_points_balance(env.clone(), user.clone());
+        if user_balance < points {
+            panic!("Insufficient points");
+        }
+        
+        // Descontar puntos
+        let points_key = (POINTS_KEY, user.clone());
+        env.storage().persistent().set(&points_key, &(user_balance - points));
+        
+        // Registrar actividad
+        Self::record_activity(&env, &user, "points_spent", -points, reason);
+    }
+
+    /// Autoriza o desautoriza a un contrato para otorgar puntos a usuarios (solo admin)
+    pub fn set_points_issuer(env: Env, issuer: Address, allowed: bool) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let issuer_key = (ISSUER_KEY, issuer);
+        if allowed {
+            env.storage().persistent().set(&issuer_key, &true);
+        } else {
+            env.storage().persistent().remove(&issuer_key);
+        }
+    }
+
+    /// Verifica si un contrato está autorizado para otorgar puntos
+    pub fn is_points_issuer(env: Env, issuer: Address) -> bool {
+        let issuer_key = (ISSUER_KEY, issuer);
+        env.storage().persistent().get(&issuer_key).unwrap_or(false)
+    }
+
+    /// Otorga puntos a un usuario desde un contrato autorizado (ej. recompensas por
+    /// creación y curaduría de CulturalNFT)
+    pub fn issue_points(
+        env: Env,
+        issuer: Address,
+        user: Address,
+        points: i128,
+        reason: String,
+    ) {
+        // Verificar que el issuer es un contrato autorizado
+        issuer.require_auth();
+        if !Self::is_points_issuer(env.clone(), issuer) {
+            panic!("Issuer not authorized");
+        }
+        
+        if points <= 0 {
+            panic!("Points must be positive");
+        }
+        
+        // Verificar que no se supera el máximo por llamada
+        if let Some(max_award) = Self::get_max_award_per_call(env.clone()) {
+            if points > max_award {
+                panic!("Award exceeds max points per call");
+            }
+        }
+        
+        Self::credit_points(&env, &user, points, reason);
+    }
+
+    /// Reclama las insignias de aniversario (1 y 5 años) alcanzadas por el usuario
+    ///
+    /// La antigüedad se mide desde la primera actividad registrada. Cada hito otorga
+    /// una insignia especial y puntos de bonificación, y solo puede reclamarse una vez.
+    pub fn claim_anniversary_badge(env: Env, user: Address) -> Vec<u32> {
+        // Verificar que el caller es el usuario
+        user.require_auth();
+        
+        let first_activity: u64 = env.storage().persistent().get(&(FIRST_ACTIVITY_KEY, user.clone())).unwrap_or_else(|| {
+            panic!("No activity recorded");
+        });
+        let elapsed = env.ledger().timestamp() - first_activity;
+        
+        let mut claimed = Vec::new(&env);
+        for (years, bonus) in [(1u32, ONE_YEAR_BONUS), (5u32, FIVE_YEAR_BONUS)] {
+            let claim_key = (ANNIVERSARY_CLAIM_KEY, user.clone(), years);
+            if elapsed < years as u64 * SECONDS_PER_YEAR || env.storage().persistent().has(&claim_key) {
+                continue;
+            }
+            
+            env.storage().persistent().set(&claim_key, &true);
+            
+            let badge_id = Self::anniversary_badge_id(&env, years);
+            Self::grant_badge(&env, &user, badge_id);
+            Self::credit_points(&env, &user, bonus, String::from_str(&env, "anniversary_bonus"));
+            claimed.push_back(badge_id);
+        }
+        
+        if claimed.is_empty() {
+            panic!("No anniversary milestone available");
+        }
+        
+        claimed
+    }
+
+    /// Obtiene el timestamp de la primera actividad del usuario
+    pub fn get_member_since(env: Env, user: Address) -> Option<u64> {
+        env.storage().persistent().get(&(FIRST_ACTIVITY_KEY, user))
+    }
+
+    /// Registra quién refirió al usuario (una sola vez por usuario)
+    pub fn register_referral(env: Env, user: Address, referrer: Address) {
+        // Verificar que el caller es el usuario referido
+        user.require_auth();
+        
+        if user == referrer {
+            panic!("Cannot refer yourself");
+        }
+        
+        let referrer_key = (REFERRER_KEY, user.clone());
+        if env.storage().persistent().has(&referrer_key) {
+            panic!("Referral already registered");
+        }
+        env.storage().persistent().set(&referrer_key, &referrer);
+        
+        let count = Self::get_referral_count(env.clone(), referrer.clone());
+        env.storage().persistent().set(&(REFERRAL_COUNT_KEY, referrer), &(count + 1));
+    }
+
+    /// Obtiene cuántos usuarios refirió un usuario
+    pub fn get_referral_count(env: Env, user: Address) -> u32 {
+        env.storage().persistent().get(&(REFERRAL_COUNT_KEY, user)).unwrap_or(0)
+    }
+
+    /// Obtiene la racha actual de días consecutivos con actividad
+    pub fn get_streak(env: Env, user: Address) -> u32 {
+        let (streak, last_day): (u32, u64) = env.storage().persistent().get(&(STREAK_KEY, user)).unwrap_or((0, 0));
+        
+        // La racha se pierde si pasó más de un día sin actividad
+        if env.ledger().timestamp() / SECONDS_PER_DAY > last_day + 1 {
+            return 0;
+        }
+        streak
+    }
+
+    /// Obtiene los tipos de actividad distintos registrados por el usuario
+    pub fn get_activity_types(env: Env, user: Address) -> Vec<String> {
+        env.storage().persistent().get(&(ACTIVITY_TYPES_KEY, user)).unwrap_or_else(|| {
+            Vec::new(&env)
+        })
+    }
+
+    /// Configura los pesos de la reputación (solo admin)
+    pub fn set_reputation_weights(env: Env, weights: ReputationWeights) {
+        // Verificar que el caller es admin
+        let admin: Address = env.storage().instance().get(&ADMIN_KEY).unwrap();
+        admin.require_auth();
+        
+        let total = weights.points + weights.badges + weights.streak + weights.referrals + weights.diversity;
+        if total != BPS_DENOMINATOR {
+            panic!("Weights must sum to 10000");
+        }
+        
+        env.storage().instance().set(&REPUTATION_WEIGHTS_KEY, &weights);
+    }
+
+    /// Obtiene los pesos de la reputación (por defecto 40/20/15/15/10 %)
+    pub fn get_reputation_weights(env: Env) -> ReputationWeights {
+        env.storage().instance().get(&REPUTATION_WEIGHTS_KEY).unwrap_or(ReputationWeights {
+            points: 4_000,
+            badges: 2_000,
+            streak: 1_500,
+            referrals: 1_500,
+            diversity: 1_000,
+        })
+    }
+
+    /// Calcula la reputación del usuario (0-100) ponderando puntos, insignias, racha,
+    /// referidos y diversidad de actividad
+    ///
+    /// El resultado se guarda en caché durante 24 horas.
+    pub fn compute_reputation_score(env: Env, user: Address) -> u32 {
+        let now = env.ledger().timestamp();
+        let cache_key = (REPUTATION_SCORE_KEY, user.clone());
+        if let Some((score, computed_at)) = env.storage().persistent().get::<_, (u32, u64)>(&cache_key) {
+            if now < computed_at + REPUTATION_CACHE_DURATION {
+                return score;
+            }
+        }
+        
+        // Normalizar cada factor a 0-100
+        let points = Self::get_points_balance(env.clone(), user.clone()).clamp(0, REPUTATION_POINTS_CAP);
+        let points_factor = (points * 100 / REPUTATION_POINTS_CAP) as u32;
+        let badges = Self::get_user_badges(env.clone(), user.clone()).len();
+        let badges_factor = badges.min(REPUTATION_BADGES_CAP) * 100 / REPUTATION_BADGES_CAP;
+        let streak = Self::get_streak(env.clone(), user.clone());
+        let streak_factor = streak.min(REPUTATION_STREAK_CAP) * 100 / REPUTATION_STREAK_CAP;
+        let referrals = Self::get_referral_count(env.clone(), user.clone());
+        let referrals_factor = referrals.min(REPUTATION_REFERRALS_CAP) * 100 / REPUTATION_REFERRALS_CAP;
+        let diversity = Self::get_activity_types(env.clone(), user.clone()).len();
+        let diversity_factor = diversity.min(REPUTATION_DIVERSITY_CAP) * 100 / REPUTATION_DIVERSITY_CAP;
+        
+        let weights = Self::get_reputation_weights(env.clone());
+        let score = (points_factor * weights.points
+            + badges_factor * weights.badges
+            + streak_factor * weights.streak
+            + referrals_factor * weights.referrals
+            + diversity_factor * weights.diversity)
+            / BPS_DENOMINATOR;
+        
+        env.storage().persistent().set(&cache_key, &(score, now));
+        score
+    }
+
+    /// Nomina una obra para ser destacada; cuesta `NOMINATION_COST` puntos
+    pub fn nominate_artwork(env: Env, nominator: Address, nft_contract: Address, token_id: u32) -> u32 {
+        // Verificar que el caller es el nominador
+        nominator.require_auth();
+        
+        let now = env.ledger().timestamp();
+        
+        // Verificar que la obra no tiene una nominación vigente
+        let artwork_key = (NOMINATED_ARTWORK_KEY, nft_contract.clone(), token_id);
+        if let Some(existing_id) = env.storage().persistent().get::<_, u32>(&artwork_key) {
+            if now < Self::get_nomination(env.clone(), existing_id).expires_at {
+                panic!("Artwork already nominated");
+            }
+        }
+        
+        // Cobrar la nominación
+        let user_balance = Self::get_points_balance(env.clone(), nominator.clone());
+        if user_balance < NOMINATION_COST {
+            panic!("Insufficient points");
+        }
+        let points_key = (POINTS_KEY, nominator.clone());
+        env.storage().persistent().set(&points_key, &(user_balance - NOMINATION_COST));
+        
+        // Obtener nuevo ID
+        let mut count: u32 = env.storage().instance().get(&NOMINATION_COUNT_KEY).unwrap_or(0);
+        count += 1;
+        env.storage().instance().set(&NOMINATION_COUNT_KEY, &count);
+        
+        let nomination = Nomination {
+            id: count,
+            nominator: nominator.clone(),
+            nft_contract,
+            token_id,
+            created_at: now,
+            expires_at: now + NOMINATION_DURATION,
+        };
+        env.storage().persistent().set(&(NOMINATION_KEY, count), &nomination);
+        env.storage().persistent().set(&artwork_key, &count);
+        
+        // Agregar a las nominaciones activas, descartando las vencidas
+        let mut active = Vec::new(&env);
+        for nomination_id in Self::active_nominations(&env).iter() {
+            if now < Self::get_nomination(env.clone(), nomination_id).expires_at {
+                active.push_back(nomination_id);
+            }
+        }
+        active.push_back(count);
+        env.storage().persistent().set(&ACTIVE_NOMINATIONS_KEY, &active);
+        
+        // Registrar actividad
+        Self::record_activity(&env, &nominator, "artwork_nominated", -NOMINATION_COST, String::from_str(&env, "Nominated artwork"));
+        
+        count
+    }
+
+    /// Vota por una nominación; el peso del voto es el balance de puntos del votante
+    pub fn vote_for_artwork(env: Env, voter: Address, nomination_id: u32) {
+        // Verificar que el caller es el votante
+        voter.require_auth();
+        
+        let nomination = Self::get_nomination(env.clone(), nomination_id);
+        if env.ledger().timestamp() >= nomination.expires_at {
+            panic!("Nomination expired");
+        }
+        
+        // Un voto por usuario y nominación
+        let voter_key = (NOMINATION_VOTER_KEY, nomination_id, voter.clone());
+        if env.storage().persistent().has(&voter_key) {
+            panic!("Already voted");
+        }
+        
+        let weight = Self::get_points_balance(env.clone(), voter.clone());
+        if weight <= 0 {
+            panic!("No points to vote with");
+        }
+        
+        env.storage().persistent().set(&voter_key, &weight);
+        let votes_key = (NOMINATION_VOTES_KEY, nomination_id);
+        let votes: i128 = env.storage().persistent().get(&votes_key).unwrap_or(0);
+        env.storage().persistent().set(&votes_key, &(votes + weight));
+    }
+
+    /// Obtiene una nominación
+    pub fn get_nomination(env: Env, nomination_id: u32) -> Nomination {
+        env.storage().persistent().get(&(NOMINATION_KEY, nomination_id)).unwrap_or_else(|| {
+            panic!("Nomination not found");
+        })
+    }
+
+    /// Obtiene el peso total de votos de una nominación
+    pub fn get_nomination_votes(env: Env, nomination_id: u32) -> i128 {
+        env.storage().persistent().get(&(NOMINATION_VOTES_KEY, nomination_id)).unwrap_or(0)
+    }
+
+    /// Obtiene las obras destacadas vigentes: (contrato NFT, token, peso de votos),
+    /// ordenadas de mayor a menor peso
+    pub fn get_featured_artworks(env: Env, limit: u32) -> Vec<(Address, u32, i128)> {
+        let now = env.ledger().timestamp();
+        let limit = limit.min(MAX_FEATURED);
+        
+        // Reunir las nominaciones vigentes con sus votos
+        let mut candidates: Vec<(u32, i128)> = Vec::new(&env);
+        for nomination_id in Self::active_nominations(&env).iter() {
+            let nomination = Self::get_nomination(env.clone(), nomination_id);
+            if now < nomination.expires_at {
+                candidates.push_back((nomination_id, Self::get_nomination_votes(env.clone(), nomination_id)));
+            }
+        }
+        
+        // Selección de las nominaciones con más votos
+        let mut result = Vec::new(&env);
+        while result.len() < limit && !candidates.is_empty() {
+            let mut best = 0;
+            for i in 1..candidates.len() {
+                if candidates.get(i).unwrap().1 > candidates.get(best).unwrap().1 {
+                    best = i;
+                }
+            }
+            
+            let (nomination_id, votes) = candidates.get(best).unwrap();
+            candidates.remove(best);
+            let nomination = Self::get_nomination(env.clone(), nomination_id);
+            result.push_back((nomination.nft_contract, nomination.token_id, votes));
+        }
+        
+        result
+    }
+
+    /// Registra la opinión del usuario (-2 a +2) sobre una tendencia cultural en la época
+    /// (semana) actual; cuesta `SENTIMENT_COST` puntos
+    pub fn submit_sentiment(env: Env, user: Address, category: String, score: i32) {
+        // Verificar que el caller es el usuario
+        user.require_auth();
+        
+        if !(MIN_SENTIMENT_SCORE..=MAX_SENTIMENT_SCORE).contains(&score) {
+            panic!("Score out of range");
+        }
+        if category.is_empty() || category.len() > MAX_SENTIMENT_CATEGORY_LENGTH {
+            panic!("Invalid category");
+        }
+        
+        // Una opinión por usuario, categoría y época
+        let epoch = Self::get_sentiment_epoch(env.clone());
+        let sentiment_key = (SENTIMENT_KEY, user.clone(), category.clone(), epoch);
+        if env.storage().persistent().has(&sentiment_key) {
+            panic!("Sentiment already submitted");
+        }
+        
+        // Registrar la categoría en la época
+        let categories_key = (SENTIMENT_CATEGORIES_KEY, epoch);
+        let mut categories: Vec<String> = env.storage().persistent().get(&categories_key).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        if !categories.contains(&category) {
+            if categories.len() >= MAX_SENTIMENT_CATEGORIES {
+                panic!("Too many categories this epoch");
+            }
+            categories.push_back(category.clone());
+            env.storage().persistent().set(&categories_key, &categories);
+        }
+        
+        // Cobrar la opinión
+        let user_balance = Self::get_points_balance(env.clone(), user.clone());
+        if user_balance < SENTIMENT_COST {
+            panic!("Insufficient points");
+        }
+        env.storage().persistent().set(&(POINTS_KEY, user.clone()), &(user_balance - SENTIMENT_COST));
+        
+        env.storage().persistent().set(&sentiment_key, &score);
+        let sum_key = (SENTIMENT_SUM_KEY, category, epoch);
+        let (sum, count): (i64, u32) = env.storage().persistent().get(&sum_key).unwrap_or((0, 0));
+        env.storage().persistent().set(&sum_key, &(sum + score as i64, count + 1));
+        
+        // Registrar actividad
+        Self::record_activity(&env, &user, "sentiment_submitted", -SENTIMENT_COST, String::from_str(&env, "Submitted sentiment"));
+    }
+
+    /// Obtiene la época (número de semana) actual del índice de sentimiento
+    pub fn get_sentiment_epoch(env: Env) -> u64 {
+        env.ledger().timestamp() / SECONDS_PER_WEEK
+    }
+
+    /// Obtiene la opinión de un usuario sobre una categoría en una época
+    pub fn get_user_sentiment(env: Env, user: Address, category: String, epoch: u64) -> Option<i32> {
+        env.storage().persistent().get(&(SENTIMENT_KEY, user, category, epoch))
+    }
+
+    /// Obtiene el índice de sentimiento de una categoría en una época: promedio de las
+    /// opiniones en millonésimas (-2_000_000 a 2_000_000), o None si no hubo opiniones
+    pub fn get_sentiment_index(env: Env, category: String, epoch: u64) -> Option<i32> {
+        let (sum, count): (i64, u32) = env.storage().persistent().get(&(SENTIMENT_SUM_KEY, category, epoch))?;
+        if count == 0 {
+            return None;
+        }
+        Some((sum * SENTIMENT_SCALE / count as i64) as i32)
+    }
+
+    /// Obtiene las categorías con el sentimiento más marcado de una época (en cualquier
+    /// dirección): (categoría, índice), ordenadas por magnitud del índice
+    pub fn get_trending_categories(env: Env, epoch: u64, limit: u32) -> Vec<(String, i32)> {
+        let categories: Vec<String> = env.storage().persistent().get(&(SENTIMENT_CATEGORIES_KEY, epoch)).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        
+        let mut candidates: Vec<(String, i32)> = Vec::new(&env);
+        for category in categories.iter() {
+            if let Some(index) = Self::get_sentiment_index(env.clone(), category.clone(), epoch) {
+                candidates.push_back((category, index));
+            }
+        }
+        
+        // Selección de las categorías de mayor magnitud
+        let mut result = Vec::new(&env);
+        while result.len() < limit && !candidates.is_empty() {
+            let mut best = 0;
+            for i in 1..candidates.len() {
+                if candidates.get(i).unwrap().1.unsigned_abs() > candidates.get(best).unwrap().1.unsigned_abs() {
+                    best = i;
+                }
+            }
+            
+            result.push_back(candidates.get(best).unwrap());
+            candidates.remove(best);
+        }
+        
+        result
     }
 
     /// Obtiene el ranking de usuarios
@@ -328,11 +1169,47 @@ impl SocialFi {
         })
     }
 
-    /// Obtiene la actividad de un usuario
-    pub fn get_user_activity(env: Env, _user: Address, _limit: u32) -> Vec<ActivityRecord> {
-        // TODO: Implementar obtención de actividad del usuario
-        // Esto requeriría un sistema de indexación más complejo
-        Vec::new(&env)
+    /// Obtiene la actividad reciente de un usuario, de la más reciente a la más antigua
+    pub fn get_user_activity(env: Env, user: Address, limit: u32) -> Vec<ActivityRecord> {
+        let log = Self::activity_log(&env, &user);
+        
+        let mut page = Vec::new(&env);
+        for record in log.iter().rev() {
+            if page.len() >= limit {
+                break;
+            }
+            page.push_back(record);
+        }
+        
+        page
+    }
+
+    /// Exporta en una sola llamada todos los datos guardados de un usuario
+    ///
+    /// La actividad anterior a este índice y los canjes anteriores a su registro por
+    /// usuario no se incluyen.
+    pub fn export_user_data(env: Env, user: Address) -> UserDataExport {
+        let redemptions: Vec<u32> = env.storage().persistent().get(&(USER_REDEMPTIONS_KEY, user.clone())).unwrap_or_else(|| {
+            Vec::new(&env)
+        });
+        let mut rewards_redeemed = Vec::new(&env);
+        for redemption_id in redemptions.iter() {
+            rewards_redeemed.push_back(Self::get_redemption(env.clone(), redemption_id).reward_id);
+        }
+        
+        UserDataExport {
+            user: user.clone(),
+            points_balance: Self::get_points_balance(env.clone(), user.clone()),
+            lifetime_points: Self::get_lifetime_points(env.clone(), user.clone()),
+            level: Self::get_user_level(env.clone(), user.clone()),
+            reputation_score: Self::compute_reputation_score(env.clone(), user.clone()),
+            badges: Self::get_user_badges(env.clone(), user.clone()),
+            activity_history: Self::activity_log(&env, &user),
+            rewards_redeemed,
+            referral_count: Self::get_referral_count(env.clone(), user.clone()),
+            streak: Self::get_streak(env.clone(), user.clone()),
+            join_timestamp: Self::get_member_since(env, user),
+        }
     }
 
     /// Obtiene estadísticas del usuario
@@ -372,4 +1249,215 @@ impl SocialFi {
     pub fn get_admin(env: Env) -> Address {
         env.storage().instance().get(&ADMIN_KEY).unwrap()
     }
+
+    /// Verifica si una dirección es el admin del contrato
+    pub fn is_admin(env: Env, who: Address) -> bool {
+        let admin: Option<Address> = env.storage().instance().get(&ADMIN_KEY);
+        admin == Some(who)
+    }
+
+    /// Suma puntos al balance del usuario y registra la actividad
+    fn credit_points(env: &Env, user: &Address, points: i128, reason: String) {
+        // Obtener balance actual
+        let points_key = (POINTS_KEY, user.clone());
+        let current_balance: i128 = env.storage().persistent().get(&points_key).unwrap_or(0);
+        
+        // Actualizar balance
+        let new_balance = current_balance + points;
+        env.storage().persistent().set(&points_key, &new_balance);
+        
+        // Actualizar puntos totales ganados (determinan el nivel)
+        let lifetime_key = (LIFETIME_POINTS_KEY, user.clone());
+        let lifetime: i128 = env.storage().persistent().get(&lifetime_key).unwrap_or(0);
+        env.storage().persistent().set(&lifetime_key, &(lifetime + points));
+        Self::mint_achievement_nfts(env, user, ((lifetime + points) / POINTS_PER_LEVEL) as u32);
+        
+        // Registrar actividad
+        Self::record_activity(env, user, "points_awarded", points, reason);
+        
+        // Actualizar leaderboard
+        Self::update_leaderboard(env.clone(), user.clone());
+    }
+
+    /// Mintea los NFTs de logro de los niveles alcanzados que el usuario aún no recibió
+    ///
+    /// Es best-effort: si la colección no está configurada o el minteo falla, los puntos se
+    /// otorgan igual y el logro se mintea en el próximo otorgamiento.
+    fn mint_achievement_nfts(env: &Env, user: &Address, level: u32) {
+        let nft_contract = match Self::get_achievement_nft_contract(env.clone()) {
+            Some(nft_contract) => nft_contract,
+            None => return,
+        };
+        let nft = CulturalNFTClient::new(env, &nft_contract);
+        
+        for milestone in ACHIEVEMENT_LEVELS {
+            if level < milestone || Self::has_achievement_nft(env.clone(), user.clone(), milestone) {
+                continue;
+            }
+            
+            let token_id: u32 = env.storage().instance().get(&ACHIEVEMENT_COUNT_KEY).unwrap_or(0) + 1;
+            let metadata = Self::achievement_metadata(env, milestone);
+            if nft.try_mint_cultural_nft(user, &token_id, &metadata, &Vec::new(env)).is_ok() {
+                env.storage().instance().set(&ACHIEVEMENT_COUNT_KEY, &token_id);
+                env.storage().persistent().set(&(ACHIEVEMENT_MINTED_KEY, user.clone(), milestone), &true);
+            }
+        }
+    }
+
+    /// Plantilla de metadatos del NFT de logro de un nivel
+    fn achievement_metadata(env: &Env, level: u32) -> CulturalMetadata {
+        let (title, significance) = match level {
+            10 => ("Digital Explorer - Level 10 Achievement", "Otorgado al alcanzar el nivel 10 en PermaMuseum"),
+            25 => ("Heritage Curator - Level 25 Achievement", "Otorgado al alcanzar el nivel 25 en PermaMuseum"),
+            _ => ("Cultural Guardian - Level 50 Achievement", "Otorgado al alcanzar el nivel 50 en PermaMuseum"),
+        };
+        CulturalMetadata {
+            title: String::from_str(env, title),
+            artist: String::from_str(env, "PermaMuseum"),
+            period: String::from_str(env, "Contemporáneo"),
+            culture: String::from_str(env, "Digital"),
+            material: String::from_str(env, "Digital"),
+            dimensions: String::from_str(env, ""),
+            condition: String::from_str(env, "Nuevo"),
+            significance: String::from_str(env, significance),
+            museum_address: env.current_contract_address(),
+            location: ArtifactLocation::Unknown,
+            is_soulbound: true,
+        }
+    }
+
+    /// Agrega una insignia al usuario y registra la actividad
+    fn grant_badge(env: &Env, user: &Address, badge_id: u32) {
+        // Obtener insignias actuales del usuario
+        let badges_key = (BADGES_KEY, user.clone());
+        let mut user_badges: Vec<u32> = env.storage().persistent().get(&badges_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        
+        // Verificar que no tiene ya la insignia
+        if user_badges.contains(badge_id) {
+            panic!("User already has this badge");
+        }
+        
+        // Verificar que quedan unidades de la insignia
+        let issued = Self::get_badge_issued(env.clone(), badge_id);
+        let badge: Option<Badge> = env.storage().persistent().get(&(symbol_short!("BADGE_INF"), badge_id));
+        if let Some(badge) = badge {
+            if badge.max_supply > 0 && issued >= badge.max_supply {
+                panic!("Badge supply exhausted");
+            }
+        }
+        
+        // Agregar insignia
+        user_badges.push_back(badge_id);
+        env.storage().persistent().set(&badges_key, &user_badges);
+        env.storage().persistent().set(&(USER_BADGE_COUNT_KEY, user.clone()), &user_badges.len());
+        env.storage().persistent().set(&(BADGE_ISSUED_KEY, badge_id), &(issued + 1));
+        
+        // Registrar actividad
+        Self::record_activity(env, user, "badge_awarded", 0, String::from_str(env, "Badge awarded"));
+    }
+
+    /// Obtiene el historial de actividades recientes de un usuario
+    fn activity_log(env: &Env, user: &Address) -> Vec<ActivityRecord> {
+        env.storage().persistent().get(&(ACTIVITY_LOG_KEY, user.clone())).unwrap_or_else(|| {
+            Vec::new(env)
+        })
+    }
+
+    /// Obtiene (o crea la primera vez) la insignia especial de un aniversario
+    fn anniversary_badge_id(env: &Env, years: u32) -> u32 {
+        let anniversary_key = (ANNIVERSARY_BADGE_KEY, years);
+        if let Some(badge_id) = env.storage().persistent().get(&anniversary_key) {
+            return badge_id;
+        }
+        
+        // Obtener nuevo ID
+        let badge_count_key = symbol_short!("BADGE_CNT");
+        let mut count: u32 = env.storage().instance().get(&badge_count_key).unwrap_or(0);
+        count += 1;
+        env.storage().instance().set(&badge_count_key, &count);
+        
+        let (name, description) = if years == 1 {
+            ("1 Year Member", "Active member for one year")
+        } else {
+            ("5 Year Member", "Active member for five years")
+        };
+        
+        let badge = Badge {
+            id: count,
+            name: String::from_str(env, name),
+            description: String::from_str(env, description),
+            points_required: 0,
+            rarity: String::from_str(env, if years == 1 { "rare" } else { "legendary" }),
+            category: String::from_str(env, "explorer"),
+            max_supply: 0,
+        };
+        env.storage().persistent().set(&(symbol_short!("BADGE_INF"), count), &badge);
+        env.storage().persistent().set(&anniversary_key, &count);
+        
+        count
+    }
+
+    /// Obtiene los IDs de nominaciones activas (pueden incluir vencidas aún no depuradas)
+    fn active_nominations(env: &Env) -> Vec<u32> {
+        env.storage().persistent().get(&ACTIVE_NOMINATIONS_KEY).unwrap_or_else(|| {
+            Vec::new(env)
+        })
+    }
+
+    /// Registra una actividad del usuario
+    fn record_activity(env: &Env, user: &Address, activity_type: &str, points: i128, description: String) {
+        let timestamp = env.ledger().timestamp();
+        let activity_record = ActivityRecord {
+            user: user.clone(),
+            activity_type: String::from_str(env, activity_type),
+            points_awarded: points,
+            timestamp,
+            description,
+        };
+        
+        let activity_key = (ACTIVITY_KEY, user.clone(), timestamp);
+        env.storage().persistent().set(&activity_key, &activity_record);
+        
+        // Conservar las últimas actividades en el historial del usuario
+        let mut log = Self::activity_log(env, user);
+        log.push_back(activity_record.clone());
+        if log.len() > MAX_ACTIVITY_HISTORY {
+            log.pop_front();
+        }
+        env.storage().persistent().set(&(ACTIVITY_LOG_KEY, user.clone()), &log);
+        
+        // Guardar la primera actividad del usuario (antigüedad)
+        let first_activity_key = (FIRST_ACTIVITY_KEY, user.clone());
+        if !env.storage().persistent().has(&first_activity_key) {
+            env.storage().persistent().set(&first_activity_key, &timestamp);
+        }
+        
+        // Registrar el tipo de actividad (diversidad para la reputación)
+        let types_key = (ACTIVITY_TYPES_KEY, user.clone());
+        let mut activity_types: Vec<String> = env.storage().persistent().get(&types_key).unwrap_or_else(|| {
+            Vec::new(env)
+        });
+        if !activity_types.contains(&activity_record.activity_type) {
+            activity_types.push_back(activity_record.activity_type.clone());
+            env.storage().persistent().set(&types_key, &activity_types);
+        }
+        
+        // Actualizar la racha de días consecutivos con actividad
+        let streak_key = (STREAK_KEY, user.clone());
+        let today = timestamp / SECONDS_PER_DAY;
+        let (streak, last_day): (u32, u64) = env.storage().persistent().get(&streak_key).unwrap_or((0, 0));
+        let streak = if streak > 0 && today == last_day {
+            streak
+        } else if streak > 0 && today == last_day + 1 {
+            streak + 1
+        } else {
+            1
+        };
+        env.storage().persistent().set(&streak_key, &(streak, today));
+    }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,507 @@
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::Bytes;
+
+#[test]
+fn test_user_badge_count_tracks_awards_and_revokes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    assert_eq!(socialfi.get_user_badge_count(&user), 0);
+    
+    socialfi.award_badge(&user, &1, &String::from_str(&env, "Explorador"));
+    socialfi.award_badge(&user, &2, &String::from_str(&env, "Curador"));
+    assert_eq!(socialfi.get_user_badge_count(&user), 2);
+    
+    socialfi.revoke_badge(&user, &1);
+    assert_eq!(socialfi.get_user_badge_count(&user), 1);
+    assert_eq!(socialfi.get_user_badges(&user).len(), socialfi.get_user_badge_count(&user));
+    
+    // Retirar una insignia que el usuario no tiene falla
+    assert!(socialfi.try_revoke_badge(&user, &1).is_err());
+}
+
+#[test]
+fn test_badge_max_supply_limits_awards() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    
+    let badge_id = socialfi.create_badge(
+        &String::from_str(&env, "Guardián del Qhapaq Ñan"),
+        &String::from_str(&env, "Insignia única"),
+        &0,
+        &String::from_str(&env, "legendary"),
+        &String::from_str(&env, "explorer"),
+        &1,
+    );
+    assert_eq!(socialfi.get_badge_remaining_supply(&badge_id), Some(1));
+    
+    socialfi.award_badge(&first, &badge_id, &String::from_str(&env, "Guardián"));
+    assert_eq!(socialfi.get_badge_remaining_supply(&badge_id), Some(0));
+    assert!(socialfi.try_award_badge(&second, &badge_id, &String::from_str(&env, "Guardián")).is_err());
+    
+    // Retirarla libera la unidad
+    socialfi.revoke_badge(&first, &badge_id);
+    socialfi.award_badge(&second, &badge_id, &String::from_str(&env, "Guardián"));
+    assert_eq!(socialfi.get_badge_issued(&badge_id), 1);
+    
+    // Sin tope de emisión no hay límite
+    let common = socialfi.create_badge(
+        &String::from_str(&env, "Visitante"),
+        &String::from_str(&env, "Primera visita"),
+        &0,
+        &String::from_str(&env, "common"),
+        &String::from_str(&env, "explorer"),
+        &0,
+    );
+    assert_eq!(socialfi.get_badge_remaining_supply(&common), None);
+}
+
+#[test]
+fn test_redeem_with_delivery_reference() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    socialfi.award_points(&user, &500, &String::from_str(&env, "Visita guiada"));
+    let reward_id = socialfi.create_reward(&String::from_str(&env, "Réplica de cerámica"), &String::from_str(&env, "Envío a domicilio"), &300, &10, &None);
+    
+    // El usuario entrega solo el hash de su dirección de entrega
+    let delivery_ref = Bytes::from_array(&env, &[9u8; 32]);
+    let redemption_id = socialfi.redeem_points(&user, &reward_id, &Some(delivery_ref.clone()));
+    
+    let redemption = socialfi.get_redemption(&redemption_id);
+    assert_eq!(redemption.user, user);
+    assert_eq!(redemption.reward_id, reward_id);
+    assert_eq!(redemption.delivery_ref, Some(delivery_ref));
+    assert!(!redemption.fulfilled);
+    assert_eq!(socialfi.get_points_balance(&user), 200);
+    
+    socialfi.fulfill_redemption(&redemption_id);
+    assert!(socialfi.get_redemption(&redemption_id).fulfilled);
+    assert!(socialfi.try_fulfill_redemption(&redemption_id).is_err());
+}
+
+#[test]
+fn test_badge_gated_reward() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let holder = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    socialfi.award_points(&holder, &500, &String::from_str(&env, "Visita guiada"));
+    socialfi.award_points(&outsider, &500, &String::from_str(&env, "Visita guiada"));
+    socialfi.award_badge(&holder, &3, &String::from_str(&env, "Mecenas"));
+    
+    let reward_id = socialfi.create_reward(
+        &String::from_str(&env, "Visita privada a la reserva"),
+        &String::from_str(&env, "Solo para mecenas"),
+        &200,
+        &5,
+        &Some(3),
+    );
+    assert_eq!(socialfi.get_reward_info(&reward_id).required_badge, Some(3));
+    
+    // Sin la insignia el canje se rechaza y no se cobran puntos
+    assert!(socialfi.try_redeem_points(&outsider, &reward_id, &None).is_err());
+    assert_eq!(socialfi.get_points_balance(&outsider), 500);
+    
+    socialfi.redeem_points(&holder, &reward_id, &None);
+    assert_eq!(socialfi.get_points_balance(&holder), 300);
+    assert_eq!(socialfi.get_reward_info(&reward_id).current_redemptions, 1);
+}
+
+#[test]
+fn test_export_user_data() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let friend = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    socialfi.award_points(&user, &250, &String::from_str(&env, "Visita guiada"));
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 86_400);
+    socialfi.award_badge(&user, &7, &String::from_str(&env, "Explorador"));
+    let reward_id = socialfi.create_reward(&String::from_str(&env, "Entrada gratuita"), &String::from_str(&env, "Un pase"), &100, &10, &None);
+    socialfi.redeem_points(&user, &reward_id, &None);
+    socialfi.register_referral(&friend, &user);
+    
+    let export = socialfi.export_user_data(&user);
+    assert_eq!(export.user, user);
+    assert_eq!(export.points_balance, 150);
+    assert_eq!(export.lifetime_points, 250);
+    assert_eq!(export.level, 2);
+    assert_eq!(export.badges, soroban_sdk::vec![&env, 7u32]);
+    assert_eq!(export.rewards_redeemed, soroban_sdk::vec![&env, reward_id]);
+    assert_eq!(export.referral_count, 1);
+    assert_eq!(export.streak, 2);
+    assert_eq!(export.join_timestamp, Some(1_000));
+    assert_eq!(export.reputation_score, socialfi.compute_reputation_score(&user));
+    
+    // Historial completo en orden cronológico
+    assert_eq!(export.activity_history.len(), 3);
+    assert_eq!(export.activity_history.get(0).unwrap().points_awarded, 250);
+    assert_eq!(export.activity_history.get(2).unwrap().points_awarded, -100);
+    assert_eq!(socialfi.get_user_activity(&user, &1).get(0).unwrap().points_awarded, -100);
+    
+    // Un usuario sin datos exporta valores vacíos
+    let empty = socialfi.export_user_data(&Address::generate(&env));
+    assert_eq!(empty.points_balance, 0);
+    assert!(empty.activity_history.is_empty());
+    assert_eq!(empty.join_timestamp, None);
+}
+
+#[test]
+fn test_socialfi_sentiment_index() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 10 * 7 * 86400);
+    let admin = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    
+    let mut users = Vec::new(&env);
+    for _ in 0..4 {
+        let user = Address::generate(&env);
+        socialfi.award_points(&user, &20, &String::from_str(&env, "Visitas"));
+        users.push_back(user);
+    }
+    
+    let muralismo = String::from_str(&env, "muralismo");
+    let textiles = String::from_str(&env, "textiles");
+    let ceramica = String::from_str(&env, "ceramica");
+    let epoch = socialfi.get_sentiment_epoch();
+    assert_eq!(epoch, 10);
+    
+    // La comunidad opina sobre varias tendencias
+    for (i, score) in [2i32, 2, 1, -1].iter().enumerate() {
+        socialfi.submit_sentiment(&users.get(i as u32).unwrap(), &muralismo, score);
+    }
+    socialfi.submit_sentiment(&users.get(0).unwrap(), &textiles, &-2);
+    socialfi.submit_sentiment(&users.get(1).unwrap(), &textiles, &-2);
+    socialfi.submit_sentiment(&users.get(2).unwrap(), &ceramica, &0);
+    
+    // Cada opinión cuesta 5 puntos y es única por usuario, categoría y época
+    assert_eq!(socialfi.get_points_balance(&users.get(0).unwrap()), 10);
+    assert!(socialfi.try_submit_sentiment(&users.get(0).unwrap(), &muralismo, &1).is_err());
+    assert!(socialfi.try_submit_sentiment(&users.get(3).unwrap(), &ceramica, &3).is_err());
+    assert_eq!(socialfi.get_user_sentiment(&users.get(3).unwrap(), &muralismo, &epoch), Some(-1));
+    
+    // Promedios en millonésimas
+    assert_eq!(socialfi.get_sentiment_index(&muralismo, &epoch), Some(1_000_000));
+    assert_eq!(socialfi.get_sentiment_index(&textiles, &epoch), Some(-2_000_000));
+    assert_eq!(socialfi.get_sentiment_index(&ceramica, &epoch), Some(0));
+    assert_eq!(socialfi.get_sentiment_index(&muralismo, &(epoch + 1)), None);
+    
+    // Tendencias ordenadas por magnitud, sin importar el signo
+    let trending = socialfi.get_trending_categories(&epoch, &2);
+    assert_eq!(trending.len(), 2);
+    assert_eq!(trending.get(0).unwrap(), (textiles.clone(), -2_000_000));
+    assert_eq!(trending.get(1).unwrap(), (muralismo.clone(), 1_000_000));
+    
+    // La semana siguiente es una época nueva
+    env.ledger().with_mut(|li| li.timestamp += 7 * 86400);
+    socialfi.submit_sentiment(&users.get(0).unwrap(), &muralismo, &-2);
+    assert_eq!(socialfi.get_sentiment_index(&muralismo, &(epoch + 1)), Some(-2_000_000));
+    assert_eq!(socialfi.get_sentiment_index(&muralismo, &epoch), Some(1_000_000));
+}
+
+#[test]
+fn test_claim_anniversary_badge_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    
+    // Sin actividad no hay antigüedad que reclamar
+    assert!(socialfi.try_claim_anniversary_badge(&user).is_err());
+    
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    socialfi.award_points(&user, &10, &String::from_str(&env, "Primera visita"));
+    assert_eq!(socialfi.get_member_since(&user), Some(1_000));
+    
+    // Un segundo antes del año todavía no se puede reclamar
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 365 * 86400 - 1);
+    assert!(socialfi.try_claim_anniversary_badge(&user).is_err());
+    
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 365 * 86400);
+    let claimed = socialfi.claim_anniversary_badge(&user);
+    assert_eq!(claimed.len(), 1);
+    assert!(socialfi.get_user_badges(&user).contains(claimed.get(0).unwrap()));
+    assert_eq!(socialfi.get_points_balance(&user), 110);
+    
+    // El hito solo se reclama una vez
+    assert!(socialfi.try_claim_anniversary_badge(&user).is_err());
+    assert_eq!(socialfi.get_points_balance(&user), 110);
+}
+
+#[test]
+fn test_claim_reward_for_user_delegation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    socialfi.award_points(&user, &500, &String::from_str(&env, "Visita guiada"));
+    let reward_id = socialfi.create_reward(&String::from_str(&env, "Entrada gratuita"), &String::from_str(&env, "Una visita"), &200, &10, &None);
+    
+    // Sin delegación nadie canjea por el usuario, ni puede delegarse a sí mismo
+    assert!(socialfi.try_claim_reward_for_user(&museum, &user, &reward_id).is_err());
+    assert!(socialfi.try_delegate_reward_claiming(&user, &user).is_err());
+    
+    socialfi.delegate_reward_claiming(&user, &museum);
+    assert_eq!(socialfi.get_reward_claim_delegate(&user), Some(museum.clone()));
+    assert!(socialfi.try_claim_reward_for_user(&stranger, &user, &reward_id).is_err());
+    
+    // El delegado canjea con los puntos del usuario y el canje queda a nombre del usuario
+    let redemption_id = socialfi.claim_reward_for_user(&museum, &user, &reward_id);
+    assert_eq!(socialfi.get_redemption(&redemption_id).user, user);
+    assert_eq!(socialfi.get_points_balance(&user), 300);
+    assert_eq!(socialfi.get_points_balance(&museum), 0);
+    
+    // Tras revocar la delegación el delegado pierde el permiso
+    socialfi.revoke_reward_claiming(&user);
+    assert!(socialfi.try_claim_reward_for_user(&museum, &user, &reward_id).is_err());
+    assert!(socialfi.try_revoke_reward_claiming(&user).is_err());
+}
+
+#[test]
+fn test_featured_artwork_nomination_and_voting() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let curator = Address::generate(&env);
+    let fan = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    let nft_contract = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    socialfi.award_points(&curator, &100, &String::from_str(&env, "Curaduría"));
+    socialfi.award_points(&fan, &300, &String::from_str(&env, "Visitas"));
+    
+    // Nominar cuesta 10 puntos y cada obra tiene una sola nominación vigente
+    assert!(socialfi.try_nominate_artwork(&newcomer, &nft_contract, &1).is_err());
+    let first = socialfi.nominate_artwork(&curator, &nft_contract, &1);
+    let second = socialfi.nominate_artwork(&curator, &nft_contract, &2);
+    assert_eq!(socialfi.get_points_balance(&curator), 80);
+    assert!(socialfi.try_nominate_artwork(&fan, &nft_contract, &1).is_err());
+    
+    // El voto pesa el balance de puntos del votante y es uno por nominación
+    socialfi.vote_for_artwork(&curator, &first);
+    socialfi.vote_for_artwork(&fan, &second);
+    assert!(socialfi.try_vote_for_artwork(&fan, &second).is_err());
+    assert!(socialfi.try_vote_for_artwork(&newcomer, &first).is_err());
+    assert_eq!(socialfi.get_nomination_votes(&first), 80);
+    assert_eq!(socialfi.get_nomination_votes(&second), 300);
+    
+    // Las destacadas se ordenan por peso de votos
+    let featured = socialfi.get_featured_artworks(&10);
+    assert_eq!(featured, Vec::from_array(&env, [(nft_contract.clone(), 2, 300), (nft_contract.clone(), 1, 80)]));
+    assert_eq!(socialfi.get_featured_artworks(&1).len(), 1);
+    
+    // Al vencer la nominación deja de aceptar votos y de figurar, y la obra puede nominarse otra vez
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 30 * 86400);
+    assert!(socialfi.try_vote_for_artwork(&newcomer, &first).is_err());
+    assert!(socialfi.get_featured_artworks(&10).is_empty());
+    socialfi.nominate_artwork(&fan, &nft_contract, &1);
+}
+
+#[test]
+fn test_reputation_score_factors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 86400);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    let only = |points, badges, streak, referrals, diversity| ReputationWeights { points, badges, streak, referrals, diversity };
+    
+    // Pesos inválidos se rechazan
+    assert!(socialfi.try_set_reputation_weights(&only(5_000, 0, 0, 0, 0)).is_err());
+    
+    // Puntos: 500 de 1000 -> 50
+    let holder = Address::generate(&env);
+    socialfi.award_points(&holder, &500, &String::from_str(&env, "Visitas"));
+    socialfi.set_reputation_weights(&only(10_000, 0, 0, 0, 0));
+    assert_eq!(socialfi.compute_reputation_score(&holder), 50);
+    
+    // Insignias: 3 de 10 -> 30
+    let collector = Address::generate(&env);
+    for badge_id in 1..=3u32 {
+        socialfi.award_badge(&collector, &badge_id, &String::from_str(&env, "Insignia"));
+    }
+    socialfi.set_reputation_weights(&only(0, 10_000, 0, 0, 0));
+    assert_eq!(socialfi.compute_reputation_score(&collector), 30);
+    
+    // Racha: 3 días consecutivos de 30 -> 10
+    let regular = Address::generate(&env);
+    for day in 10..13u64 {
+        env.ledger().with_mut(|li| li.timestamp = day * 86400);
+        socialfi.award_points(&regular, &1, &String::from_str(&env, "Visita diaria"));
+    }
+    assert_eq!(socialfi.get_streak(&regular), 3);
+    socialfi.set_reputation_weights(&only(0, 0, 10_000, 0, 0));
+    assert_eq!(socialfi.compute_reputation_score(&regular), 10);
+    
+    // Referidos: 2 de 10 -> 20
+    let referrer = Address::generate(&env);
+    socialfi.register_referral(&Address::generate(&env), &referrer);
+    socialfi.register_referral(&Address::generate(&env), &referrer);
+    socialfi.set_reputation_weights(&only(0, 0, 0, 10_000, 0));
+    assert_eq!(socialfi.compute_reputation_score(&referrer), 20);
+    
+    // Diversidad: 2 tipos de actividad de 5 -> 40
+    let explorer = Address::generate(&env);
+    socialfi.award_points(&explorer, &10, &String::from_str(&env, "Visita"));
+    socialfi.award_badge(&explorer, &1, &String::from_str(&env, "Explorador"));
+    socialfi.set_reputation_weights(&only(0, 0, 0, 0, 10_000));
+    assert_eq!(socialfi.compute_reputation_score(&explorer), 40);
+    
+    // Con los pesos por defecto se combinan los factores:
+    // 40% de 50 (puntos) + 15% de 3 (racha) + 10% de 20 (diversidad) = 22
+    socialfi.set_reputation_weights(&only(4_000, 2_000, 1_500, 1_500, 1_000));
+    let newcomer = Address::generate(&env);
+    socialfi.award_points(&newcomer, &500, &String::from_str(&env, "Visitas"));
+    assert_eq!(socialfi.compute_reputation_score(&newcomer), 22);
+    
+    // El puntaje queda en caché 24 horas
+    socialfi.award_points(&newcomer, &500, &String::from_str(&env, "Visitas"));
+    assert_eq!(socialfi.compute_reputation_score(&newcomer), 22);
+    env.ledger().with_mut(|li| li.timestamp += 86400);
+    // 40% de 100 (puntos) + 15% de 3 (racha) + 10% de 20 (diversidad) = 42
+    assert_eq!(socialfi.compute_reputation_score(&newcomer), 42);
+}
+
+#[test]
+fn test_max_award_per_call() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let issuer = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    socialfi.set_points_issuer(&issuer, &true);
+    assert_eq!(socialfi.get_max_award_per_call(), None);
+    assert!(socialfi.try_set_max_award_per_call(&-1).is_err());
+    
+    // Con el límite, una llamada que lo supera falla y una que lo respeta se acredita
+    socialfi.set_max_award_per_call(&100);
+    assert_eq!(socialfi.get_max_award_per_call(), Some(100));
+    assert!(socialfi.try_award_points(&user, &101, &String::from_str(&env, "Exceso")).is_err());
+    assert!(socialfi.try_issue_points(&issuer, &user, &101, &String::from_str(&env, "Exceso")).is_err());
+    socialfi.award_points(&user, &100, &String::from_str(&env, "Visitas"));
+    socialfi.issue_points(&issuer, &user, &50, &String::from_str(&env, "Curaduría"));
+    assert_eq!(socialfi.get_points_balance(&user), 150);
+    
+    // El límite es por llamada, no por usuario
+    socialfi.award_points(&user, &100, &String::from_str(&env, "Visitas"));
+    assert_eq!(socialfi.get_points_balance(&user), 250);
+    
+    // 0 elimina el límite
+    socialfi.set_max_award_per_call(&0);
+    assert_eq!(socialfi.get_max_award_per_call(), None);
+    socialfi.award_points(&user, &1_000, &String::from_str(&env, "Evento"));
+    assert_eq!(socialfi.get_points_balance(&user), 1_250);
+}
+
+#[test]
+fn test_badge_progress_towards_unearned_badges() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    let badge = |name, points_required| {
+        socialfi.create_badge(
+            &String::from_str(&env, name),
+            &String::from_str(&env, "Insignia de prueba"),
+            &points_required,
+            &String::from_str(&env, "common"),
+            &String::from_str(&env, "visits"),
+            &0,
+        )
+    };
+    let visitor = badge("Visitante", 100);
+    let explorer = badge("Explorador", 500);
+    let _founder = badge("Fundador", 0);
+    let patron = badge("Mecenas", 1_000);
+    
+    // A mitad de camino hacia dos insignias; las que no piden puntos no figuran
+    socialfi.award_points(&user, &250, &String::from_str(&env, "Visitas"));
+    socialfi.award_badge(&user, &visitor, &String::from_str(&env, "Visitante"));
+    assert_eq!(
+        socialfi.get_badge_progress(&user),
+        Vec::from_array(&env, [(explorer, 500, 250), (patron, 1_000, 250)])
+    );
+    
+    // Al obtener una insignia deja de listarse
+    socialfi.award_badge(&user, &explorer, &String::from_str(&env, "Explorador"));
+    assert_eq!(socialfi.get_badge_progress(&user), Vec::from_array(&env, [(patron, 1_000, 250)]));
+}
+
+#[test]
+fn test_is_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    
+    // Sin inicializar no hay admin
+    assert!(!socialfi.is_admin(&admin));
+    
+    socialfi.initialize(&admin);
+    assert!(socialfi.is_admin(&admin));
+    assert!(!socialfi.is_admin(&user));
+}
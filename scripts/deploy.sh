@@ -38,4 +38,11 @@ soroban contract deploy \
     --source-account admin \
     --network testnet
 
+# Deploy RegistryFederation
+echo "🌐 Desplegando RegistryFederation..."
+soroban contract deploy \
+    --wasm target/wasm32-unknown-unknown/release/registry_federation.wasm \
+    --source-account admin \
+    --network testnet
+
 echo "✅ Deployment completado!"
@@ -1,15 +1,20 @@
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, String, Symbol, Vec,
+};
 
 // Importar los contratos (esto se ajustará cuando implementemos los contratos)
-// use museum_registry::MuseumRegistry;
-// use cultural_nft::CulturalNFT;
-// use marketplace::Marketplace;
-// use socialfi::SocialFi;
+use museum_registry::{CollectionStats, MuseumRegistry, MuseumRegistryClient};
+use cultural_nft::{ArtifactLocation, CulturalMetadata, CulturalNFT, CulturalNFTClient};
+use marketplace::{Marketplace, MarketplaceClient};
+use socialfi::{SocialFi, SocialFiClient};
+use registry_federation::{RegistryFederation, RegistryFederationClient};
 
 #[test]
 fn test_museum_registry_workflow() {
     let env = Env::default();
-    let admin = Address::generate(&env);
+    let _admin = Address::generate(&env);
     
     // TODO: Implementar tests de integración para MuseumRegistry
     // - Registrar museo
@@ -20,21 +25,57 @@ fn test_museum_registry_workflow() {
 #[test]
 fn test_cultural_nft_workflow() {
     let env = Env::default();
+    env.mock_all_auths();
     let admin = Address::generate(&env);
     let user = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let collector = Address::generate(&env);
+    
+    let contract_id = env.register_contract(None, CulturalNFT);
+    let client = CulturalNFTClient::new(&env, &contract_id);
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "PermaMuseum"),
+        &String::from_str(&env, "PMUS"),
+        &Address::generate(&env),
+        &None,
+    );
+    
+    // Mint NFT
+    let metadata = CulturalMetadata {
+        title: String::from_str(&env, "Manto Paracas"),
+        artist: String::from_str(&env, "Desconocido"),
+        period: String::from_str(&env, "Paracas Necrópolis"),
+        culture: String::from_str(&env, "Paracas"),
+        material: String::from_str(&env, "Algodón y lana de camélido"),
+        dimensions: String::from_str(&env, "250x130 cm"),
+        condition: String::from_str(&env, "Frágil"),
+        significance: String::from_str(&env, "Textil funerario"),
+        museum_address: museum.clone(),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    };
+    client.mint_cultural_nft(&user, &1, &metadata, &Vec::new(&env));
+    assert_eq!(client.owner_of(&1), user);
+    assert_eq!(client.total_supply(), 1);
+    
+    // Transferir NFT
+    client.transfer(&user, &collector, &1);
+    assert_eq!(client.owner_of(&1), collector);
+    assert_eq!(client.get_provenance(&1).len(), 1);
     
-    // TODO: Implementar tests de integración para CulturalNFT
-    // - Mint NFT
-    // - Transferir NFT
-    // - Obtener metadatos
+    // Obtener metadatos
+    let stored = client.get_cultural_metadata(&1);
+    assert_eq!(stored.title, metadata.title);
+    assert_eq!(stored.museum_address, museum);
 }
 
 #[test]
 fn test_marketplace_workflow() {
     let env = Env::default();
-    let admin = Address::generate(&env);
-    let seller = Address::generate(&env);
-    let buyer = Address::generate(&env);
+    let _admin = Address::generate(&env);
+    let _seller = Address::generate(&env);
+    let _buyer = Address::generate(&env);
     
     // TODO: Implementar tests de integración para Marketplace
     // - Listar NFT
@@ -46,8 +87,8 @@ fn test_marketplace_workflow() {
 #[test]
 fn test_socialfi_workflow() {
     let env = Env::default();
-    let admin = Address::generate(&env);
-    let user = Address::generate(&env);
+    let _admin = Address::generate(&env);
+    let _user = Address::generate(&env);
     
     // TODO: Implementar tests de integración para SocialFi
     // - Otorgar puntos
@@ -66,3 +107,483 @@ fn test_full_ecosystem_workflow() {
     // 5. Otorgar puntos por transacción
     // 6. Verificar distribución de royalties
 }
+
+#[test]
+fn test_content_rewards_award_socialfi_points() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    
+    // Desplegar SocialFi y CulturalNFT enlazados
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    
+    let nft_id = env.register_contract(None, CulturalNFT);
+    let nft = CulturalNFTClient::new(&env, &nft_id);
+    nft.initialize(
+        &admin,
+        &String::from_str(&env, "PermaMuseum"),
+        &String::from_str(&env, "PMUS"),
+        &Address::generate(&env),
+        &Some(socialfi_id.clone()),
+    );
+    assert_eq!(nft.get_socialfi(), Some(socialfi_id));
+    nft.set_content_rewards(&50, &10);
+    
+    let metadata = CulturalMetadata {
+        title: String::from_str(&env, "Huaco retrato"),
+        artist: String::from_str(&env, "Desconocido"),
+        period: String::from_str(&env, "Moche"),
+        culture: String::from_str(&env, "Moche"),
+        material: String::from_str(&env, "Cerámica"),
+        dimensions: String::from_str(&env, "30x20 cm"),
+        condition: String::from_str(&env, "Bueno"),
+        significance: String::from_str(&env, "Retrato ceremonial"),
+        museum_address: museum.clone(),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    };
+    
+    // Sin autorización como emisor el minteo funciona pero no otorga puntos
+    nft.mint_cultural_nft(&museum, &1, &metadata, &Vec::new(&env));
+    assert_eq!(socialfi.get_points_balance(&museum), 0);
+    
+    // Con autorización el museo recibe el bono de creación
+    socialfi.set_points_issuer(&nft_id, &true);
+    nft.mint_cultural_nft(&museum, &2, &metadata, &Vec::new(&env));
+    assert_eq!(socialfi.get_points_balance(&museum), 50);
+    
+    // Y los puntos de curaduría por cada informe de estado
+    nft.add_condition_report(&museum, &2, &String::from_str(&env, "Restaurado"), &String::from_str(&env, "Limpieza"));
+    assert_eq!(socialfi.get_points_balance(&museum), 60);
+}
+
+#[test]
+fn test_federated_search_across_registries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let lima_museum = Address::generate(&env);
+    let cusco_museum = Address::generate(&env);
+    let shared_museum = Address::generate(&env);
+    let other_museum = Address::generate(&env);
+    
+    // Dos despliegues del registro; uno de los museos figura en ambos
+    let north_id = env.register_contract(None, MuseumRegistry);
+    let north = MuseumRegistryClient::new(&env, &north_id);
+    north.initialize(&admin);
+    let south_id = env.register_contract(None, MuseumRegistry);
+    let south = MuseumRegistryClient::new(&env, &south_id);
+    south.initialize(&admin);
+    
+    let tag = String::from_str(&env, "textiles");
+    let mut tagged = Vec::new(&env);
+    tagged.push_back(tag.clone());
+    north.register_museum(&lima_museum, &String::from_str(&env, "Museo Textil de Lima"), &String::from_str(&env, "Tejidos"), &tagged, &None);
+    north.register_museum(&shared_museum, &String::from_str(&env, "Museo Andino"), &String::from_str(&env, "Norte"), &tagged, &None);
+    north.register_museum(&other_museum, &String::from_str(&env, "Museo de Cerámica"), &String::from_str(&env, "Ceramios"), &Vec::new(&env), &None);
+    south.register_museum(&cusco_museum, &String::from_str(&env, "Museo Textil del Cusco"), &String::from_str(&env, "Tejidos"), &tagged, &None);
+    south.register_museum(&shared_museum, &String::from_str(&env, "Museo Andino"), &String::from_str(&env, "Sur"), &tagged, &None);
+    
+    let query_hash = north.hash_search_term(&tag);
+    assert_eq!(north.get_museums_matching(&query_hash, &10).len(), 2);
+    assert_eq!(north.get_museums_matching(&north.hash_search_term(&String::from_str(&env, "Museo de Cerámica")), &10).len(), 1);
+    
+    let federation_id = env.register_contract(None, RegistryFederation);
+    let federation = RegistryFederationClient::new(&env, &federation_id);
+    federation.initialize(&admin);
+    federation.add_registry(&north_id);
+    federation.add_registry(&south_id);
+    assert!(federation.try_add_registry(&north_id).is_err());
+    
+    // Resultados combinados y sin duplicados; el museo compartido trae la info del primer registro
+    let results = federation.search_museums(&query_hash, &10);
+    assert_eq!(results.len(), 3);
+    let found = |museum: &Address| results.iter().any(|(address, _)| address == *museum);
+    assert!(found(&lima_museum));
+    assert!(found(&cusco_museum));
+    assert!(!found(&other_museum));
+    let (_, shared_info) = results.iter().find(|(address, _)| *address == shared_museum).unwrap();
+    assert_eq!(shared_info.description, String::from_str(&env, "Norte"));
+    
+    // El límite se reparte entre los registros: 2 / 2 = 1 por registro
+    assert_eq!(federation.search_museums(&query_hash, &2).len(), 2);
+    
+    federation.remove_registry(&north_id);
+    assert_eq!(federation.search_museums(&query_hash, &10).len(), 2);
+}
+
+#[test]
+fn test_achievement_nft_minted_at_level_10() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    
+    // SocialFi administra la colección de logros
+    let nft_id = env.register_contract(None, CulturalNFT);
+    let nft = CulturalNFTClient::new(&env, &nft_id);
+    nft.initialize(
+        &socialfi_id,
+        &String::from_str(&env, "PermaMuseum Achievements"),
+        &String::from_str(&env, "PMACH"),
+        &Address::generate(&env),
+        &None,
+    );
+    socialfi.set_achievement_nft_contract(&nft_id);
+    
+    // Nivel 9: todavía sin logro
+    socialfi.award_points(&user, &900, &String::from_str(&env, "Visitas"));
+    assert_eq!(socialfi.get_user_level(&user), 9);
+    assert_eq!(nft.total_supply(), 0);
+    
+    // Nivel 10: se mintea el NFT conmemorativo
+    socialfi.award_points(&user, &100, &String::from_str(&env, "Visitas"));
+    assert_eq!(socialfi.get_user_level(&user), 10);
+    assert!(socialfi.has_achievement_nft(&user, &10));
+    assert_eq!(nft.owner_of(&1), user);
+    let metadata = nft.get_cultural_metadata(&1);
+    assert_eq!(metadata.title, String::from_str(&env, "Digital Explorer - Level 10 Achievement"));
+    assert!(metadata.is_soulbound);
+    
+    // Más puntos en el mismo nivel no mintean otro NFT
+    socialfi.award_points(&user, &50, &String::from_str(&env, "Visitas"));
+    assert_eq!(nft.total_supply(), 1);
+}
+
+#[test]
+fn test_museum_stats_fed_by_peer_contracts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(&museum, &String::from_str(&env, "Museo Larco"), &String::from_str(&env, "Arte precolombino"), &Vec::new(&env), &None);
+    
+    let nft_id = env.register_contract(None, CulturalNFT);
+    let nft = CulturalNFTClient::new(&env, &nft_id);
+    nft.initialize(
+        &admin,
+        &String::from_str(&env, "PermaMuseum"),
+        &String::from_str(&env, "PMUS"),
+        &registry_id,
+        &None,
+    );
+    let market_id = env.register_contract(None, Marketplace);
+    let market = MarketplaceClient::new(&env, &market_id);
+    market.initialize(&admin, &250, &7);
+    market.set_museum_registry(&registry_id);
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &4_000);
+    market.set_payment_token(&asset.address());
+    
+    let metadata = CulturalMetadata {
+        title: String::from_str(&env, "Huaco retrato"),
+        artist: String::from_str(&env, "Desconocido"),
+        period: String::from_str(&env, "Moche"),
+        culture: String::from_str(&env, "Moche"),
+        material: String::from_str(&env, "Cerámica"),
+        dimensions: String::from_str(&env, "25x18 cm"),
+        condition: String::from_str(&env, "Bueno"),
+        significance: String::from_str(&env, "Retrato de un gobernante"),
+        museum_address: museum.clone(),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    };
+    
+    // Sin autorizar como pares, los contratos no alteran las estadísticas
+    nft.mint_cultural_nft(&seller, &1, &metadata, &Vec::new(&env));
+    assert_eq!(registry.get_museum_stats(&museum).nfts_minted, 0);
+    
+    registry.set_peer_contract(&nft_id, &true);
+    registry.set_peer_contract(&market_id, &true);
+    nft.mint_cultural_nft(&seller, &2, &metadata, &Vec::new(&env));
+    nft.mint_cultural_nft(&seller, &3, &metadata, &Vec::new(&env));
+    nft.approve(&seller, &market_id, &2);
+    market.list_nft(&seller, &nft_id, &2, &4_000, &None);
+    market.buy_nft(&buyer, &nft_id, &2);
+    
+    let stats = registry.get_museum_stats(&museum);
+    assert_eq!(stats.nfts_minted, 2);
+    assert_eq!(stats.sales_completed, 1);
+    assert_eq!(stats.total_volume, 4_000);
+    
+    // Solo los pares pueden reportar actividad
+    let outsider = Address::generate(&env);
+    assert!(registry.try_record_museum_activity(&outsider, &museum, &Symbol::new(&env, "sale"), &100).is_err());
+    assert!(registry.try_record_museum_activity(&nft_id, &museum, &Symbol::new(&env, "gift"), &0).is_err());
+}
+
+#[test]
+fn test_museum_collection_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let other_museum = Address::generate(&env);
+    let owner = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(&museum, &String::from_str(&env, "Museo Tumbas Reales de Sipán"), &String::from_str(&env, "Cultura Moche"), &Vec::new(&env), &None);
+    
+    let nft_id = env.register_contract(None, CulturalNFT);
+    let nft = CulturalNFTClient::new(&env, &nft_id);
+    nft.initialize(
+        &admin,
+        &String::from_str(&env, "PermaMuseum"),
+        &String::from_str(&env, "PMUS"),
+        &registry_id,
+        &None,
+    );
+    
+    // Sin contrato CulturalNFT configurado no se pueden calcular
+    assert!(registry.try_update_museum_collection_stats(&museum, &0, &10).is_err());
+    registry.set_cultural_nft(&nft_id);
+    
+    let mut metadata = CulturalMetadata {
+        title: String::from_str(&env, "Orejera de oro"),
+        artist: String::from_str(&env, "Desconocido"),
+        period: String::from_str(&env, "Moche"),
+        culture: String::from_str(&env, "Moche"),
+        material: String::from_str(&env, "Oro y turquesa"),
+        dimensions: String::from_str(&env, "9 cm"),
+        condition: String::from_str(&env, "Excelente"),
+        significance: String::from_str(&env, "Ajuar del Señor de Sipán"),
+        museum_address: museum.clone(),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    };
+    let tokens = [
+        (1u32, "Excelente", ["unesco", "original"]),
+        (2, "Excelente", ["unesco", "reproduction"]),
+        (3, "Regular", ["original", "moche"]),
+        (4, "Bueno", ["digital", "moche"]),
+    ];
+    for (token_id, condition, tags) in tokens {
+        metadata.condition = String::from_str(&env, condition);
+        nft.mint_cultural_nft(&owner, &token_id, &metadata, &Vec::new(&env));
+        let tags = Vec::from_array(&env, tags.map(|tag| Symbol::new(&env, tag)));
+        nft.set_tags(&museum, &token_id, &tags);
+    }
+    // Los tokens de otros museos no cuentan
+    metadata.museum_address = other_museum;
+    nft.mint_cultural_nft(&owner, &5, &metadata, &Vec::new(&env));
+    
+    // El recorrido avanza por páginas y solo publica el resultado al terminar
+    env.ledger().with_mut(|ledger| ledger.timestamp = 500);
+    assert_eq!(registry.update_museum_collection_stats(&museum, &0, &3), Some(3));
+    assert_eq!(registry.get_collection_stats(&museum).total_tokens, 0);
+    assert!(registry.try_update_museum_collection_stats(&museum, &2, &3).is_err());
+    assert_eq!(registry.update_museum_collection_stats(&museum, &3, &3), None);
+    assert!(registry.try_update_museum_collection_stats(&museum, &3, &3).is_err());
+    let expected = CollectionStats {
+        total_tokens: 4,
+        unesco_tokens: 2,
+        unesco_bps: 5_000,
+        original_tokens: 2,
+        reproduction_tokens: 1,
+        digital_tokens: 1,
+        condition_counts: Vec::from_array(
+            &env,
+            [
+                (String::from_str(&env, "Excelente"), 2),
+                (String::from_str(&env, "Regular"), 1),
+                (String::from_str(&env, "Bueno"), 1),
+            ],
+        ),
+        updated_at: 500,
+    };
+    assert_eq!(registry.get_collection_stats(&museum), expected);
+}
+
+#[test]
+fn test_buy_nft_with_points_discount_delivers_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    let nft_id = env.register_contract(None, CulturalNFT);
+    let nft = CulturalNFTClient::new(&env, &nft_id);
+    nft.initialize(
+        &admin,
+        &String::from_str(&env, "PermaMuseum"),
+        &String::from_str(&env, "PMUS"),
+        &Address::generate(&env),
+        &None,
+    );
+    let metadata = CulturalMetadata {
+        title: String::from_str(&env, "Keru ceremonial"),
+        artist: String::from_str(&env, "Desconocido"),
+        period: String::from_str(&env, "Inca"),
+        culture: String::from_str(&env, "Inca"),
+        material: String::from_str(&env, "Madera policromada"),
+        dimensions: String::from_str(&env, "18x15 cm"),
+        condition: String::from_str(&env, "Bueno"),
+        significance: String::from_str(&env, "Vaso ritual"),
+        museum_address: Address::generate(&env),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    };
+    nft.mint_cultural_nft(&seller, &1, &metadata, &Vec::new(&env));
+    
+    let socialfi_id = env.register_contract(None, SocialFi);
+    let socialfi = SocialFiClient::new(&env, &socialfi_id);
+    socialfi.initialize(&admin);
+    socialfi.award_points(&buyer, &120, &String::from_str(&env, "Visitas"));
+    
+    let asset = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = TokenClient::new(&env, &asset.address());
+    StellarAssetClient::new(&env, &asset.address()).mint(&buyer, &10_000);
+    
+    let market_id = env.register_contract(None, Marketplace);
+    let market = MarketplaceClient::new(&env, &market_id);
+    market.initialize(&admin, &250, &7);
+    market.set_payment_token(&asset.address());
+    
+    // 50 puntos dan un 20% de descuento
+    socialfi.set_points_spender(&market_id, &true);
+    market.set_points_discount(&socialfi_id, &50, &2_000);
+    
+    nft.approve(&seller, &market_id, &1);
+    market.list_nft(&seller, &nft_id, &1, &10_000, &None);
+    market.buy_nft_with_points(&buyer, &nft_id, &1);
+    
+    // Paga el precio reducido, gasta los puntos y recibe el token
+    assert_eq!(token.balance(&buyer), 2_000);
+    assert_eq!(token.balance(&seller), 8_000 - 200);
+    assert_eq!(socialfi.get_points_balance(&buyer), 70);
+    assert_eq!(nft.owner_of(&1), buyer);
+}
+
+#[test]
+fn test_conditional_listing_activates_on_verification() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(&museum, &String::from_str(&env, "Museo Tumbas Reales de Sipán"), &String::from_str(&env, "Cultura Moche"), &Vec::new(&env), &None);
+    
+    let nft_id = env.register_contract(None, CulturalNFT);
+    let nft = CulturalNFTClient::new(&env, &nft_id);
+    nft.initialize(
+        &admin,
+        &String::from_str(&env, "PermaMuseum"),
+        &String::from_str(&env, "PMUS"),
+        &registry_id,
+        &None,
+    );
+    let metadata = CulturalMetadata {
+        title: String::from_str(&env, "Orejera del Señor de Sipán"),
+        artist: String::from_str(&env, "Desconocido"),
+        period: String::from_str(&env, "Moche"),
+        culture: String::from_str(&env, "Moche"),
+        material: String::from_str(&env, "Oro y turquesa"),
+        dimensions: String::from_str(&env, "9,4 cm"),
+        condition: String::from_str(&env, "Excelente"),
+        significance: String::from_str(&env, "Ajuar funerario"),
+        museum_address: museum.clone(),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    };
+    nft.mint_cultural_nft(&museum, &1, &metadata, &Vec::new(&env));
+    
+    let market_id = env.register_contract(None, Marketplace);
+    let market = MarketplaceClient::new(&env, &market_id);
+    market.initialize(&admin, &250, &7);
+    market.set_museum_registry(&registry_id);
+    nft.approve(&museum, &market_id, &1);
+    
+    let condition = String::from_str(&env, "museum_verified");
+    assert!(market.try_create_conditional_listing(&museum, &nft_id, &1, &5_000, &String::from_str(&env, "sold_out"), &museum).is_err());
+    market.create_conditional_listing(&museum, &nft_id, &1, &5_000, &condition, &museum);
+    assert_eq!(market.get_conditional_listing(&nft_id, &1).unwrap().museum, museum);
+    
+    // Mientras el museo no esté verificado el listado no se activa
+    assert!(market.try_activate_conditional_listing(&keeper, &nft_id, &1).is_err());
+    assert!(market.find_listing(&nft_id, &1).is_none());
+    
+    // La verificación permite que cualquiera lo active
+    registry.verify_museum(&admin, &museum, &0);
+    market.activate_conditional_listing(&keeper, &nft_id, &1);
+    let listing = market.get_listing(&nft_id, &1);
+    assert!(listing.active);
+    assert_eq!(listing.price, 5_000);
+    assert_eq!(listing.seller, museum);
+    assert!(market.get_conditional_listing(&nft_id, &1).is_none());
+}
+
+#[test]
+fn test_mint_approval_requires_current_verification() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let museum = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let registry_id = env.register_contract(None, MuseumRegistry);
+    let registry = MuseumRegistryClient::new(&env, &registry_id);
+    registry.initialize(&admin);
+    registry.register_museum(&museum, &String::from_str(&env, "Museo Nacional Sicán"), &String::from_str(&env, "Cultura Lambayeque"), &Vec::new(&env), &None);
+    registry.verify_museum(&admin, &museum, &86_400);
+    
+    let nft_id = env.register_contract(None, CulturalNFT);
+    let nft = CulturalNFTClient::new(&env, &nft_id);
+    nft.initialize(
+        &admin,
+        &String::from_str(&env, "PermaMuseum"),
+        &String::from_str(&env, "PMUS"),
+        &registry_id,
+        &None,
+    );
+    let metadata = CulturalMetadata {
+        title: String::from_str(&env, "Tumi de Lambayeque"),
+        artist: String::from_str(&env, "Desconocido"),
+        period: String::from_str(&env, "Sicán Medio"),
+        culture: String::from_str(&env, "Lambayeque"),
+        material: String::from_str(&env, "Oro"),
+        dimensions: String::from_str(&env, "43 cm"),
+        condition: String::from_str(&env, "Buena"),
+        significance: String::from_str(&env, "Cuchillo ceremonial"),
+        museum_address: museum.clone(),
+        location: ArtifactLocation::Unknown,
+        is_soulbound: false,
+    };
+    nft.set_mint_request_ttl(&(7 * 86_400));
+    let expiring = nft.request_mint(&museum, &metadata, &Vec::new(&env));
+    let renewed = nft.request_mint(&museum, &metadata, &Vec::new(&env));
+    
+    // Una solicitud presentada antes de expirar la verificación no se aprueba después
+    env.ledger().with_mut(|li| li.timestamp = 1_000 + 86_400);
+    assert!(!registry.is_verified(&museum));
+    assert!(nft.try_approve_mint_request(&expiring).is_err());
+    
+    // Tras renovar la verificación la aprobación vuelve a funcionar
+    registry.renew_verification(&admin, &museum);
+    let token_id = nft.approve_mint_request(&renewed);
+    assert_eq!(nft.owner_of(&token_id), museum);
+    
+    // Revocar también bloquea las solicitudes pendientes
+    let revoked = nft.request_mint(&museum, &metadata, &Vec::new(&env));
+    registry.revoke_verification(&admin, &museum, &String::from_str(&env, "Licencia cancelada"));
+    assert!(nft.try_approve_mint_request(&revoked).is_err());
+}